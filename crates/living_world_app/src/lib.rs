@@ -0,0 +1,5790 @@
+//! Living World: an isometric ecosystem sandbox on Bevy.
+//!
+//! This crate is the desktop frontend: sprites, UI, input and the player's
+//! tools, on top of the rules in `living_world_core`. Add [`LivingWorldPlugin`]
+//! to an app to run both. Everything from the core is re-exported here, so
+//! events, [`SimulationSet`] and the [`LivingWorldAppExt`] callbacks work
+//! the same through this crate.
+
+// Bevy systems take every query and resource as an argument, so long signatures
+// and query types are normal here; nested `if`s are kept where they read better
+#![allow(clippy::type_complexity, clippy::too_many_arguments, clippy::collapsible_if)]
+
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized};
+use bevy::camera::Viewport;
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::ui::{ComputedNode, IsDefaultUiCamera, RelativeCursorPosition};
+use bevy::input::keyboard::{KeyboardInput, Key};
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::ecs::prelude::ChildSpawnerCommands;
+use bevy::ecs::system::SystemParam;
+use bevy::diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, Diagnostics, DiagnosticsStore, FrameTimeDiagnosticsPlugin, RegisterDiagnostic};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub use living_world_core::*;
+
+// Constants
+const TILE_WIDTH: f32 = 64.0;
+const TILE_HEIGHT: f32 = 32.0;
+//const MAP_SIZE: i32 = 20;
+
+// Grass shades from bare earth to lush green; worn paths stay dirt
+fn land_color(footfall: &Footfall, shaded: bool, grass: f32) -> Color {
+    let color = if footfall.dirt {
+        Color::srgb(0.45, 0.35, 0.2)
+    } else {
+        let g = grass.clamp(0.0, 1.0);
+        Color::srgb(0.42 - 0.14 * g, 0.4 + 0.14 * g, 0.25 + 0.05 * g)
+    };
+    if shaded { color.darker(0.08) } else { color }
+}
+
+// Scent overlay: tiles are tinted by how much of the channel lies on them
+#[derive(Resource, Default)]
+struct PheromoneOverlay(Option<Pheromone>);
+
+// Middle click picks a creature: the inspector follows it and its move
+// decisions are drawn over the map
+#[derive(Resource, Default)]
+struct SelectedCreature(Option<Entity>);
+
+// Followed by the picture-in-picture camera in the corner (V pins the selected creature)
+#[derive(Resource, Default)]
+struct PinnedCreature(Option<Entity>);
+
+// The camera the player zooms and the UI draws on; cursor picking goes through it
+#[derive(Component)]
+struct MainCamera;
+
+#[derive(Component)]
+struct PipCamera;
+
+// Text above a creature: its name and status icons (see update_creature_labels)
+#[derive(Component)]
+struct CreatureLabel;
+
+#[derive(Component)]
+struct Labelled(Entity);
+
+// I: every creature's target arrow, not just the selected one's
+#[derive(Resource, Default)]
+struct IntentOverlay(bool);
+
+fn intent_color(goal: Goal) -> Color {
+    match goal {
+        Goal::Food => Color::srgb(0.2, 0.9, 0.2),
+        Goal::Mate => Color::srgb(1.0, 0.45, 0.75),
+        Goal::Prey => Color::srgb(0.95, 0.15, 0.15),
+        Goal::Flee => Color::srgb(0.25, 0.45, 1.0),
+        Goal::Shelter => Color::srgb(0.6, 0.5, 0.35),
+        Goal::Fish => Color::srgb(0.2, 0.85, 0.85),
+        Goal::Mother => Color::srgb(1.0, 0.9, 0.5),
+    }
+}
+
+fn pheromone_color(channel: Pheromone) -> Color {
+    match channel {
+        Pheromone::Trail => Color::srgb(1.0, 0.85, 0.2),
+        Pheromone::Territory => Color::srgb(0.8, 0.1, 0.6),
+    }
+}
+
+// This tags the floating highlight box
+#[derive(Component)]
+struct MapCursor;
+
+fn gate_color(allowed: Option<u32>) -> Color {
+    match allowed {
+        Some(0) => Color::srgb(0.9, 0.9, 0.85),
+        Some(_) => Color::srgb(0.3, 0.15, 0.05),
+        None => Color::srgb(0.75, 0.6, 0.35),
+    }
+}
+
+// Shallows are light blue, deep water darkens towards navy
+fn water_color(depth: f32, cfg: &SimulationConfig) -> Color {
+    let t = (depth / cfg.max_water_depth).clamp(0.0, 1.0);
+    Color::srgb(0.35 - 0.25 * t, 0.55 - 0.4 * t, 0.9 - 0.2 * t)
+}
+
+// Which terrain tool the mouse buttons currently apply
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
+enum PlayerTool {
+    #[default]
+    Water,
+    Bridge,
+    Crop,
+    Fence,
+    Gate,
+    Shelter,
+    Harvest,
+    Clone,
+}
+
+impl PlayerTool {
+    fn label(&self) -> &'static str {
+        match self {
+            PlayerTool::Water => "Water",
+            PlayerTool::Bridge => "Bridge",
+            PlayerTool::Crop => "Crop",
+            PlayerTool::Fence => "Fence",
+            PlayerTool::Gate => "Gate",
+            PlayerTool::Shelter => "Shelter",
+            PlayerTool::Harvest => "Harvest",
+            PlayerTool::Clone => "Clone",
+        }
+    }
+}
+
+#[derive(Component)]
+struct BloodStain(Timer);
+
+#[derive(Component)]
+struct StatsText;
+
+// Frame-time history next to the FPS line, one bar per frame
+const SPARKLINE_FRAMES: usize = 60;
+
+#[derive(Component)]
+struct FrameSparkBar(usize); // 0 = oldest
+
+#[derive(Component)]
+struct ChartTextHealthy; // White count
+
+#[derive(Component)]
+struct ChartTextHungry;  // Yellow count
+
+#[derive(Component)]
+struct ChartTextCritical; // Red count
+
+// Age pyramid in the demographics panel: sheep bars grow left, wolves right
+const PYRAMID_BUCKETS: usize = 6;
+const PYRAMID_BUCKET_DAYS: f32 = 4.0;
+const PYRAMID_HALF_WIDTH: f32 = 45.0;
+
+#[derive(Component)]
+struct PyramidBar {
+    bucket: usize, // 0 = youngest
+    species_id: u32,
+}
+
+#[derive(Component)]
+struct NotificationText;
+
+#[derive(Component)]
+struct BudgetBannerText;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CompassTarget {
+    Selected,
+    NearestPack, // of a species that hunts
+    LargestHerd, // of one that doesn't
+}
+
+impl CompassTarget {
+    fn color(&self) -> Color {
+        match self {
+            CompassTarget::Selected => Color::srgb(1.0, 1.0, 0.2),
+            CompassTarget::NearestPack => Color::srgb(1.0, 0.5, 0.3),
+            CompassTarget::LargestHerd => Color::srgb(0.9, 0.9, 0.9),
+        }
+    }
+}
+
+#[derive(Component)]
+struct CompassMarker(CompassTarget);
+
+// Bottom-left readout for the creature under the cursor
+#[derive(Component)]
+struct InspectorText;
+
+#[derive(Component)]
+struct SpeciesStatsSheepText;
+
+#[derive(Component)]
+struct SpeciesStatsWolfText;
+
+// The species panel's row of columns; split-off species get a column each
+#[derive(Component)]
+struct SpeciesStatsRow;
+
+#[derive(Component)]
+struct CladeColumn(u32);
+
+#[derive(Component)]
+struct CladeStatsText(u32);
+
+#[derive(Component)]
+struct DebugPanelRoot;
+
+// The debug panel's rows sit in up to DEBUG_COLUMNS of these, side by side
+#[derive(Component)]
+struct DebugColumn(usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum ConfigField {
+    PlantSpawnChance,
+    SheepStartCount,
+    WolfStartCount,
+    SheepAdultSeconds,
+    WolfAdultSeconds,
+    SheepTimeScale,
+    WolfTimeScale,
+    WorldCode, // not a config value: the new-game menu's code box
+}
+
+#[derive(Component)]
+struct Slider {
+    field: ConfigField,
+    min: f32,
+    max: f32,
+}
+
+#[derive(Component)]
+struct SliderKnob {
+    field: ConfigField,
+}
+
+#[derive(Component)]
+struct SliderValueText {
+    field: ConfigField,
+}
+
+#[derive(Component)]
+struct TextBox {
+    field: ConfigField,
+}
+
+#[derive(Component)]
+struct TextBoxText {
+    field: ConfigField,
+}
+
+#[derive(Resource, Default)]
+struct TextBoxFocus {
+    active: Option<ConfigField>,
+    buffer: String,
+}
+
+
+
+#[derive(Component)]
+struct WorldShadow {
+    phase: f32,
+    drift_a: f32,
+    drift_b: f32,
+    base_y: f32,
+}
+
+// ========================
+// PLUGIN
+// ========================
+/// The simulation plus everything you see: sprites, UI, input. Add it next
+/// to `DefaultPlugins`.
+#[derive(Default)]
+pub struct LivingWorldPlugin {
+    /// Preset id from `assets/presets/` to start with
+    pub preset: Option<String>,
+    /// Challenge from `assets/scenarios/` to start with (overrides `preset`)
+    pub scenario: Option<String>,
+    /// Map file from `MAP_DIR` to start the first game on
+    pub map: Option<String>,
+    /// Seed for every new game; `None` picks a fresh one each time
+    pub seed: Option<u64>,
+}
+
+impl Plugin for LivingWorldPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+        app
+            .add_plugins(SimulationPlugin {
+                preset: self.preset.clone(),
+                scenario: self.scenario.clone(),
+                map: self.map.clone(),
+                seed: self.seed,
+                ..default()
+            })
+            .insert_resource(Milestones::load())
+            .init_resource::<Snapshots>()
+            .init_resource::<MapFiles>()
+            .init_resource::<CloneSource>()
+            .init_resource::<RewindBuffer>()
+            .init_resource::<Ghosts>()
+            .init_non_send_resource::<WorldTabs>()
+            .init_resource::<SplitScreen>()
+            .init_resource::<CreatureFiles>()
+            .init_resource::<PlayerTool>()
+            .init_resource::<LodState>()
+            .init_resource::<UiRefresh>()
+            .init_resource::<PheromoneOverlay>()
+            .init_resource::<SelectedCreature>()
+            .init_resource::<PinnedCreature>()
+            .init_resource::<IntentOverlay>()
+
+            .add_systems(Startup, (setup, setup_chart, setup_debug_panel, setup_new_game_menu, setup_snapshot_panel, setup_milestones_page, setup_records_panel, setup_behavior_editor, setup_timeline_panel, setup_day_digest, setup_seasons_panel, setup_scenario_results, setup_profiler_panel, setup_genetics_panel, setup_clone_panel, setup_world_tabs, setup_split_screen).chain())
+
+            // Everything that changes the world runs with the simulation
+            .add_systems(Update, (
+                // Debug panel (config tweaking is a sandbox-only god power)
+                (
+                    (toggle_debug_panel, debug_slider_system, debug_textbox_system, gene_edit_buttons, drag_creature, scatter_button).run_if(in_state(GameMode::Sandbox)),
+                    (toggle_behavior_editor, behavior_editor_buttons).run_if(in_state(GameMode::Sandbox)),
+                    debug_panel_visibility,
+                ),
+
+                // Player input
+                (
+                    (select_player_tool, cursor_system, harvest_tool_system, call_rain, smite_selected, toggle_shepherd, move_shepherd).run_if(not(in_state(GameMode::Observer))),
+                    (new_game_menu_system, world_code_system, save_map_button, toggle_milestones_page, toggle_records_panel, toggle_timeline_panel, toggle_seasons_panel, toggle_observer_mode, toggle_management_mode, toggle_experiment_mode, save_load_input, snapshot_panel_system, rewind_input, toggle_ghosts),
+                ),
+
+                toggle_governor.run_if(in_state(GameMode::Sandbox)).before(balance_governor_system),
+                ((clone_tool_system, clone_panel_buttons).run_if(in_state(GameMode::Sandbox)), update_clone_panel),
+                toggle_profiler,
+                (toggle_world_tabs, world_tab_buttons, step_world_tabs),
+                (toggle_genetics_panel, export_genetics_csv, export_family_tree, export_map_png),
+                cycle_pheromone_overlay,
+                select_creature,
+                toggle_pin,
+                intent_overlay_system,
+                camera_zoom,
+                tick_ui_refresh,
+            ).in_set(SimulationSet::Simulate))
+
+            // Drawing only reads it
+            .add_systems(Update, (
+                // World
+                (
+                    (attach_world_sprites, attach_corpse_sprites),
+                    update_lod,
+                    (update_tile_colors, update_crop_sprites, update_gate_sprites, update_creature_looks, sync_creature_visuals),
+                    (update_pip_camera, update_creature_labels),
+                ).chain(),
+                (draw_decision_gizmos, draw_planned_path, draw_intent_arrows, draw_perception_gizmos, draw_hunger_bars, draw_trails, draw_ghosts),
+
+                // Event listeners
+                ((kill_fx_system, cap_blood_stains).chain(), fade_blood_stains, clear_world_fx, track_milestones, record_rewind, record_ghost),
+
+                // UI
+                (update_stats_ui, update_species_stats_ui, update_clade_columns, update_chart_ui, update_age_pyramid, update_inspector_ui, update_genetics_panel).run_if(ui_refresh_due),
+                (update_notifications_ui, update_milestones_page, update_records_panel, update_behavior_editor, update_timeline_panel, update_day_digest, update_seasons_panel, update_scenario_results, update_snapshot_list, update_map_list, update_profiler_panel, update_frame_sparkline, update_budget_banner, update_edge_compass, update_world_tabs, update_split_screen),
+
+                // Layout
+                (ui_scale_system, fit_panels_to_window, reflow_debug_panel).chain(),
+            ).in_set(SimulationSet::Observe))
+
+            .add_systems(Startup, spawn_world_shadow)
+            .add_systems(Update, animate_world_shadow);
+
+        add_profiler(app);
+    }
+}
+
+
+fn setup(mut commands: Commands) {
+    // 1. Initialize Game Stats Resource (Day 0)
+    //commands.insert_resource(GameStats { days: 0.0 });
+
+    // NEW: Init population stats
+    //commands.insert_resource(PopulationStats::default());
+
+    // 2. Spawn Camera
+    let mut camera_transform = Transform::from_xyz(0.0, 0.0, 800.0);
+    camera_transform.scale = Vec3::new(1.5, 1.5, 1.0);
+    commands.spawn((Camera2d, camera_transform, MainCamera, IsDefaultUiCamera));
+
+    // Picture-in-picture: drawn over the main view, off until a creature is pinned
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 1,
+            is_active: false,
+            clear_color: ClearColorConfig::Custom(Color::srgb(0.05, 0.05, 0.08)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 800.0).with_scale(Vec3::new(0.8, 0.8, 1.0)),
+        PipCamera,
+    ));
+
+    // 3. Spawn Cursor
+    commands.spawn((
+        Sprite::from_color(Color::srgba(1.0, 0.0, 0.0, 0.5), Vec2::new(TILE_WIDTH, TILE_HEIGHT)),
+        Transform::from_xyz(0.0, 0.0, 1.0),
+        MapCursor,
+    ));
+
+    // 4. Spawn UI Text (Top-Left) - general world stats (keep yours)
+    commands.spawn((
+        Text::new("Stats: Loading..."),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        StatsText,
+    ));
+
+    // Frame-time sparkline, right of the FPS line
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(210.0),
+            width: Val::Px(SPARKLINE_FRAMES as f32 * 2.0),
+            height: Val::Px(18.0),
+            align_items: AlignItems::FlexEnd,
+            ..default()
+        })
+        .insert(BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)))
+        .with_children(|line| {
+            for i in 0..SPARKLINE_FRAMES {
+                line.spawn((
+                    Node { width: Val::Px(2.0), height: Val::Percent(0.0), ..default() },
+                    BackgroundColor(Color::srgb(0.3, 0.8, 0.3)),
+                    FrameSparkBar(i),
+                ));
+            }
+        });
+
+    // Notifications (Top-Center)
+    commands.spawn((
+        Text::new(""),
+        TextFont { font_size: 18.0, ..default() },
+        TextColor(Color::srgb(1.0, 0.85, 0.4)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Percent(40.0),
+            ..default()
+        },
+        NotificationText,
+    ));
+
+    // Entity budget warnings (below the notifications)
+    commands.spawn((
+        Text::new(""),
+        TextFont { font_size: 16.0, ..default() },
+        TextColor(Color::srgb(1.0, 0.4, 0.3)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(90.0),
+            left: Val::Percent(40.0),
+            ..default()
+        },
+        BudgetBannerText,
+    ));
+
+    // Edge compass: points at notable creatures that are off screen
+    for target in [CompassTarget::Selected, CompassTarget::NearestPack, CompassTarget::LargestHerd] {
+        commands.spawn((
+            Text::new(""),
+            TextFont { font_size: 14.0, ..default() },
+            TextColor(target.color()),
+            Node { position_type: PositionType::Absolute, display: Display::None, ..default() },
+            CompassMarker(target),
+        ));
+    }
+
+    // Creature inspector (Bottom-Left), empty unless the cursor is over a creature
+    commands.spawn((
+        Text::new(""),
+        TextFont { font_size: 16.0, ..default() },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        InspectorText,
+    ));
+
+    // 5. NEW: Species Stats Panel (Top-Left, below general stats)
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(280.0),
+            left: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(10.0)),
+            column_gap: Val::Px(25.0),
+            flex_direction: FlexDirection::Row, // columns side-by-side
+            ..default()
+        })
+        .insert((BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)), SpeciesStatsRow))
+        .with_children(|parent| {
+            // ---- COLUMN 1: Sheep ----
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|col| {
+                    col.spawn((
+                        Text::new("Sheep"),
+                        TextFont { font_size: 16.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                    col.spawn((
+                        Text::new("Born: 0\nCurrent: 0\nTotal Ever: 0\nExtinctions: 0"),
+                        TextFont { font_size: 14.0, ..default() },
+                        SpeciesStatsSheepText,
+                    ));
+                });
+
+            // ---- COLUMN 2: Wolves ----
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|col| {
+                    col.spawn((
+                        Text::new("Wolves"),
+                        TextFont { font_size: 16.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                    col.spawn((
+                        Text::new("Born: 0\nCurrent: 0\nTotal Ever: 0\nExtinctions: 0"),
+                        TextFont { font_size: 14.0, ..default() },
+                        SpeciesStatsWolfText,
+                    ));
+                });
+        });
+}
+
+// F5 quicksave, F9 quickload, plus the autosave timer
+fn save_load_input(
+    mut commands: Commands,
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut since_autosave: Local<f32>,
+    snapshot: SnapshotQueries,
+    mut notifications: ResMut<Notifications>,
+) {
+    let cfg = &snapshot.cfg;
+
+    if keys.just_pressed(KeyCode::F5) {
+        write_save(snapshot.capture(), "quicksave", cfg.save_format, cfg.save_compression_level);
+        notifications.push("Quicksaved".to_string(), cfg);
+    }
+
+    *since_autosave += time.delta().as_secs_f32();
+    if cfg.autosave_seconds > 0.0 && *since_autosave >= cfg.autosave_seconds {
+        *since_autosave = 0.0;
+        write_save(snapshot.capture(), "autosave", cfg.save_format, cfg.save_compression_level);
+    }
+
+    if keys.just_pressed(KeyCode::F9) {
+        let Some((path, format)) = find_save("quicksave") else {
+            notifications.push("No quicksave yet".to_string(), cfg);
+            return;
+        };
+        let loaded = std::fs::read(&path).map_err(|e| e.to_string()).and_then(|bytes| WorldSnapshot::decode(&bytes, format));
+        match loaded {
+            Ok(snapshot) => commands.insert_resource(LoadRequest { snapshot, label: "quicksave".to_string() }),
+            Err(err) => {
+                println!("Could not load {}: {}", path.display(), err);
+                notifications.push("Quickload failed".to_string(), cfg);
+            }
+        }
+    }
+}
+
+// --- REWIND (Z) ---
+// The last rewind_seconds of sim time as a snapshot every rewind_step_seconds.
+// Z steps back to the previous one; playing on from there forks the timeline:
+// the points after it are gone and new ones are recorded from the fork. A new
+// game or load starts the buffer over.
+#[derive(Resource, Default)]
+struct RewindBuffer {
+    points: VecDeque<WorldSnapshot>,
+    rewinding: bool, // the next WorldCleared is our own load
+}
+
+fn record_rewind(
+    capture: SnapshotQueries,
+    mut buffer: ResMut<RewindBuffer>,
+    mut cleared: MessageReader<WorldCleared>,
+    new_game: Option<Res<NewGameRequest>>,
+    load: Option<Res<LoadRequest>>,
+) {
+    if cleared.read().count() > 0 && !std::mem::take(&mut buffer.rewinding) {
+        buffer.points.clear();
+    }
+    let cfg = &capture.cfg;
+    if cfg.rewind_seconds <= 0.0 {
+        buffer.points.clear();
+        return;
+    }
+    if new_game.is_some() || load.is_some() { return; }
+
+    let now = capture.totals.stats.days * SECONDS_PER_DAY;
+    let seconds = |s: &WorldSnapshot| s.days * SECONDS_PER_DAY;
+    // Points after the clock belong to a timeline that was rewound away from
+    while buffer.points.back().is_some_and(|s| seconds(s) > now) {
+        buffer.points.pop_back();
+    }
+    if buffer.points.back().is_none_or(|s| now - seconds(s) >= cfg.rewind_step_seconds) {
+        buffer.points.push_back(capture.capture());
+    }
+    while buffer.points.front().is_some_and(|s| now - seconds(s) > cfg.rewind_seconds) {
+        buffer.points.pop_front();
+    }
+}
+
+fn rewind_input(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    mode: Res<State<GameMode>>,
+    mut buffer: ResMut<RewindBuffer>,
+    mut notifications: ResMut<Notifications>,
+    load: Option<Res<LoadRequest>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyZ) || load.is_some() { return; }
+    // Loading ends a challenge, so there's no rewinding one
+    if *mode.get() == GameMode::Challenge {
+        notifications.push("No rewinding during a challenge".to_string(), &cfg);
+        return;
+    }
+
+    // A point taken a moment ago is where we already are
+    let now = stats.days * SECONDS_PER_DAY;
+    while buffer.points.back().is_some_and(|s| now - s.days * SECONDS_PER_DAY < cfg.rewind_step_seconds * 0.5) {
+        buffer.points.pop_back();
+    }
+    let Some(snapshot) = buffer.points.pop_back() else {
+        notifications.push("Nothing to rewind to yet".to_string(), &cfg);
+        return;
+    };
+    let label = format!("{:.0}s back", now - snapshot.days * SECONDS_PER_DAY);
+    buffer.rewinding = true;
+    commands.insert_resource(LoadRequest { snapshot, label });
+}
+
+// --- GHOSTS (Y) ---
+// The live run is recorded as a GhostRun. When a new game starts the finished
+// run goes to GHOST_DIR, and the new game picks up the last run its seed had:
+// Y shows it as translucent creatures at the same sim time. Change the config,
+// start again from the same seed (--seed or a world code) and watch where the
+// two timelines part.
+#[derive(Resource, Default)]
+struct Ghosts {
+    recording: Option<GhostRun>, // this run so far
+    ghost: Option<GhostRun>,     // an earlier run from the same seed
+    shown: bool,
+    frame: Option<usize>, // what the sprites show now, so they're only respawned on a new frame
+}
+
+#[derive(Component)]
+struct GhostSprite;
+
+fn record_ghost(
+    cfg: Res<SimulationConfig>,
+    rng: Res<SimRng>,
+    stats: Res<GameStats>,
+    mut ghosts: ResMut<Ghosts>,
+    mut notifications: ResMut<Notifications>,
+    new_game: Option<Res<NewGameRequest>>,
+    q_creatures: Query<(&GridPosition, &CreatureStats), (With<Creature>, Without<Dead>)>,
+) {
+    // A run ends with a new game, or a load from another seed
+    if new_game.is_some() || ghosts.recording.as_ref().is_some_and(|r| r.seed != rng.seed) {
+        if let Some(run) = ghosts.recording.take().filter(|r| !r.frames.is_empty()) {
+            if let Err(err) = run.save() {
+                println!("Ghost not saved: {}", err);
+            }
+            ghosts.ghost = Some(run);
+        }
+    }
+    if new_game.is_some() { return; }
+
+    let recording = match ghosts.recording.take() {
+        Some(recording) => recording,
+        None => {
+            if ghosts.ghost.as_ref().is_none_or(|g| g.seed != rng.seed) {
+                ghosts.ghost = GhostRun::load(rng.seed).ok();
+            }
+            if let Some(ghost) = &ghosts.ghost {
+                let shown = if ghosts.shown { "shown" } else { "Y to show it" };
+                notifications.push(format!("Ghost of an earlier run ({:.0} days, {})", ghost.seconds() / SECONDS_PER_DAY, shown), &cfg);
+            }
+            ghosts.frame = None;
+            GhostRun::new(rng.seed, cfg.ghost_step_seconds)
+        }
+    };
+    let recording = ghosts.recording.insert(recording);
+    recording.record(stats.days * SECONDS_PER_DAY, || {
+        let mut frame: Vec<(u32, (i32, i32))> = q_creatures.iter().map(|(pos, creature)| (creature.species_id, (pos.x, pos.y))).collect();
+        frame.sort_unstable();
+        frame
+    });
+}
+
+fn toggle_ghosts(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    cfg: Res<SimulationConfig>,
+    mut ghosts: ResMut<Ghosts>,
+    mut notifications: ResMut<Notifications>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyY) { return; }
+    if ghosts.ghost.is_none() {
+        notifications.push("No earlier run from this seed".to_string(), &cfg);
+        return;
+    }
+    ghosts.shown = !ghosts.shown;
+    ghosts.frame = None;
+    notifications.push(format!("Ghosts {}", if ghosts.shown { "on" } else { "off" }), &cfg);
+}
+
+// Respawned whenever the ghost run moves on a frame; gone past its end
+fn draw_ghosts(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    lod: Res<LodState>,
+    mut ghosts: ResMut<Ghosts>,
+    q_sprites: Query<Entity, With<GhostSprite>>,
+) {
+    let seconds = stats.days * SECONDS_PER_DAY;
+    let frame = match &ghosts.ghost {
+        Some(ghost) if ghosts.shown && !lod.active => Some(ghost.frame_index(seconds)).filter(|&i| i < ghost.frames.len()),
+        _ => None,
+    };
+    if frame == ghosts.frame { return; }
+    ghosts.frame = frame;
+
+    for entity in q_sprites.iter() {
+        commands.entity(entity).despawn();
+    }
+    let (Some(i), Some(ghost)) = (frame, &ghosts.ghost) else { return; };
+    for &(species_id, (x, y)) in &ghost.frames[i] {
+        let at = iso(&cfg, x, y);
+        let look = &cfg.s(species_id).appearance;
+        let [r, g, b] = look.color;
+        commands.spawn((
+            Sprite::from_color(Color::srgba(r, g, b, 0.3), Vec2::splat(look.size)),
+            Transform::from_xyz(at.x, at.y, 1.9),
+            GhostSprite,
+        ));
+    }
+}
+
+// --- WORLD TABS (W) ---
+// Several worlds in one window. The tab on screen is the live world; the rest
+// keep running as headless Simulations on the same sim clock (pausing and the
+// speed setting hold for all of them). Switching puts the live world into its
+// tab's Simulation and loads the other tab's. "Fork" copies the live world into
+// a new tab: change the config on one and flip between them.
+struct WorldTab {
+    name: String,
+    sim: Option<Simulation>, // None for the tab on screen
+}
+
+// A non-send resource: the Simulations' Apps have to stay on the main thread
+struct WorldTabs {
+    tabs: Vec<WorldTab>,
+    active: usize,
+    opened: u32, // for names, so they stay unique after closes
+}
+
+impl Default for WorldTabs {
+    fn default() -> Self {
+        Self { tabs: vec![WorldTab { name: "World 1".to_string(), sim: None }], active: 0, opened: 1 }
+    }
+}
+
+#[derive(Component)]
+struct WorldTabsRoot;
+
+#[derive(Component)]
+struct WorldTabList;
+
+#[derive(Component)]
+struct WorldTabButton(usize);
+
+#[derive(Component, Clone, Copy, PartialEq)]
+enum WorldTabAction {
+    Fork,
+    Close,
+    Split,
+}
+
+fn setup_world_tabs(mut commands: Commands) {
+    commands
+        .spawn((
+            WorldTabsRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(40.0),
+                bottom: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                column_gap: Val::Px(6.0),
+                flex_direction: FlexDirection::Row,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            Visibility::Hidden,
+            Interaction::default(),
+            GlobalZIndex(1), // stays over the split screen
+        ))
+        .with_children(|p| {
+            p.spawn((
+                WorldTabList,
+                Node {
+                    column_gap: Val::Px(4.0),
+                    flex_direction: FlexDirection::Row,
+                    ..default()
+                },
+            ));
+            let buttons = [
+                ("Fork", Color::srgb(0.2, 0.3, 0.2), WorldTabAction::Fork),
+                ("Split", Color::srgb(0.2, 0.25, 0.35), WorldTabAction::Split),
+                ("Close", Color::srgb(0.35, 0.15, 0.15), WorldTabAction::Close),
+            ];
+            for (label, color, action) in buttons {
+                p.spawn((
+                    action,
+                    Button,
+                    Node {
+                        padding: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                ))
+                .with_children(|b| {
+                    b.spawn((
+                        Text::new(label),
+                        TextFont { font_size: 13.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                });
+            }
+        });
+}
+
+fn toggle_world_tabs(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut q_panel: Query<&mut Visibility, With<WorldTabsRoot>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyW) { return; }
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+    *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+}
+
+fn world_tab_buttons(
+    mut commands: Commands,
+    capture: SnapshotQueries,
+    rng: Res<SimRng>,
+    mode: Res<State<GameMode>>,
+    mut tabs: NonSendMut<WorldTabs>,
+    mut split: ResMut<SplitScreen>,
+    mut notifications: ResMut<Notifications>,
+    busy: (Option<Res<LoadRequest>>, Option<Res<NewGameRequest>>),
+    q_tabs: Query<(&Interaction, &WorldTabButton), Changed<Interaction>>,
+    q_actions: Query<(&Interaction, &WorldTabAction), Changed<Interaction>>,
+) {
+    let picked = q_tabs.iter().find(|(i, _)| **i == Interaction::Pressed).map(|(_, b)| b.0);
+    let action = q_actions.iter().find(|(i, _)| **i == Interaction::Pressed).map(|(_, a)| *a);
+    if picked.is_none() && action.is_none() { return; }
+    if busy.0.is_some() || busy.1.is_some() { return; }
+    let cfg = &capture.cfg;
+
+    // With one world there's nothing to compare with yet, so fork one
+    if action == Some(WorldTabAction::Split) {
+        split.on = !split.on;
+        if !split.on || tabs.tabs.len() > 1 { return; }
+    }
+    if matches!(action, Some(WorldTabAction::Fork | WorldTabAction::Split)) {
+        if tabs.tabs.len() >= cfg.world_tab_limit.max(1) {
+            notifications.push(format!("{} worlds open already", tabs.tabs.len()), cfg);
+            return;
+        }
+        tabs.opened += 1;
+        let name = format!("World {}", tabs.opened);
+        notifications.push(format!("Forked {} into {}", tabs.tabs[tabs.active].name, name), cfg);
+        let sim = Simulation::from_snapshot(capture.capture(), rng.seed);
+        tabs.tabs.push(WorldTab { name, sim: Some(sim) });
+        return;
+    }
+
+    // Switching loads a world, and loading ends a challenge
+    if *mode.get() == GameMode::Challenge {
+        notifications.push("No switching worlds during a challenge".to_string(), cfg);
+        return;
+    }
+    // Closing shows the next tab over (the previous one for the last tab)
+    let active = tabs.active;
+    let to = match picked {
+        Some(i) if i != active && i < tabs.tabs.len() => i,
+        Some(_) => return,
+        None if tabs.tabs.len() > 1 => if active + 1 < tabs.tabs.len() { active + 1 } else { active - 1 },
+        None => {
+            notifications.push("The last world can't be closed".to_string(), cfg);
+            return;
+        }
+    };
+    let Some(mut target) = tabs.tabs[to].sim.take() else { return; };
+    let snapshot = target.snapshot();
+    let label = tabs.tabs[to].name.clone();
+    if action == Some(WorldTabAction::Close) {
+        tabs.tabs.remove(active);
+        tabs.active = if to > active { to - 1 } else { to };
+    } else {
+        target.load(capture.capture());
+        tabs.tabs[active].sim = Some(target);
+        tabs.active = to;
+    }
+    commands.insert_resource(LoadRequest { snapshot, label });
+}
+
+// Background worlds owe whatever sim time went by on screen, all of it paid
+// each frame so they keep the same clock
+fn step_world_tabs(time: Res<Time>, mut tabs: NonSendMut<WorldTabs>, mut owed: Local<f32>) {
+    if tabs.tabs.len() < 2 {
+        *owed = 0.0;
+        return;
+    }
+    *owed += time.delta_secs() * TICKS_PER_SECOND as f32;
+    let ticks = owed.floor();
+    *owed -= ticks;
+    // Only switching and forking count as a change to the tab bar
+    for sim in tabs.bypass_change_detection().tabs.iter_mut().filter_map(|t| t.sim.as_mut()) {
+        sim.run(ticks as u64);
+    }
+}
+
+fn update_world_tabs(mut commands: Commands, tabs: NonSend<WorldTabs>, q_list: Query<Entity, With<WorldTabList>>) {
+    if !tabs.is_changed() { return; }
+    let Ok(list) = q_list.single() else { return; };
+
+    commands.entity(list).despawn_related::<Children>();
+    commands.entity(list).with_children(|p| {
+        for (i, tab) in tabs.tabs.iter().enumerate() {
+            let color = if i == tabs.active { Color::srgb(0.25, 0.35, 0.5) } else { Color::srgb(0.15, 0.15, 0.2) };
+            p.spawn((
+                WorldTabButton(i),
+                Button,
+                Node {
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(color),
+            ))
+            .with_children(|b| {
+                b.spawn((
+                    Text::new(tab.name.clone()),
+                    TextFont { font_size: 13.0, ..default() },
+                    TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                ));
+            });
+        }
+    });
+}
+
+// --- SPLIT SCREEN ---
+// "Split" in the world tabs: the tab after the one on screen takes the right
+// half of the window as a top-down map, redrawn every split_refresh_seconds,
+// with both worlds' numbers in a strip underneath. The tabs share one sim clock,
+// so pausing and the speed setting hold for both sides.
+#[derive(Resource, Default)]
+struct SplitScreen {
+    on: bool,
+}
+
+#[derive(Component)]
+struct SplitPanelRoot;
+
+#[derive(Component)]
+struct SplitMap;
+
+#[derive(Component)]
+struct SplitTitle;
+
+#[derive(Component)]
+struct SplitStrip;
+
+fn setup_split_screen(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let text = |text: &str, size: f32| (Text::new(text), TextFont { font_size: size, ..default() }, TextColor(Color::srgb(1.0, 1.0, 1.0)));
+    commands
+        .spawn((
+            SplitPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(0.0),
+                top: Val::Px(0.0),
+                width: Val::Percent(50.0),
+                height: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(8.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.05, 0.05, 0.08)),
+            Visibility::Hidden,
+            Interaction::default(),
+        ))
+        .with_children(|p| {
+            p.spawn((SplitTitle, text("", 16.0)));
+            p.spawn((
+                SplitMap,
+                ImageNode::new(images.add(Image::default())),
+                Node {
+                    flex_grow: 1.0,
+                    max_width: Val::Percent(100.0),
+                    aspect_ratio: Some(1.0),
+                    ..default()
+                },
+            ));
+            p.spawn((SplitStrip, text("", 14.0)));
+        });
+}
+
+// A line per world: its clock, each species' head count and the plants
+fn split_strip_line(name: &str, cfg: &SimulationConfig, days: f32, population: &HashMap<u32, u32>, plants: usize) -> String {
+    let mut ids: Vec<u32> = cfg.species.keys().copied().collect();
+    ids.sort_unstable();
+    let counts: Vec<String> = ids.iter().map(|id| format!("{} {}", cfg.s(*id).name, population.get(id).copied().unwrap_or(0))).collect();
+    format!("{}: day {:.1} | {} | plants {}", name, days, counts.join(", "), plants)
+}
+
+fn update_split_screen(
+    time: Res<Time<Real>>,
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    split: Res<SplitScreen>,
+    mut tabs: NonSendMut<WorldTabs>,
+    mut images: ResMut<Assets<Image>>,
+    mut since: Local<f32>,
+    mut q_panel: Query<&mut Visibility, With<SplitPanelRoot>>,
+    q_map: Query<&ImageNode, With<SplitMap>>,
+    mut q_text: ParamSet<(Query<&mut Text, With<SplitTitle>>, Query<&mut Text, With<SplitStrip>>)>,
+    q_creatures: Query<&CreatureStats, (With<Creature>, Without<Dead>)>,
+    q_plants: Query<(), (With<Plant>, Without<Dead>)>,
+) {
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+    let shown = split.on && tabs.tabs.len() > 1;
+    visibility.set_if_neq(if shown { Visibility::Visible } else { Visibility::Hidden });
+    if !shown { return; }
+
+    // Redrawn at once when the other side changes, then on the timer
+    *since += time.delta_secs();
+    if *since < cfg.split_refresh_seconds && !(split.is_changed() || tabs.is_changed()) { return; }
+    *since = 0.0;
+
+    let tabs = tabs.bypass_change_detection();
+    let (active, other) = (tabs.active, (tabs.active + 1) % tabs.tabs.len());
+    let live_name = tabs.tabs[active].name.clone();
+    let tab = &mut tabs.tabs[other];
+    let Some(sim) = tab.sim.as_mut() else { return; };
+    let snapshot = sim.snapshot();
+    let other_population = sim.population();
+
+    let mut map = MapImage::render(&snapshot, cfg.split_map_cell);
+    map.mark_creatures(&snapshot, cfg.split_map_cell);
+    let rgba: Vec<u8> = map.rgb.chunks(3).flat_map(|px| [px[0], px[1], px[2], 255]).collect();
+    let mut image = Image::new(
+        Extent3d { width: map.width.max(1), height: map.height.max(1), depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        if rgba.is_empty() { vec![0; 4] } else { rgba },
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = ImageSampler::nearest();
+    if let Some(target) = q_map.single().ok().and_then(|node| images.get_mut(&node.image)) {
+        *target = image;
+    }
+
+    let mut population: HashMap<u32, u32> = HashMap::new();
+    for creature in q_creatures.iter() {
+        *population.entry(creature.species_id).or_default() += 1;
+    }
+    let lines = [
+        split_strip_line(&live_name, &cfg, stats.days, &population, q_plants.iter().count()),
+        split_strip_line(&tab.name, &snapshot.config, snapshot.days, &other_population, snapshot.plants.len()),
+    ];
+    if let Ok(mut title) = q_text.p0().single_mut() {
+        title.0 = format!("{} (on screen: {})", tab.name, live_name);
+    }
+    if let Ok(mut strip) = q_text.p1().single_mut() {
+        strip.0 = lines.join("\n");
+    }
+}
+
+// --- SNAPSHOTS ---
+// In-memory forks of the timeline: take one, change something, and jump back
+// to compare. Kept until the app closes; the oldest goes past snapshot_limit.
+#[derive(Resource, Default)]
+struct Snapshots {
+    list: Vec<(String, WorldSnapshot)>,
+    taken: u32, // for names, so they stay unique after deletes
+}
+
+#[derive(Component)]
+struct SnapshotPanelRoot;
+
+#[derive(Component)]
+struct SnapshotList;
+
+#[derive(Component)]
+struct TakeSnapshotButton;
+
+#[derive(Component)]
+struct RestoreSnapshotButton(usize);
+
+#[derive(Component)]
+struct DeleteSnapshotButton(usize);
+
+fn setup_snapshot_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            SnapshotPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                width: Val::Px(280.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Snapshots (T)"),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                TakeSnapshotButton,
+                Button,
+                Node {
+                    padding: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.3, 0.2)),
+            ))
+            .with_children(|b| {
+                b.spawn((
+                    Text::new("Take snapshot"),
+                    TextFont { font_size: 14.0, ..default() },
+                    TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                ));
+            });
+            p.spawn((
+                SnapshotList,
+                Node {
+                    row_gap: Val::Px(4.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+            ));
+        });
+}
+
+// T shows/hides the panel; its buttons take, restore and drop snapshots
+fn snapshot_panel_system(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut snapshots: ResMut<Snapshots>,
+    capture: SnapshotQueries,
+    mut notifications: ResMut<Notifications>,
+    mut q_panel: Query<&mut Visibility, With<SnapshotPanelRoot>>,
+    q_take: Query<&Interaction, (Changed<Interaction>, With<TakeSnapshotButton>)>,
+    q_restore: Query<(&Interaction, &RestoreSnapshotButton), Changed<Interaction>>,
+    q_delete: Query<(&Interaction, &DeleteSnapshotButton), Changed<Interaction>>,
+) {
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+
+    if focus.active.is_none() && keys.just_pressed(KeyCode::KeyT) {
+        *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+    }
+
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+
+    if q_take.iter().any(|i| *i == Interaction::Pressed) {
+        let snapshot = capture.capture();
+        let count = |id: u32| snapshot.creature_records.iter().filter(|r| r.creature.species_id == id).count();
+        snapshots.taken += 1;
+        let mut species: Vec<u32> = capture.cfg.species.keys().copied().collect();
+        species.sort_unstable();
+        let counts: Vec<String> = species.iter().map(|&id| format!("{} {}", count(id), capture.cfg.s(id).name)).collect();
+        let name = format!("#{} day {:.1} ({})", snapshots.taken, snapshot.days, counts.join(", "));
+        notifications.push(format!("Snapshot {} taken", snapshots.taken), &capture.cfg);
+        snapshots.list.push((name, snapshot));
+        let limit = capture.cfg.snapshot_limit.max(1);
+        while snapshots.list.len() > limit {
+            snapshots.list.remove(0);
+        }
+    }
+
+    for (interaction, button) in q_restore.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        if let Some((name, snapshot)) = snapshots.list.get(button.0) {
+            commands.insert_resource(LoadRequest { snapshot: snapshot.clone(), label: format!("snapshot {}", name) });
+        }
+    }
+
+    // One delete per frame: indices shift after a removal
+    if let Some((_, button)) = q_delete.iter().find(|(i, _)| **i == Interaction::Pressed) {
+        if button.0 < snapshots.list.len() {
+            snapshots.list.remove(button.0);
+        }
+    }
+}
+
+// Rebuild the rows whenever the list changes
+fn update_snapshot_list(
+    mut commands: Commands,
+    snapshots: Res<Snapshots>,
+    q_list: Query<Entity, With<SnapshotList>>,
+) {
+    if !snapshots.is_changed() { return; }
+    let Ok(list) = q_list.single() else { return; };
+
+    commands.entity(list).despawn_related::<Children>();
+    commands.entity(list).with_children(|p| {
+        if snapshots.list.is_empty() {
+            p.spawn((
+                Text::new("No snapshots yet"),
+                TextFont { font_size: 13.0, ..default() },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        }
+        for (i, (name, _)) in snapshots.list.iter().enumerate() {
+            p.spawn(Node {
+                column_gap: Val::Px(4.0),
+                ..default()
+            })
+            .with_children(|row| {
+                row.spawn((
+                    RestoreSnapshotButton(i),
+                    Button,
+                    Node {
+                        flex_grow: 1.0,
+                        padding: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.2, 0.25)),
+                ))
+                .with_children(|b| {
+                    b.spawn((
+                        Text::new(name.clone()),
+                        TextFont { font_size: 13.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                });
+                row.spawn((
+                    DeleteSnapshotButton(i),
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(6.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.35, 0.15, 0.15)),
+                ))
+                .with_children(|b| {
+                    b.spawn((
+                        Text::new("x"),
+                        TextFont { font_size: 13.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                });
+            });
+        }
+    });
+}
+
+#[derive(Component)]
+struct NewGameMenuRoot;
+
+#[derive(Component)]
+struct PresetButton(&'static str);
+
+#[derive(Component)]
+struct ScenarioButton(&'static str);
+
+#[derive(Component)]
+struct MapList;
+
+#[derive(Component)]
+struct MapButton(String);
+
+#[derive(Component)]
+struct SaveMapButton;
+
+#[derive(Component)]
+struct WorldCodeBox;
+
+#[derive(Component)]
+struct WorldCodeText;
+
+// Map files on disk: re-read whenever the menu opens and after saving one
+#[derive(Resource, Default)]
+struct MapFiles(Vec<String>);
+
+fn setup_new_game_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            NewGameMenuRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(40.0),
+                top: Val::Percent(30.0),
+                width: Val::Px(260.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                row_gap: Val::Px(8.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("New Game (N)"),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+
+            p.spawn((
+                Text::new("World code (click to enter one)"),
+                TextFont { font_size: 13.0, ..default() },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+            p.spawn((
+                WorldCodeBox,
+                Node {
+                    height: Val::Px(26.0),
+                    padding: UiRect::horizontal(Val::Px(6.0)),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                Interaction::default(),
+            ))
+            .with_children(|tb| {
+                tb.spawn((
+                    WorldCodeText,
+                    Text::new(""),
+                    TextFont { font_size: 14.0, ..default() },
+                    TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                ));
+            });
+
+            for &(id, name) in PRESETS {
+                p.spawn((
+                    PresetButton(id),
+                    Button,
+                    Node {
+                        padding: UiRect::all(Val::Px(6.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.2, 0.25)),
+                ))
+                .with_children(|b| {
+                    b.spawn((
+                        Text::new(name),
+                        TextFont { font_size: 14.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                });
+            }
+
+            p.spawn((
+                Text::new("Challenges"),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+
+            for &(id, name) in SCENARIOS {
+                p.spawn((
+                    ScenarioButton(id),
+                    Button,
+                    Node {
+                        padding: UiRect::all(Val::Px(6.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.25, 0.2, 0.15)),
+                ))
+                .with_children(|b| {
+                    b.spawn((
+                        Text::new(name),
+                        TextFont { font_size: 14.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                });
+            }
+
+            p.spawn((
+                Text::new("Maps"),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                SaveMapButton,
+                Button,
+                Node {
+                    padding: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.3, 0.2)),
+            ))
+            .with_children(|b| {
+                b.spawn((
+                    Text::new("Save this map"),
+                    TextFont { font_size: 14.0, ..default() },
+                    TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                ));
+            });
+            p.spawn((
+                MapList,
+                Node {
+                    row_gap: Val::Px(4.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+            ));
+        });
+}
+
+// N opens/closes the menu; picking a preset, challenge or map starts a fresh world with it
+fn new_game_menu_system(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut maps: ResMut<MapFiles>,
+    mut q_menu: Query<&mut Visibility, With<NewGameMenuRoot>>,
+    q_buttons: Query<(&Interaction, &PresetButton), Changed<Interaction>>,
+    q_scenario_buttons: Query<(&Interaction, &ScenarioButton), Changed<Interaction>>,
+    q_map_buttons: Query<(&Interaction, &MapButton), Changed<Interaction>>,
+) {
+    let Ok(mut visibility) = q_menu.single_mut() else { return; };
+
+    if focus.active.is_none() && keys.just_pressed(KeyCode::KeyN) {
+        *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+        if *visibility == Visibility::Visible {
+            maps.0 = MapBlueprint::list();
+        }
+    }
+
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+
+    for (interaction, button) in q_buttons.iter() {
+        if *interaction == Interaction::Pressed {
+            commands.insert_resource(NewGameRequest { preset: Some(button.0.to_string()), scenario: None, map: None, code: None });
+            *visibility = Visibility::Hidden;
+        }
+    }
+
+    for (interaction, button) in q_scenario_buttons.iter() {
+        if *interaction == Interaction::Pressed {
+            commands.insert_resource(NewGameRequest { preset: None, scenario: Some(button.0.to_string()), map: None, code: None });
+            *visibility = Visibility::Hidden;
+        }
+    }
+
+    for (interaction, button) in q_map_buttons.iter() {
+        if *interaction == Interaction::Pressed {
+            commands.insert_resource(NewGameRequest { preset: None, scenario: None, map: Some(button.0.clone()), code: None });
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+// The menu's code box shows this world's code; click it, type another and
+// press Enter to start the world it describes (Escape gives up)
+fn world_code_system(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    rng: Res<SimRng>,
+    active: Res<ActivePreset>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut key_evr: MessageReader<KeyboardInput>,
+    mut focus: ResMut<TextBoxFocus>,
+    mut notifications: ResMut<Notifications>,
+    mut q_menu: Query<&mut Visibility, With<NewGameMenuRoot>>,
+    q_box: Query<&Interaction, (Changed<Interaction>, With<WorldCodeBox>)>,
+    mut q_text: Query<&mut Text, With<WorldCodeText>>,
+) {
+    let typed: String = key_evr
+        .read()
+        .filter(|ev| ev.state.is_pressed())
+        .filter_map(|ev| match &ev.logical_key {
+            Key::Character(s) => Some(s.to_string()),
+            _ => None,
+        })
+        .collect();
+    let Ok(mut visibility) = q_menu.single_mut() else { return; };
+    let editing = focus.active == Some(ConfigField::WorldCode);
+    if *visibility == Visibility::Hidden {
+        if editing {
+            focus.active = None;
+            focus.buffer.clear();
+        }
+        return;
+    }
+
+    if !editing && q_box.iter().any(|i| *i == Interaction::Pressed) {
+        focus.active = Some(ConfigField::WorldCode);
+        focus.buffer.clear();
+    } else if editing {
+        focus.buffer.extend(typed.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').map(|c| c.to_ascii_uppercase()));
+        if keys.just_pressed(KeyCode::Backspace) {
+            focus.buffer.pop();
+        }
+        if keys.just_pressed(KeyCode::Enter) {
+            match WorldCode::decode(&focus.buffer) {
+                Ok(code) => {
+                    commands.insert_resource(NewGameRequest { preset: code.preset.clone(), scenario: None, map: None, code: Some(code) });
+                    *visibility = Visibility::Hidden;
+                }
+                Err(err) => notifications.push(format!("Not a world code: {}", err), &cfg),
+            }
+        }
+        if keys.any_just_pressed([KeyCode::Enter, KeyCode::Escape]) {
+            focus.active = None;
+            focus.buffer.clear();
+        }
+    }
+
+    let Ok(mut text) = q_text.single_mut() else { return; };
+    let shown = match focus.active {
+        Some(ConfigField::WorldCode) => format!("{}_", focus.buffer),
+        _ => WorldCode::of(&cfg, rng.seed, active.0.as_deref()).encode(),
+    };
+    set_text(&mut text, shown);
+}
+
+// "Save this map": the world's ground and fixtures, without its creatures, as a map file
+fn save_map_button(
+    q_save: Query<&Interaction, (Changed<Interaction>, With<SaveMapButton>)>,
+    capture: SnapshotQueries,
+    mut maps: ResMut<MapFiles>,
+    mut notifications: ResMut<Notifications>,
+) {
+    if !q_save.iter().any(|i| *i == Interaction::Pressed) { return; }
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let id = format!("map_{}", timestamp);
+    match MapBlueprint::from_snapshot(&capture.capture()).save(&id) {
+        Ok(path) => {
+            notifications.push(format!("Map saved to {}", path.display()), &capture.cfg);
+            maps.0 = MapBlueprint::list();
+        }
+        Err(err) => {
+            println!("Could not save map {}: {}", id, err);
+            notifications.push("Map save failed".to_string(), &capture.cfg);
+        }
+    }
+}
+
+// Rebuild the map buttons whenever the list is re-read
+fn update_map_list(mut commands: Commands, maps: Res<MapFiles>, q_list: Query<Entity, With<MapList>>) {
+    if !maps.is_changed() { return; }
+    let Ok(list) = q_list.single() else { return; };
+
+    commands.entity(list).despawn_related::<Children>();
+    commands.entity(list).with_children(|p| {
+        if maps.0.is_empty() {
+            p.spawn((
+                Text::new("No saved maps yet"),
+                TextFont { font_size: 13.0, ..default() },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        }
+        for id in &maps.0 {
+            p.spawn((
+                MapButton(id.clone()),
+                Button,
+                Node {
+                    padding: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.15, 0.25, 0.25)),
+            ))
+            .with_children(|b| {
+                b.spawn((
+                    Text::new(id.clone()),
+                    TextFont { font_size: 14.0, ..default() },
+                    TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                ));
+            });
+        }
+    });
+}
+
+#[derive(Component)]
+struct ScenarioResultRoot;
+
+#[derive(Component)]
+struct ScenarioResultText;
+
+fn setup_scenario_results(mut commands: Commands) {
+    commands
+        .spawn((
+            ScenarioResultRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(35.0),
+                top: Val::Percent(20.0),
+                width: Val::Px(360.0),
+                padding: UiRect::all(Val::Px(16.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                ScenarioResultText,
+            ));
+        });
+}
+
+fn update_scenario_results(
+    cfg: Res<SimulationConfig>,
+    game_stats: Res<GameStats>,
+    pop: Res<PopulationStats>,
+    scenario: Res<ActiveScenario>,
+    mut q_root: Query<&mut Visibility, With<ScenarioResultRoot>>,
+    mut q_text: Query<&mut Text, With<ScenarioResultText>>,
+) {
+    let Ok(mut visibility) = q_root.single_mut() else { return; };
+    let finished = scenario.0.as_ref().and_then(|run| run.outcome.map(|won| (run, won)));
+    let Some((run, won)) = finished else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Visible;
+
+    let mut lines = vec![
+        if won { "VICTORY".to_string() } else { "DEFEAT".to_string() },
+        run.def.name.clone(),
+        String::new(),
+        format!("Win: {}", run.def.win.iter().map(|c| c.label(&cfg)).collect::<Vec<_>>().join(" and ")),
+        format!("Lose if: {}", run.def.lose.iter().map(|c| c.label(&cfg)).collect::<Vec<_>>().join(" or ")),
+        String::new(),
+        format!("Days: {:.1}", game_stats.days),
+    ];
+    let mut ids: Vec<&u32> = cfg.species.keys().collect();
+    ids.sort();
+    for id in ids {
+        let counters = pop.species.get(id).copied().unwrap_or_default();
+        lines.push(format!(
+            "{}: peak {}, total {}, extinctions {}",
+            cfg.s(*id).name,
+            run.peak.get(id).copied().unwrap_or(0),
+            counters.total_ever,
+            counters.extinctions
+        ));
+    }
+    lines.push(String::new());
+    lines.push("Press N for a new game".to_string());
+
+    for mut text in q_text.iter_mut() {
+        **text = lines.join("\n");
+    }
+}
+
+fn spawn_world_shadow(mut commands: Commands, cfg: Res<SimulationConfig>) {
+    let map = cfg.map_size as f32;
+    let half_w = cfg.tile_w * map;
+    let half_h = cfg.tile_h * map;
+
+    // True diamond bounds of the tile carpet
+    let diamond_width  = half_w * 2.0;
+    let diamond_height = half_h * 2.0;
+
+    // Shadow slightly larger than the platform
+    let scale = 1.5;
+    let w = diamond_width * scale;
+    let h = diamond_height * scale;
+
+    // Push down so it peeks out under the platform
+    let base_y = -half_h * 0.9;
+    let base_z = -0.01;
+
+    commands.spawn((
+        Sprite::from_color(Color::srgba(0.05, 0.05, 0.08, 0.45), Vec2::new(w, h)),
+        Transform::from_xyz(0.0, base_y, base_z)
+            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)), // 45° for diamond
+        WorldShadow {
+            phase: rand::random::<f32>() * std::f32::consts::TAU,
+            drift_a: 0.06 + rand::random::<f32>() * 0.03,
+            drift_b: 0.03 + rand::random::<f32>() * 0.02,
+            base_y,
+        },
+    ));
+}
+
+fn animate_world_shadow(time: Res<Time>, mut q: Query<(&mut Transform, &mut Sprite, &mut WorldShadow)>) {
+    for (mut tr, mut spr, mut sh) in q.iter_mut() {
+        let dt = time.delta().as_secs_f32();
+        sh.phase += dt;
+
+        // Slow deliberate "hover" motion (not jittery)
+        let y_float = (sh.phase * sh.drift_a).sin() * 18.0 + (sh.phase * sh.drift_b).sin() * 10.0;
+        tr.translation.y = sh.base_y + y_float;
+
+        // Keep it hovering around its original offset by nudging relative to current baseline
+        // (Better: store base_y if you want; this works fine visually.)
+        tr.translation.y += y_float * dt;
+
+        // Color pulse: black <-> deep blue, with red hints
+        let pulse = (time.elapsed_secs() * 0.35).sin() * 0.5 + 0.5;
+        let a = 0.25 + pulse * 0.20; // lower max alpha
+        let r = 0.02 + pulse * 0.12;
+        let g = 0.01 + pulse * 0.03;
+        let b = 0.06 + pulse * 0.28;
+
+        spr.color = Color::srgba(r, g, b, a);
+
+        let rotation_speed = 0.05; // radians per second (adjust to taste)
+        tr.rotate_z(rotation_speed * dt);
+    }
+}
+
+
+// The player's resources, as the current mode charges them
+#[derive(SystemParam)]
+struct Purse<'w> {
+    mode: Res<'w, State<GameMode>>,
+    resources: ResMut<'w, PlayerResources>,
+}
+
+impl Purse<'_> {
+    // Challenges and management charge; everywhere else building is free
+    fn pay(&mut self, kind: ResourceKind, amount: u32) -> bool {
+        !self.mode.get().charges() || self.resources.spend(kind, amount)
+    }
+}
+
+// --- LOGIC SYSTEMS ---
+
+// Number keys pick the terrain tool (ignored while typing into a debug textbox)
+fn select_player_tool(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut tool: ResMut<PlayerTool>,
+) {
+    if focus.active.is_some() { return; }
+
+    if keys.just_pressed(KeyCode::Digit1) {
+        *tool = PlayerTool::Water;
+    }
+    if keys.just_pressed(KeyCode::Digit2) {
+        *tool = PlayerTool::Bridge;
+    }
+    if keys.just_pressed(KeyCode::Digit3) {
+        *tool = PlayerTool::Crop;
+    }
+    if keys.just_pressed(KeyCode::Digit4) {
+        *tool = PlayerTool::Fence;
+    }
+    if keys.just_pressed(KeyCode::Digit5) {
+        *tool = PlayerTool::Gate;
+    }
+    if keys.just_pressed(KeyCode::Digit6) {
+        *tool = PlayerTool::Shelter;
+    }
+    if keys.just_pressed(KeyCode::Digit7) {
+        *tool = PlayerTool::Harvest;
+    }
+    if keys.just_pressed(KeyCode::Digit8) {
+        *tool = PlayerTool::Clone;
+    }
+}
+
+// The grid tile under a world position, rounded but still as floats
+fn snap_to_grid(world_pos: Vec2) -> (f32, f32) {
+    let half_w = TILE_WIDTH / 2.0;
+    let half_h = TILE_HEIGHT / 2.0;
+    let grid_x = (world_pos.y / half_h + world_pos.x / half_w) / 2.0;
+    let grid_y = (world_pos.y / half_h - world_pos.x / half_w) / 2.0;
+    (grid_x.round(), grid_y.round())
+}
+
+// The grid tile under the mouse, if it's over the window
+fn hovered_tile(window: &Window, camera: &Camera, camera_transform: &GlobalTransform) -> Option<(i32, i32)> {
+    let screen_pos = window.cursor_position()?;
+    let world_pos = camera.viewport_to_world_2d(camera_transform, screen_pos).ok()?;
+    let (x, y) = snap_to_grid(world_pos);
+    Some((x as i32, y as i32))
+}
+
+// This function figures out where the mouse is in the Isometric World
+fn cursor_system(
+    mut commands: Commands,
+    tool: Res<PlayerTool>,
+    cfg: Res<SimulationConfig>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut q_cursor: Query<&mut Transform, With<MapCursor>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut q_tiles: Query<(Entity, &Tile, Option<&mut Water>, Has<Bridge>)>,
+    // NEW: We need to see plants and soil markers to delete them
+    q_plants: Query<(Entity, &GridPosition), Or<(With<Plant>, With<Crop>)>>,
+    q_exhausted: Query<(Entity, &GridPosition), With<ExhaustedSoil>>,
+    q_crops: Query<(Entity, &GridPosition, &Crop), Without<Dead>>,
+    q_fences: Query<(Entity, &GridPosition), With<Fence>>,
+    mut q_gates: Query<(Entity, &GridPosition, &mut Gate), Without<Tile>>,
+    q_shelters: Query<(Entity, &GridPosition), With<Shelter>>,
+    mut purse: Purse,
+) {
+    let Ok(window) = q_window.single() else { return; }; // headless
+    let (camera, camera_transform) = q_camera.single().expect("Camera not found!");
+    let mut cursor_transform = q_cursor.single_mut().expect("Cursor not found!");
+
+    if let Some(screen_pos) = window.cursor_position() {
+        if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, screen_pos) {
+
+            let half_w = TILE_WIDTH / 2.0;
+            let half_h = TILE_HEIGHT / 2.0;
+            let (snapped_x, snapped_y) = snap_to_grid(world_pos);
+
+            cursor_transform.translation.x = (snapped_x - snapped_y) * half_w;
+            cursor_transform.translation.y = (snapped_x + snapped_y) * half_h;
+
+            match *tool {
+                PlayerTool::Water => {
+                    // --- LEFT CLICK: Create Water & Destroy Nature ---
+                    // Dragging floods land as shallows; each new click on water deepens it
+                    if mouse_input.pressed(MouseButton::Left) {
+                        // 1. Flood the tile (bridges keep their planks)
+                        for (entity, tile, water, _) in q_tiles.iter_mut() {
+                            if tile.x != snapped_x as i32 || tile.y != snapped_y as i32 { continue; }
+
+                            match water {
+                                None => {
+                                    commands.entity(entity).insert(Water { depth: cfg.water_paint_depth });
+                                }
+                                Some(mut water) => {
+                                    if mouse_input.just_pressed(MouseButton::Left) {
+                                        water.depth = (water.depth + cfg.water_paint_depth).min(cfg.max_water_depth);
+                                    }
+                                }
+                            }
+                        }
+
+                        // 2. Kill Plants on this spot
+                        for (entity, pos) in q_plants.iter() {
+                            if pos.x == snapped_x as i32 && pos.y == snapped_y as i32 {
+                                commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+                            }
+                        }
+
+                        // 3. Remove Exhausted Soil markers on this spot
+                        for (entity, pos) in q_exhausted.iter() {
+                            if pos.x == snapped_x as i32 && pos.y == snapped_y as i32 {
+                                commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+                            }
+                        }
+                    }
+
+                    // --- RIGHT CLICK: Remove Water (Restore Land) ---
+                    // Draining a tile also takes any bridge on it with it
+                    if mouse_input.pressed(MouseButton::Right) {
+                        for (entity, tile, _, _) in q_tiles.iter() {
+                            if tile.x == snapped_x as i32 && tile.y == snapped_y as i32 {
+                                commands.entity(entity).remove::<(Water, Bridge, Pollution)>();
+                            }
+                        }
+                    }
+                }
+                PlayerTool::Bridge => {
+                    // --- LEFT CLICK: Lay planks (only over water) ---
+                    if mouse_input.pressed(MouseButton::Left) {
+                        for (entity, tile, water, has_bridge) in q_tiles.iter() {
+                            if tile.x == snapped_x as i32 && tile.y == snapped_y as i32 && water.is_some() && !has_bridge {
+                                commands.entity(entity).insert(Bridge);
+                            }
+                        }
+                    }
+
+                    // --- RIGHT CLICK: Tear the bridge down (water stays) ---
+                    if mouse_input.pressed(MouseButton::Right) {
+                        for (entity, tile, _, has_bridge) in q_tiles.iter() {
+                            if tile.x == snapped_x as i32 && tile.y == snapped_y as i32 && has_bridge {
+                                commands.entity(entity).remove::<Bridge>();
+                            }
+                        }
+                    }
+                }
+                PlayerTool::Crop => {
+                    let (cx, cy) = (snapped_x as i32, snapped_y as i32);
+
+                    // --- LEFT CLICK: Sow a crop on free dry land ---
+                    if mouse_input.pressed(MouseButton::Left) {
+                        let dry_land = q_tiles
+                            .iter()
+                            .any(|(_, tile, water, _)| tile.x == cx && tile.y == cy && water.is_none());
+                        let free = !q_plants.iter().any(|(_, pos)| pos.x == cx && pos.y == cy);
+
+                        if dry_land && free && purse.pay(ResourceKind::Seeds, cfg.crop_cost) {
+                            commands.spawn((Crop { stage: CropStage::Seed, growth: 0.0 }, GridPosition { x: cx, y: cy }));
+                        }
+                    }
+
+                    // --- RIGHT CLICK: Pull the crop up (ripe ones give seeds) ---
+                    if mouse_input.pressed(MouseButton::Right) {
+                        for (entity, pos, crop) in q_crops.iter() {
+                            if pos.x == cx && pos.y == cy {
+                                if crop.stage == CropStage::Ripe {
+                                    purse.resources.add(ResourceKind::Seeds, cfg.seeds_per_crop);
+                                }
+                                commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+                            }
+                        }
+                    }
+                }
+                PlayerTool::Fence => {
+                    let (cx, cy) = (snapped_x as i32, snapped_y as i32);
+
+                    // --- LEFT CLICK: Put up a fence on dry land ---
+                    if mouse_input.pressed(MouseButton::Left) {
+                        let dry_land = q_tiles
+                            .iter()
+                            .any(|(_, tile, water, _)| tile.x == cx && tile.y == cy && water.is_none());
+                        let free = !q_fences.iter().any(|(_, pos)| pos.x == cx && pos.y == cy)
+                            && !q_gates.iter().any(|(_, pos, _)| pos.x == cx && pos.y == cy)
+                            && !q_shelters.iter().any(|(_, pos)| pos.x == cx && pos.y == cy);
+
+                        if dry_land && free && purse.pay(ResourceKind::Wool, cfg.fence_cost) {
+                            commands.spawn((Fence, GridPosition { x: cx, y: cy }));
+                        }
+                    }
+
+                    // --- RIGHT CLICK: Take the fence down ---
+                    if mouse_input.pressed(MouseButton::Right) {
+                        for (entity, pos) in q_fences.iter() {
+                            if pos.x == cx && pos.y == cy {
+                                commands.entity(entity).despawn();
+                            }
+                        }
+                    }
+                }
+                PlayerTool::Gate => {
+                    let (cx, cy) = (snapped_x as i32, snapped_y as i32);
+
+                    // --- LEFT CLICK: Place a gate, or click an existing one to cycle who may pass ---
+                    if mouse_input.just_pressed(MouseButton::Left) {
+                        let mut existing = false;
+                        for (_, pos, mut gate) in q_gates.iter_mut() {
+                            if pos.x != cx || pos.y != cy { continue; }
+                            existing = true;
+
+                            let mut ids: Vec<u32> = cfg.species.keys().copied().collect();
+                            ids.sort();
+                            gate.allowed = match gate.allowed {
+                                // next species id, then "everyone", then back to the first
+                                Some(id) => ids.iter().copied().find(|&next| next > id),
+                                None => ids.first().copied(),
+                            };
+                        }
+
+                        let dry_land = q_tiles
+                            .iter()
+                            .any(|(_, tile, water, _)| tile.x == cx && tile.y == cy && water.is_none());
+                        let fenced = q_fences.iter().any(|(_, pos)| pos.x == cx && pos.y == cy)
+                            || q_shelters.iter().any(|(_, pos)| pos.x == cx && pos.y == cy);
+
+                        if !existing && dry_land && !fenced && purse.pay(ResourceKind::Wool, cfg.gate_cost) {
+                            // new gates start sheep-only
+                            commands.spawn((Gate { allowed: Some(0) }, GridPosition { x: cx, y: cy }));
+                        }
+                    }
+
+                    // --- RIGHT CLICK: Remove the gate ---
+                    if mouse_input.pressed(MouseButton::Right) {
+                        for (entity, pos, _) in q_gates.iter() {
+                            if pos.x == cx && pos.y == cy {
+                                commands.entity(entity).despawn();
+                            }
+                        }
+                    }
+                }
+                PlayerTool::Shelter => {
+                    let (cx, cy) = (snapped_x as i32, snapped_y as i32);
+
+                    // --- LEFT CLICK: Build a hut on free dry land ---
+                    if mouse_input.just_pressed(MouseButton::Left) {
+                        let dry_land = q_tiles
+                            .iter()
+                            .any(|(_, tile, water, _)| tile.x == cx && tile.y == cy && water.is_none());
+                        let free = !q_fences.iter().any(|(_, pos)| pos.x == cx && pos.y == cy)
+                            && !q_gates.iter().any(|(_, pos, _)| pos.x == cx && pos.y == cy)
+                            && !q_shelters.iter().any(|(_, pos)| pos.x == cx && pos.y == cy);
+
+                        if dry_land && free && purse.pay(ResourceKind::Wool, cfg.shelter_cost) {
+                            commands.spawn((Shelter::default(), GridPosition { x: cx, y: cy }));
+                        }
+                    }
+
+                    // --- RIGHT CLICK: Tear the hut down ---
+                    if mouse_input.pressed(MouseButton::Right) {
+                        for (entity, pos) in q_shelters.iter() {
+                            if pos.x == cx && pos.y == cy {
+                                commands.entity(entity).despawn();
+                            }
+                        }
+                    }
+                }
+                // Creatures aren't part of the terrain; see harvest_tool_system and clone_tool_system
+                PlayerTool::Harvest | PlayerTool::Clone => {}
+            }
+        }
+    }
+}
+
+// Left click shears the sheep on the hovered tile, right click butchers them for meat
+fn harvest_tool_system(
+    mut commands: Commands,
+    tool: Res<PlayerTool>,
+    cfg: Res<SimulationConfig>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut resources: ResMut<PlayerResources>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut q_sheep: Query<(Entity, &GridPosition, &CreatureStats, &CreatureName, &Generation, &Dna, Option<&mut Wool>), (With<Creature>, Without<Dead>)>,
+    mut deaths: MessageWriter<DeathEvent>,
+) {
+    if *tool != PlayerTool::Harvest { return; }
+    let shearing = mouse_input.just_pressed(MouseButton::Left);
+    let butchering = mouse_input.just_pressed(MouseButton::Right);
+    if !shearing && !butchering { return; }
+
+    let Ok(window) = q_window.single() else { return; };
+    let Ok((camera, camera_transform)) = q_camera.single() else { return; };
+    let Some((x, y)) = hovered_tile(window, camera, camera_transform) else { return; };
+
+    for (entity, pos, stats, name, generation, dna, wool) in q_sheep.iter_mut() {
+        if pos.x != x || pos.y != y || stats.species_id != 0 { continue; }
+
+        if shearing {
+            if let Some(mut wool) = wool {
+                shear(&mut wool, &cfg, &mut resources);
+            }
+        } else {
+            resources.add(ResourceKind::Meat, cfg.meat_per_sheep);
+            commands.entity(entity).insert(Dead { cause: DeathCause::Butchered });
+            deaths.write(DeathEvent {
+                entity,
+                species_id: stats.species_id,
+                name: name.0.clone(),
+                generation: generation.0,
+                dna: *dna,
+                pos: (pos.x, pos.y),
+                cause: DeathCause::Butchered,
+            });
+        }
+    }
+}
+
+// --- CLONE TOOL (8) ---
+// Sandbox only. Left click puts a copy of the source creature on the hovered
+// dry tile: the selected creature, or one from a creature file so individuals
+// can move between worlds. The panel beside it exports the selected creature
+// to a file and picks the source.
+#[derive(Resource, Default)]
+struct CloneSource(Option<String>); // creature file id; None: the selected creature
+
+#[derive(Resource, Default)]
+struct CreatureFiles(Vec<String>);
+
+#[derive(Component)]
+struct ClonePanelRoot;
+
+#[derive(Component)]
+struct CloneSourceList;
+
+#[derive(Component)]
+struct CloneSourceButton(Option<String>);
+
+#[derive(Component)]
+struct ExportCreatureButton;
+
+fn setup_clone_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            ClonePanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(40.0),
+                top: Val::Px(10.0),
+                width: Val::Px(260.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            Visibility::Hidden,
+            Interaction::default(),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Clone tool (8): left click places a copy of"),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                CloneSourceList,
+                Node {
+                    row_gap: Val::Px(4.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+            ));
+            p.spawn((
+                ExportCreatureButton,
+                Button,
+                Node {
+                    padding: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.3, 0.2)),
+            ))
+            .with_children(|b| {
+                b.spawn((
+                    Text::new("Export selected creature"),
+                    TextFont { font_size: 14.0, ..default() },
+                    TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                ));
+            });
+        });
+}
+
+// Where a placed copy is counted, logged and its story started
+#[derive(SystemParam)]
+struct Arrivals<'w> {
+    stats: Res<'w, GameStats>,
+    pop: ResMut<'w, PopulationStats>,
+    logger: ResMut<'w, SimulationLogger>,
+    bios: ResMut<'w, Biographies>,
+}
+
+impl Arrivals<'_> {
+    // A copy counts as one more of its species, like a founder
+    fn welcome(&mut self, cfg: &SimulationConfig, entity: Entity, record: &CreatureRecord, event: &str, story: String) {
+        let c = &record.creature;
+        let entry = self.pop.species.entry(c.species_id).or_default();
+        entry.born += 1;
+        entry.total_ever += 1;
+        self.logger.log(event, self.stats.days, &cfg.s(c.species_id).name, &c.name, c.generation, &c.dna);
+        self.bios.0.entry(entity).or_default().note(self.stats.days, story, cfg.biography_length);
+    }
+}
+
+fn clone_tool_system(
+    mut commands: Commands,
+    tool: Res<PlayerTool>,
+    cfg: Res<SimulationConfig>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    selected: Res<SelectedCreature>,
+    source: Res<CloneSource>,
+    records: CreatureRecords,
+    mut arrivals: Arrivals,
+    mut notifications: ResMut<Notifications>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    q_tiles: Query<(&Tile, Has<Water>)>,
+    q_blocked: Query<&GridPosition, Or<(With<Fence>, With<Gate>)>>,
+    q_panel: Query<&Interaction, With<ClonePanelRoot>>,
+) {
+    if *tool != PlayerTool::Clone || !mouse_input.just_pressed(MouseButton::Left) { return; }
+    // Clicks on the panel's buttons aren't meant for the ground under it
+    if q_panel.iter().any(|i| *i != Interaction::None) { return; }
+    let Ok(window) = q_window.single() else { return; };
+    let Ok((camera, camera_transform)) = q_camera.single() else { return; };
+    let Some((x, y)) = hovered_tile(window, camera, camera_transform) else { return; };
+
+    let dry_land = q_tiles.iter().any(|(tile, water)| tile.x == x && tile.y == y && !water);
+    if !dry_land || q_blocked.iter().any(|p| p.x == x && p.y == y) { return; }
+
+    let (record, event, story) = match &source.0 {
+        None => {
+            let Some(record) = selected.0.and_then(|e| records.get(e)) else {
+                notifications.push("Select a creature to clone first".to_string(), &cfg);
+                return;
+            };
+            let story = format!("Cloned from {}", record.creature.name);
+            (record, "Clone", story)
+        }
+        Some(id) => match CreatureRecord::load(id) {
+            // Its clade belongs to the world it came from
+            Ok(record) => (CreatureRecord { clade: None, ..record }, "Import", format!("Imported from {}", id)),
+            Err(err) => {
+                println!("Could not import creature {}: {}", id, err);
+                notifications.push(format!("Import of {} failed", id), &cfg);
+                return;
+            }
+        },
+    };
+    if !cfg.species.contains_key(&record.creature.species_id) {
+        notifications.push(format!("This world has no species {}", record.creature.species_id), &cfg);
+        return;
+    }
+
+    let entity = record.clone().at((x, y)).spawn(&mut commands, &cfg);
+    arrivals.welcome(&cfg, entity, &record, event, story);
+}
+
+// Export writes CREATURE_DIR/<name>_<timestamp>.ron; the source buttons pick
+// what the next click places
+fn clone_panel_buttons(
+    cfg: Res<SimulationConfig>,
+    selected: Res<SelectedCreature>,
+    records: CreatureRecords,
+    mut source: ResMut<CloneSource>,
+    mut files: ResMut<CreatureFiles>,
+    mut notifications: ResMut<Notifications>,
+    q_export: Query<&Interaction, (Changed<Interaction>, With<ExportCreatureButton>)>,
+    q_sources: Query<(&Interaction, &CloneSourceButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in q_sources.iter() {
+        if *interaction == Interaction::Pressed {
+            source.0 = button.0.clone();
+        }
+    }
+
+    if !q_export.iter().any(|i| *i == Interaction::Pressed) { return; }
+    let Some(record) = selected.0.and_then(|e| records.get(e)) else {
+        notifications.push("Select a creature to export first".to_string(), &cfg);
+        return;
+    };
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let id = format!("{}_{}", record.creature.name.to_lowercase(), timestamp);
+    match record.save(&id) {
+        Ok(path) => {
+            notifications.push(format!("{} exported to {}", record.creature.name, path.display()), &cfg);
+            files.0 = CreatureRecord::list();
+        }
+        Err(err) => {
+            println!("Could not export creature {}: {}", id, err);
+            notifications.push("Creature export failed".to_string(), &cfg);
+        }
+    }
+}
+
+// Shown while the clone tool is picked in Sandbox mode. The files are re-read
+// each time it's picked; the list is rebuilt when they or the source change.
+fn update_clone_panel(
+    mut commands: Commands,
+    tool: Res<PlayerTool>,
+    mode: Res<State<GameMode>>,
+    source: Res<CloneSource>,
+    mut files: ResMut<CreatureFiles>,
+    mut q_panel: Query<&mut Visibility, With<ClonePanelRoot>>,
+    q_list: Query<Entity, With<CloneSourceList>>,
+) {
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+    let shown = *tool == PlayerTool::Clone && *mode.get() == GameMode::Sandbox;
+    visibility.set_if_neq(if shown { Visibility::Visible } else { Visibility::Hidden });
+    if !shown { return; }
+    if tool.is_changed() || mode.is_changed() {
+        files.0 = CreatureRecord::list();
+    }
+    if !(files.is_changed() || source.is_changed()) { return; }
+    let Ok(list) = q_list.single() else { return; };
+
+    commands.entity(list).despawn_related::<Children>();
+    commands.entity(list).with_children(|p| {
+        let choices = std::iter::once(None).chain(files.0.iter().cloned().map(Some));
+        for choice in choices {
+            let color = if choice == source.0 { Color::srgb(0.25, 0.35, 0.5) } else { Color::srgb(0.15, 0.15, 0.2) };
+            let label = choice.clone().unwrap_or_else(|| "the selected creature".to_string());
+            p.spawn((
+                CloneSourceButton(choice),
+                Button,
+                Node {
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(color),
+            ))
+            .with_children(|b| {
+                b.spawn((
+                    Text::new(label),
+                    TextFont { font_size: 13.0, ..default() },
+                    TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                ));
+            });
+        }
+    });
+}
+
+// God power: end whatever the weather is doing with a spell of rain
+fn call_rain(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    cfg: Res<SimulationConfig>,
+    mut weather: ResMut<Weather>,
+    mut purse: Purse,
+    mut changes: MessageWriter<WeatherChanged>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyR) { return; }
+    if weather.kind == WeatherKind::Rain || !purse.pay(ResourceKind::Meat, cfg.rain_cost) { return; }
+
+    weather.kind = WeatherKind::Rain;
+    weather.timer = Timer::from_seconds(cfg.weather_min_seconds.max(1.0), TimerMode::Once);
+    changes.write(WeatherChanged { kind: weather.kind });
+}
+
+// K: the selected creature drops dead where it stands (god power)
+fn smite_selected(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    cfg: Res<SimulationConfig>,
+    selected: Res<SelectedCreature>,
+    q_creatures: Query<(&CreatureStats, &CreatureName, &Generation, &Dna, &GridPosition), (With<Creature>, Without<Dead>)>,
+    mut purse: Purse,
+    mut deaths: MessageWriter<DeathEvent>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyK) { return; }
+    let Some(entity) = selected.0 else { return; };
+    let Ok((stats, name, generation, dna, pos)) = q_creatures.get(entity) else { return; };
+    if !purse.pay(ResourceKind::Meat, cfg.smite_cost) { return; }
+
+    commands.entity(entity).insert(Dead { cause: DeathCause::Smitten });
+    deaths.write(DeathEvent {
+        entity,
+        species_id: stats.species_id,
+        name: name.0.clone(),
+        generation: generation.0,
+        dna: *dna,
+        pos: (pos.x, pos.y),
+        cause: DeathCause::Smitten,
+    });
+}
+
+fn toggle_shepherd(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    q_shepherd: Query<Entity, With<Shepherd>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyH) { return; }
+
+    if let Ok(entity) = q_shepherd.single() {
+        commands.entity(entity).despawn();
+        return;
+    }
+
+    commands.spawn((
+        Shepherd,
+        GridPosition { x: 0, y: 0 },
+        NextMove::default(),
+    ));
+}
+
+// Arrow keys walk the shepherd one tile every `shepherd_move_seconds`
+fn move_shepherd(
+    clock: Res<MoveClock>,
+    cfg: Res<SimulationConfig>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut q_shepherd: Query<(&mut GridPosition, &mut NextMove), With<Shepherd>>,
+    q_fences: Query<&GridPosition, (With<Fence>, Without<Shepherd>)>,
+) {
+    let Ok((mut pos, mut next_move)) = q_shepherd.single_mut() else { return; };
+
+    let mut dir = (0, 0);
+    if keys.pressed(KeyCode::ArrowUp) { dir = (1, 1); }
+    if keys.pressed(KeyCode::ArrowDown) { dir = (-1, -1); }
+    if keys.pressed(KeyCode::ArrowLeft) { dir = (-1, 1); }
+    if keys.pressed(KeyCode::ArrowRight) { dir = (1, -1); }
+
+    // Letting go starts over: the first step of a new press waits a full interval
+    if dir == (0, 0) {
+        if next_move.0.is_some() { next_move.0 = None; }
+        return;
+    }
+
+    let interval = MoveClock::ticks_for(&cfg, cfg.shepherd_move_seconds);
+    if !clock.is_due(&next_move) {
+        if next_move.0.is_none() { *next_move = clock.after(interval); }
+        return;
+    }
+    *next_move = clock.after(interval);
+
+    // Screen-space directions are diagonal on the iso grid; step one axis at a time
+    let (dx, dy) = if rand::random::<bool>() { (dir.0, 0) } else { (0, dir.1) };
+    let nx = (pos.x + dx).clamp(-cfg.map_size, cfg.map_size - 1);
+    let ny = (pos.y + dy).clamp(-cfg.map_size, cfg.map_size - 1);
+    if q_fences.iter().any(|f| f.x == nx && f.y == ny) { return; }
+    pos.x = nx;
+    pos.y = ny;
+}
+
+fn sync_creature_visuals(
+    time: Res<Time>, // We need Time to calculate animation speed
+    lod: Res<LodState>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<&Transform, (With<MainCamera>, Without<Creature>, Without<Shepherd>, Without<Bird>, Without<Insect>)>,
+    mut query: Query<(&mut Transform, &GridPosition, Has<Creature>, Has<Bird>, Has<Insect>), Or<(With<Creature>, With<Shepherd>, With<Bird>, With<Insect>)>>
+) {
+    // What the camera sees, plus a tile of margin so nobody pops in at the edge
+    let view = match (q_window.single(), q_camera.single()) {
+        (Ok(window), Ok(camera)) => {
+            let half = window.size() / 2.0 * camera.scale.truncate() + Vec2::new(TILE_WIDTH, TILE_HEIGHT);
+            Some(Rect::from_center_half_size(camera.translation.truncate(), half))
+        }
+        _ => None,
+    };
+
+    for (mut transform, pos, is_creature, is_bird, is_insect) in query.iter_mut() {
+        // Blobs stand in for creatures when zoomed out
+        if lod.active && is_creature { continue; }
+
+        // 1. Calculate the TARGET position (Where they logically are)
+        let target_x = (pos.x - pos.y) as f32 * (TILE_WIDTH / 2.0);
+        let target_y = (pos.x + pos.y) as f32 * (TILE_HEIGHT / 2.0);
+
+        // Off screen: nothing to animate (they catch up once in view)
+        if let Some(view) = view {
+            if !view.contains(Vec2::new(target_x, target_y)) && !view.contains(transform.translation.truncate()) {
+                continue;
+            }
+        }
+
+        // We define the target vector.
+        // We keep Z at 2.0 so they stay above the ground (birds and insects fly over everyone).
+        let z = if is_bird { 3.0 } else if is_insect { 2.8 } else { 2.0 };
+        let target = Vec3::new(target_x, target_y, z);
+
+        // 2. Interpolate (Lerp) towards the target
+        // "15.0 * dt" controls the speed.
+        // Higher = Snappier, Lower = Floaty/Driftier.
+        // 15.0 is a good balance for top-down movement.
+        let interpolation_speed = 15.0 * time.delta().as_secs_f32();
+
+        // Settled sprites are left alone so Bevy doesn't re-propagate their transforms;
+        // the last sub-pixel of the lerp snaps so they actually settle
+        if transform.translation == target { continue; }
+        let next = transform.translation.lerp(target, interpolation_speed);
+        transform.translation = if next.distance_squared(target) < 0.01 { target } else { next };
+    }
+}
+
+// --- CREATURE SELECTION & DECISION DEBUG ---
+// Middle click on a creature selects it (and asks the planner to trace its
+// decisions); middle click anywhere else clears the selection
+fn select_creature(
+    mut commands: Commands,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    q_creatures: Query<(Entity, &GridPosition), (With<Creature>, Without<Dead>)>,
+    mut selected: ResMut<SelectedCreature>,
+    intents: Res<IntentOverlay>,
+) {
+    // Dead and gone: drop it
+    if let Some(entity) = selected.0 {
+        if q_creatures.get(entity).is_err() {
+            selected.0 = None;
+        }
+    }
+    if !mouse_input.just_pressed(MouseButton::Middle) { return; }
+    let (Ok(window), Ok((camera, camera_transform))) = (q_window.single(), q_camera.single()) else { return; };
+    let Some((x, y)) = hovered_tile(window, camera, camera_transform) else { return; };
+
+    let picked = q_creatures.iter().find(|(_, pos)| pos.x == x && pos.y == y).map(|(e, _)| e);
+    if let Some(old) = selected.0 {
+        // The intent overlay still wants its trace
+        if Some(old) != picked && !intents.0 {
+            commands.entity(old).remove::<DecisionTrace>();
+        }
+    }
+    if let Some(entity) = picked {
+        commands.entity(entity).insert(DecisionTrace::default());
+    }
+    selected.0 = picked;
+}
+
+// Ring on the selected creature, an arrow to its target (see intent_color),
+// and its four candidate steps: green for the one it took, orange for other
+// walkable ones, red for ones scored below zero
+fn draw_decision_gizmos(
+    mut gizmos: Gizmos,
+    cfg: Res<SimulationConfig>,
+    selected: Res<SelectedCreature>,
+    q_creatures: Query<(&GridPosition, Option<&DecisionTrace>), With<Creature>>,
+) {
+    let Some((pos, trace)) = selected.0.and_then(|e| q_creatures.get(e).ok()) else { return; };
+    let here = iso(&cfg, pos.x, pos.y);
+    gizmos.circle_2d(here, cfg.tile_w * 0.4, Color::srgb(1.0, 1.0, 0.2));
+
+    let Some(trace) = trace else { return; };
+    if let (Some((tx, ty)), Some(goal)) = (trace.target, trace.goal) {
+        let color = intent_color(goal);
+        let there = iso(&cfg, tx, ty);
+        gizmos.arrow_2d(here, there, color);
+        gizmos.circle_2d(there, cfg.tile_w * 0.25, color);
+    }
+    for ((dx, dy), score) in MOVE_DIRECTIONS.into_iter().zip(trace.scores) {
+        let Some(score) = score else { continue; };
+        let color = if trace.chosen == Some((dx, dy)) {
+            Color::srgb(0.2, 1.0, 0.2)
+        } else if score >= 0 {
+            Color::srgb(1.0, 0.6, 0.1)
+        } else {
+            Color::srgb(0.9, 0.1, 0.1)
+        };
+        let step = iso(&cfg, pos.x + dx, pos.y + dy);
+        gizmos.arrow_2d(here, here.lerp(step, 0.8), color);
+    }
+}
+
+// How long the path stays lit up after the planner throws it away
+const REPLAN_FLASH_SECONDS: f32 = 0.4;
+
+// The selected creature's planned path as a dotted line, one dash per tile
+// edge. When the planner replans (the new path doesn't carry on from the old
+// one) the path flashes white, so a creature that keeps changing its mind
+// flickers.
+fn draw_planned_path(
+    mut gizmos: Gizmos,
+    cfg: Res<SimulationConfig>,
+    time: Res<Time>,
+    selected: Res<SelectedCreature>,
+    q_creatures: Query<(&GridPosition, &DecisionTrace), With<Creature>>,
+    mut last_seen: Local<Option<(Entity, u32)>>,
+    mut flash: Local<f32>,
+) {
+    let Some((entity, (pos, trace))) = selected.0.and_then(|e| q_creatures.get(e).ok().map(|c| (e, c))) else {
+        *last_seen = None;
+        return;
+    };
+    if last_seen.is_some_and(|(e, replans)| e == entity && replans < trace.replans) {
+        *flash = REPLAN_FLASH_SECONDS;
+    }
+    *last_seen = Some((entity, trace.replans));
+    *flash = (*flash - time.delta_secs()).max(0.0);
+    if trace.path.is_empty() { return; }
+
+    let base = trace.goal.map(intent_color).unwrap_or(Color::WHITE).with_alpha(0.8);
+    let color = base.mix(&Color::WHITE, *flash / REPLAN_FLASH_SECONDS);
+    let mut from = iso(&cfg, pos.x, pos.y);
+    for &(x, y) in &trace.path {
+        let to = iso(&cfg, x, y);
+        gizmos.line_2d(from.lerp(to, 0.3), from.lerp(to, 0.7), color);
+        gizmos.circle_2d(to, cfg.tile_w * 0.05, color);
+        from = to;
+    }
+}
+
+// Breadcrumbs of where the selected creature has been, fading out with age;
+// with the debug panel open (sandbox), every creature's
+fn draw_trails(
+    mut gizmos: Gizmos,
+    cfg: Res<SimulationConfig>,
+    mode: Res<State<GameMode>>,
+    selected: Res<SelectedCreature>,
+    q_creatures: Query<(Entity, &GridPosition, &History, &CreatureStats), (With<Creature>, Without<Dead>)>,
+) {
+    let all = cfg.debug_panel_enabled && *mode.get() == GameMode::Sandbox;
+    for (entity, pos, history, stats) in q_creatures.iter() {
+        if !all && selected.0 != Some(entity) { continue; }
+        let [r, g, b] = cfg.s(stats.species_id).appearance.color;
+        let len = history.trail.len() as f32;
+        let mut to = iso(&cfg, pos.x, pos.y);
+        // Newest first, so each step back is a little fainter
+        for (i, &(x, y)) in history.trail.iter().rev().enumerate() {
+            let from = iso(&cfg, x, y);
+            gizmos.line_2d(from, to, Color::srgba(r, g, b, 0.8 * (1.0 - i as f32 / len)));
+            to = from;
+        }
+    }
+}
+
+// --- WORLD VISUALS ---
+// The core spawns bare simulation entities; they get their sprites here
+fn iso(cfg: &SimulationConfig, x: i32, y: i32) -> Vec2 {
+    Vec2::new((x - y) as f32 * (cfg.tile_w / 2.0), (x + y) as f32 * (cfg.tile_h / 2.0))
+}
+
+fn crop_look(stage: CropStage) -> (Color, f32) {
+    match stage {
+        CropStage::Seed => (Color::srgb(0.4, 0.3, 0.15), 6.0),
+        CropStage::Sprout => (Color::srgb(0.4, 0.7, 0.2), 10.0),
+        CropStage::Ripe => (Color::srgb(0.9, 0.75, 0.2), 16.0),
+    }
+}
+
+fn attach_world_sprites(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    q_tiles: Query<(Entity, &Tile), Added<Tile>>,
+    q_plants: Query<(Entity, &GridPosition, Has<ShadeTolerant>), (Added<Plant>, Without<Crop>)>,
+    q_crops: Query<(Entity, &GridPosition, &Crop), Added<Crop>>,
+    q_trees: Query<(Entity, &GridPosition), Added<Tree>>,
+    q_fences: Query<(Entity, &GridPosition), Added<Fence>>,
+    q_gates: Query<(Entity, &GridPosition, &Gate), Added<Gate>>,
+    q_shelters: Query<(Entity, &GridPosition), Added<Shelter>>,
+    q_exhausted: Query<(Entity, &GridPosition), Added<ExhaustedSoil>>,
+    q_creatures: Query<(Entity, &GridPosition), Added<Creature>>,
+    q_shepherd: Query<(Entity, &GridPosition), Added<Shepherd>>,
+    q_birds: Query<(Entity, &GridPosition), Added<Bird>>,
+    q_insects: Query<(Entity, &GridPosition), Added<Insect>>,
+) {
+    let tile_size = Vec2::new(cfg.tile_w - 2.0, cfg.tile_h - 2.0);
+    for (entity, tile) in q_tiles.iter() {
+        let at = iso(&cfg, tile.x, tile.y);
+        // update_tile_colors paints it on the same frame
+        commands.entity(entity).insert((
+            Sprite::from_color(Color::srgb(0.3, 0.5, 0.3), tile_size),
+            Transform::from_xyz(at.x, at.y, 0.0),
+        ));
+    }
+    for (entity, pos, tolerant) in q_plants.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        // Ferns are darker and smaller
+        let sprite = if tolerant {
+            Sprite::from_color(Color::srgb(0.1, 0.45, 0.25), Vec2::new(12.0, 12.0))
+        } else {
+            Sprite::from_color(Color::srgb(0.2, 0.8, 0.2), Vec2::new(15.0, 15.0))
+        };
+        commands.entity(entity).insert((sprite, Transform::from_xyz(at.x, at.y, 0.5)));
+    }
+    for (entity, pos, crop) in q_crops.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        let (color, size) = crop_look(crop.stage);
+        commands.entity(entity).insert((
+            Sprite::from_color(color, Vec2::splat(size)),
+            Transform::from_xyz(at.x, at.y, 0.5),
+        ));
+    }
+    for (entity, pos) in q_trees.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        commands.entity(entity).insert((
+            Sprite::from_color(Color::srgb(0.05, 0.3, 0.1), Vec2::new(18.0, 34.0)),
+            Transform::from_xyz(at.x, at.y + 10.0, 1.5),
+        ));
+    }
+    for (entity, pos) in q_fences.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        commands.entity(entity).insert((
+            Sprite::from_color(Color::srgb(0.45, 0.3, 0.15), Vec2::new(30.0, 10.0)),
+            Transform::from_xyz(at.x, at.y + 4.0, 1.2),
+        ));
+    }
+    for (entity, pos, gate) in q_gates.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        commands.entity(entity).insert((
+            Sprite::from_color(gate_color(gate.allowed), Vec2::new(30.0, 6.0)),
+            Transform::from_xyz(at.x, at.y + 4.0, 1.2),
+        ));
+    }
+    for (entity, pos) in q_shelters.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        commands.entity(entity).insert((
+            Sprite::from_color(Color::srgb(0.6, 0.45, 0.25), Vec2::new(26.0, 20.0)),
+            Transform::from_xyz(at.x, at.y + 8.0, 1.4),
+        ));
+    }
+    for (entity, pos) in q_exhausted.iter() {
+        // Brown X
+        let at = iso(&cfg, pos.x, pos.y);
+        commands.entity(entity).insert((
+            Sprite::from_color(Color::srgb(0.5, 0.25, 0.0), Vec2::new(10.0, 40.0)),
+            Transform::from_xyz(at.x, at.y, 0.1).with_rotation(Quat::from_rotation_z(0.785)),
+        ));
+    }
+    for (entity, pos) in q_creatures.iter() {
+        // update_creature_looks takes over size and colour
+        let at = iso(&cfg, pos.x, pos.y);
+        commands.entity(entity).insert((
+            Sprite::from_color(Color::srgb(1.0, 1.0, 1.0), Vec2::new(10.0, 10.0)),
+            Transform::from_xyz(at.x, at.y, 2.0),
+        ));
+    }
+    for (entity, pos) in q_shepherd.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        commands.entity(entity).insert((
+            Sprite::from_color(Color::srgb(0.2, 0.6, 1.0), Vec2::new(16.0, 26.0)),
+            Transform::from_xyz(at.x, at.y, 2.5),
+        ));
+    }
+    for (entity, pos) in q_birds.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        commands.entity(entity).insert((
+            Sprite::from_color(Color::srgb(0.15, 0.15, 0.2), Vec2::new(8.0, 4.0)),
+            Transform::from_xyz(at.x, at.y, 3.0),
+        ));
+    }
+    for (entity, pos) in q_insects.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        commands.entity(entity).insert((
+            Sprite::from_color(Color::srgb(1.0, 0.85, 0.1), Vec2::splat(3.0)),
+            Transform::from_xyz(at.x, at.y, 2.8),
+        ));
+    }
+}
+
+// Bodies lie flat, charred if lightning got them
+fn attach_corpse_sprites(mut commands: Commands, cfg: Res<SimulationConfig>, q_corpses: Query<(Entity, &GridPosition, &Corpse), Added<Corpse>>) {
+    for (entity, pos, corpse) in q_corpses.iter() {
+        let at = iso(&cfg, pos.x, pos.y);
+        let color = match (corpse.cause, corpse.species_id) {
+            (DeathCause::Lightning, _) => Color::srgb(0.15, 0.12, 0.1),
+            (_, 0) => Color::srgb(0.7, 0.68, 0.6),
+            _ => Color::srgb(0.35, 0.3, 0.3),
+        };
+        commands.entity(entity).insert((
+            Sprite::from_color(color, Vec2::new(12.0, 6.0)),
+            Transform::from_xyz(at.x, at.y, 1.8),
+        ));
+    }
+}
+
+// Bridges show their planks, water its depth, land its grass, wear and shade
+fn update_tile_colors(
+    cfg: Res<SimulationConfig>,
+    overlay: Res<PheromoneOverlay>,
+    pheromones: Res<PheromoneField>,
+    mut q_tiles: Query<(&Tile, &mut Sprite, &Footfall, Option<&Water>, Has<Bridge>, Has<Shaded>, &Grass, Option<&Pollution>)>,
+) {
+    for (tile, mut sprite, footfall, water, bridge, shaded, grass, pollution) in q_tiles.iter_mut() {
+        let mut color = match (water, bridge) {
+            (Some(_), true) => Color::srgb(0.55, 0.4, 0.2),
+            // Foul water turns a murky green-brown
+            (Some(water), false) => water_color(water.depth, &cfg)
+                .mix(&Color::srgb(0.35, 0.4, 0.2), pollution.map(|p| p.0).unwrap_or(0.0).clamp(0.0, 1.0) * 0.7),
+            (None, _) => land_color(footfall, shaded, grass.0),
+        };
+        if let Some(channel) = overlay.0 {
+            let strength = pheromones.get((tile.x, tile.y), channel) / cfg.pheromone_max.max(0.01);
+            color = color.mix(&pheromone_color(channel), strength.clamp(0.0, 1.0) * 0.8);
+        }
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
+
+fn update_crop_sprites(mut q_crops: Query<(&Crop, &mut Sprite), Changed<Crop>>) {
+    for (crop, mut sprite) in q_crops.iter_mut() {
+        let (color, size) = crop_look(crop.stage);
+        sprite.color = color;
+        sprite.custom_size = Some(Vec2::splat(size));
+    }
+}
+
+fn update_gate_sprites(mut q_gates: Query<(&Gate, &mut Sprite), Changed<Gate>>) {
+    for (gate, mut sprite) in q_gates.iter_mut() {
+        sprite.color = gate_color(gate.allowed);
+    }
+}
+
+// Size from age and genes, colour from digestion, breeding cooldown and hunger
+fn update_creature_looks(
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    lod: Res<LodState>,
+    mut q_creatures: Query<(
+        &mut Sprite,
+        &Hunger,
+        &Age,
+        &CreatureStats,
+        &Dna,
+        Has<Digesting>,
+        Has<Overfed>,
+        Has<ReproductionCooldown>,
+        Option<&Clade>,
+    ), With<Creature>>,
+    registry: Res<SpeciesRegistry>,
+) {
+    if lod.active { return; }
+    let current_time = time.elapsed_secs();
+
+    for (mut sprite, hunger, age, stats, dna, digesting, overfed, cooldown, clade) in q_creatures.iter_mut() {
+        let look = &cfg.s(stats.species_id).appearance;
+        // Apply DNA Size Multiplier
+        let my_size = look.size * dna.size_multiplier;
+
+        let target_scale = if age.is_adult { my_size } else { my_size / 2.0 };
+        sprite.custom_size = Some(Vec2::new(target_scale, target_scale));
+
+        if digesting {
+            // Dark while digesting
+            sprite.color = Color::srgb(0.2, 0.1, 0.05);
+        } else if overfed {
+            // Greenish tint (Overfed/Sluggish)
+            sprite.color = Color::srgb(0.4, 0.3, 0.1);
+        } else if cooldown {
+            let pulse = (current_time * 5.0).sin().abs(); // 0.0 to 1.0
+            let ([r0, g0, b0], [r1, g1, b1]) = look.cooldown_colors;
+            sprite.color = Color::srgb(r0 + (r1 - r0) * pulse, g0 + (g1 - g0) * pulse, b0 + (b1 - b0) * pulse);
+        } else {
+            // Hunger tint
+            let [r, g, b] = look.color_for(hunger.0);
+            sprite.color = Color::srgb(r, g, b);
+        }
+
+        // Split-off species wear their own colour over the usual state colours
+        if let Some(info) = clade.and_then(|c| registry.get(c.0)) {
+            let [r, g, b] = info.color;
+            sprite.color = sprite.color.mix(&Color::srgb(r, g, b), 0.4);
+        }
+
+        // Elders go grey whatever their state
+        if age.is_elder {
+            sprite.color = sprite.color.mix(&Color::srgb(0.6, 0.6, 0.6), 0.5);
+        }
+    }
+}
+
+// --- EDGE COMPASS ---
+// Arrows on the screen edge towards the selected creature, the nearest pack
+// and the largest herd while they're off screen. Nearby creatures of one
+// species (same compass_group_cell cell) count as one group.
+fn update_edge_compass(
+    cfg: Res<SimulationConfig>,
+    behaviors: Res<SpeciesBehaviors>,
+    selected: Res<SelectedCreature>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    q_creatures: Query<(&GridPosition, &CreatureStats, &CreatureName), (With<Creature>, Without<Dead>)>,
+    mut q_markers: Query<(&CompassMarker, &mut Node, &mut Text)>,
+) {
+    let Ok((camera, camera_transform)) = q_camera.single() else { return; };
+    let Some(viewport) = camera.logical_viewport_size() else { return; };
+    let view_centre = camera_transform.translation().truncate();
+
+    let cell = cfg.compass_group_cell.max(1);
+    let mut cells: HashMap<(u32, i32, i32), (Vec2, u32)> = HashMap::new();
+    for (pos, stats, _) in q_creatures.iter() {
+        let group = cells.entry((stats.species_id, pos.x.div_euclid(cell), pos.y.div_euclid(cell))).or_insert((Vec2::ZERO, 0));
+        group.0 += iso(&cfg, pos.x, pos.y);
+        group.1 += 1;
+    }
+    // Sorted so ties don't flicker between frames
+    let mut groups: Vec<_> = cells.into_iter().collect();
+    groups.sort_by_key(|&(key, _)| key);
+    let groups: Vec<(u32, Vec2, u32)> = groups.into_iter().map(|((species, _, _), (sum, n))| (species, sum / n as f32, n)).collect();
+
+    let label = |species: u32, count: u32| format!("{} x{}", cfg.s(species).name, count);
+    let pack = groups
+        .iter()
+        .filter(|(species, ..)| behaviors.is_hunter(*species))
+        .min_by(|a, b| a.1.distance(view_centre).total_cmp(&b.1.distance(view_centre)))
+        .map(|&(species, at, count)| (at, label(species, count)));
+    let herd = groups
+        .iter()
+        .filter(|(species, ..)| !behaviors.is_hunter(*species))
+        .max_by_key(|(_, _, count)| *count)
+        .map(|&(species, at, count)| (at, label(species, count)));
+
+    for (marker, mut node, mut text) in q_markers.iter_mut() {
+        let target = match marker.0 {
+            CompassTarget::Selected => selected
+                .0
+                .and_then(|e| q_creatures.get(e).ok())
+                .map(|(pos, _, name)| (iso(&cfg, pos.x, pos.y), name.0.clone())),
+            CompassTarget::NearestPack => pack.clone(),
+            CompassTarget::LargestHerd => herd.clone(),
+        };
+        let on_screen = |p: Vec2| p.x >= 0.0 && p.y >= 0.0 && p.x <= viewport.x && p.y <= viewport.y;
+        let screen = target.as_ref().and_then(|(at, _)| camera.world_to_viewport(camera_transform, at.extend(0.0)).ok());
+        let (Some(screen), Some((_, name))) = (screen.filter(|&p| !on_screen(p)), target) else {
+            if node.display != Display::None {
+                node.display = Display::None;
+            }
+            continue;
+        };
+
+        // Where the line from the screen centre to the target leaves the screen
+        let centre = viewport / 2.0;
+        let dir = screen - centre;
+        let room = centre - Vec2::splat(24.0);
+        let at = centre + dir * (room.x / dir.x.abs()).min(room.y / dir.y.abs());
+
+        let across = if dir.x.abs() > dir.y.abs() * 0.4 { if dir.x < 0.0 { "<" } else { ">" } } else { "" };
+        let up_down = if dir.y.abs() > dir.x.abs() * 0.4 { if dir.y < 0.0 { "^" } else { "v" } } else { "" };
+        let arrow = format!("{}{}", up_down, across);
+        set_text(&mut text, if dir.x < 0.0 { format!("{} {}", arrow, name) } else { format!("{} {}", name, arrow) });
+
+        // Anchored on the side it points to, so the label grows inwards
+        let mut placed = node.clone();
+        placed.display = Display::Flex;
+        (placed.left, placed.right) = if dir.x < 0.0 { (Val::Px(at.x), Val::Auto) } else { (Val::Auto, Val::Px(viewport.x - at.x)) };
+        (placed.top, placed.bottom) = if dir.y < 0.0 { (Val::Px(at.y), Val::Auto) } else { (Val::Auto, Val::Px(viewport.y - at.y)) };
+        node.set_if_neq(placed);
+    }
+}
+
+// --- CREATURE LABELS ---
+// While any of the config's label_* switches is on, each creature gets a label
+// child showing what they pick; turning them all off removes the labels.
+// Hidden with the creatures when zoomed out.
+fn update_creature_labels(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    lod: Res<LodState>,
+    q_creatures: Query<(
+        Entity,
+        &Hunger,
+        &CreatureStats,
+        &CreatureName,
+        Has<ReproductionCooldown>,
+        Option<&Parasites>,
+        Has<BerryStun>,
+        Option<&Labelled>,
+    ), (With<Creature>, With<Sprite>, Without<Dead>)>,
+    mut q_labels: Query<&mut Text2d, With<CreatureLabel>>,
+) {
+    let enabled = cfg.label_names || cfg.label_hungry || cfg.label_breeding || cfg.label_parasites || cfg.label_stunned;
+    for (entity, hunger, stats, name, breeding, parasites, stunned, labelled) in q_creatures.iter() {
+        if !enabled {
+            if let Some(label) = labelled {
+                commands.entity(label.0).despawn();
+                commands.entity(entity).remove::<Labelled>();
+            }
+            continue;
+        }
+
+        let mut parts: Vec<String> = Vec::new();
+        if !lod.active {
+            if cfg.label_names { parts.push(name.0.clone()); }
+            if cfg.label_hungry && hunger.0 > cfg.s(stats.species_id).appearance.hungry_hunger { parts.push("!".into()); }
+            if cfg.label_breeding && breeding { parts.push("+".into()); }
+            if cfg.label_parasites {
+                if let Some(p) = parasites.filter(|p| p.count > 0) { parts.push("~".repeat(p.count.min(3) as usize)); }
+            }
+            if cfg.label_stunned && stunned { parts.push("*".into()); }
+        }
+        let value = parts.join(" ");
+
+        match labelled.and_then(|l| q_labels.get_mut(l.0).ok()) {
+            Some(mut text) if text.0 != value => text.0 = value,
+            Some(_) => {}
+            None if labelled.is_none() => {
+                let label = commands
+                    .spawn((
+                        Text2d::new(value),
+                        TextFont { font_size: 10.0, ..default() },
+                        Transform::from_xyz(0.0, 14.0, 1.0),
+                        CreatureLabel,
+                    ))
+                    .id();
+                commands.entity(entity).add_child(label).insert(Labelled(label));
+            }
+            None => {}
+        }
+    }
+}
+
+// What the selected creature can perceive. Distances are in grid steps, so a
+// range is a diamond on the grid and an upright rectangle on screen.
+fn draw_perception_gizmos(
+    mut gizmos: Gizmos,
+    cfg: Res<SimulationConfig>,
+    behaviors: Res<SpeciesBehaviors>,
+    pheromones: Res<PheromoneField>,
+    selected: Res<SelectedCreature>,
+    q_creatures: Query<(&GridPosition, &CreatureStats, &Age), With<Creature>>,
+) {
+    let Some((pos, stats, age)) = selected.0.and_then(|e| q_creatures.get(e).ok()) else { return; };
+    let here = iso(&cfg, pos.x, pos.y);
+    let range_rect = |range: f32| Vec2::new(range * cfg.tile_w, range * cfg.tile_h);
+
+    // Targets count strictly inside sight
+    let sight = cfg.sight_for(stats, age);
+    gizmos.rect_2d(here, range_rect(sight as f32 - 0.5), Color::srgba(0.3, 0.8, 1.0, 0.8));
+
+    // Alarm calls reach the kinds that flee from something
+    let behavior = behaviors.get(stats.species_id);
+    if cfg.alarm_hearing_range > 0 && behavior.rules.iter().any(|r| r.goal == Goal::Flee) {
+        gizmos.rect_2d(here, range_rect(cfg.alarm_hearing_range as f32 + 0.5), Color::srgba(1.0, 0.8, 0.2, 0.6));
+    }
+
+    // Outline of its species' marked range, as far as it could walk in two sights
+    if !behavior.scents.iter().any(|sc| sc.channel == Pheromone::Territory && sc.deposit > 0.0) { return; }
+    let marked = |x: i32, y: i32| pheromones.get((x, y), Pheromone::Territory) >= cfg.territory_outline_level;
+    let (hw, hh) = (cfg.tile_w / 2.0, cfg.tile_h / 2.0);
+    let reach = sight * 2;
+    for x in pos.x - reach..=pos.x + reach {
+        for y in pos.y - reach..=pos.y + reach {
+            if !marked(x, y) { continue; }
+            let c = iso(&cfg, x, y);
+            let (e, n, w, s) = (c + Vec2::new(hw, 0.0), c + Vec2::new(0.0, hh), c + Vec2::new(-hw, 0.0), c + Vec2::new(0.0, -hh));
+            // The edge shared with each unmarked neighbour
+            for ((dx, dy), (a, b)) in [((1, 0), (e, n)), ((-1, 0), (w, s)), ((0, 1), (n, w)), ((0, -1), (s, e))] {
+                if !marked(x + dx, y + dy) {
+                    gizmos.line_2d(a, b, Color::srgb(0.9, 0.3, 0.6));
+                }
+            }
+        }
+    }
+}
+
+// While the overlay is on every creature carries a DecisionTrace (new ones
+// get theirs here); turning it off leaves only the selection's
+fn intent_overlay_system(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    selected: Res<SelectedCreature>,
+    mut overlay: ResMut<IntentOverlay>,
+    q_untraced: Query<Entity, (With<Creature>, Without<Dead>, Without<DecisionTrace>)>,
+    q_traced: Query<Entity, With<DecisionTrace>>,
+) {
+    if focus.active.is_none() && keys.just_pressed(KeyCode::KeyI) {
+        overlay.0 = !overlay.0;
+        if !overlay.0 {
+            for entity in q_traced.iter().filter(|&e| Some(e) != selected.0) {
+                commands.entity(entity).remove::<DecisionTrace>();
+            }
+        }
+    }
+    if overlay.0 {
+        for entity in q_untraced.iter() {
+            commands.entity(entity).insert(DecisionTrace::default());
+        }
+    }
+}
+
+fn draw_intent_arrows(
+    mut gizmos: Gizmos,
+    cfg: Res<SimulationConfig>,
+    overlay: Res<IntentOverlay>,
+    selected: Res<SelectedCreature>,
+    q_creatures: Query<(Entity, &GridPosition, &DecisionTrace), (With<Creature>, Without<Dead>)>,
+) {
+    if !overlay.0 { return; }
+    for (entity, pos, trace) in q_creatures.iter() {
+        // draw_decision_gizmos has the selection
+        if Some(entity) == selected.0 { continue; }
+        let (Some((tx, ty)), Some(goal)) = (trace.target, trace.goal) else { continue; };
+        gizmos.arrow_2d(iso(&cfg, pos.x, pos.y), iso(&cfg, tx, ty), intent_color(goal));
+    }
+}
+
+// Gizmo bars, so they cost no entities: full green, starving red
+fn draw_hunger_bars(
+    mut gizmos: Gizmos,
+    cfg: Res<SimulationConfig>,
+    lod: Res<LodState>,
+    selected: Res<SelectedCreature>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    q_creatures: Query<(Entity, &GridPosition, &Transform, &Hunger), (With<Creature>, Without<Dead>)>,
+) {
+    if cfg.hunger_bars == BarMode::Never || lod.active { return; }
+    let hovered = match (q_window.single(), q_camera.single()) {
+        (Ok(window), Ok((camera, camera_transform))) => hovered_tile(window, camera, camera_transform),
+        _ => None,
+    };
+
+    for (entity, pos, transform, hunger) in q_creatures.iter() {
+        let shown = cfg.hunger_bars == BarMode::Always || selected.0 == Some(entity) || hovered == Some((pos.x, pos.y));
+        if !shown { continue; }
+
+        let fullness = (1.0 - hunger.0 / 100.0).clamp(0.0, 1.0);
+        let left = transform.translation.truncate() + Vec2::new(-8.0, 10.0);
+        gizmos.line_2d(left, left + Vec2::new(16.0, 0.0), Color::srgb(0.15, 0.15, 0.15));
+        if fullness > 0.0 {
+            let color = Color::srgb(1.0 - fullness, fullness, 0.1);
+            gizmos.line_2d(left, left + Vec2::new(16.0 * fullness, 0.0), color);
+        }
+    }
+}
+
+// --- CAMERA ZOOM & LEVEL OF DETAIL ---
+// Mouse wheel zooms around the map centre within camera_zoom_bounds
+fn camera_zoom(
+    cfg: Res<SimulationConfig>,
+    mut wheel: MessageReader<MouseWheel>,
+    mut q_camera: Query<&mut Transform, With<MainCamera>>,
+) {
+    let scroll: f32 = wheel
+        .read()
+        .map(|e| match e.unit {
+            MouseScrollUnit::Line => e.y,
+            MouseScrollUnit::Pixel => e.y / 40.0,
+        })
+        .sum();
+    if scroll == 0.0 { return; }
+    let Ok(mut camera) = q_camera.single_mut() else { return; };
+
+    let (min, max) = cfg.camera_zoom_bounds;
+    let zoom = (camera.scale.x * 1.1_f32.powf(-scroll)).clamp(min, max);
+    camera.scale = Vec3::new(zoom, zoom, 1.0);
+}
+
+// Far out, individual creatures are noise: draw one blob per cell and species instead
+#[derive(Resource)]
+struct LodState {
+    active: bool,
+    refresh: Timer,
+}
+
+impl Default for LodState {
+    fn default() -> Self {
+        Self { active: false, refresh: Timer::from_seconds(0.25, TimerMode::Repeating) }
+    }
+}
+
+#[derive(Component)]
+struct LodBlob;
+
+fn update_lod(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    mut lod: ResMut<LodState>,
+    q_camera: Query<&Transform, With<MainCamera>>,
+    mut q_creatures: Query<(&GridPosition, &CreatureStats, &mut Visibility), (With<Creature>, Without<Dead>)>,
+    q_blobs: Query<Entity, With<LodBlob>>,
+) {
+    let Ok(camera) = q_camera.single() else { return; };
+    let active = camera.scale.x >= cfg.lod_zoom_threshold;
+    let switched = active != lod.active;
+    lod.active = active;
+
+    // Creatures born while zoomed out start hidden too
+    let wanted = if active { Visibility::Hidden } else { Visibility::Inherited };
+    for (_, _, mut visibility) in q_creatures.iter_mut() {
+        visibility.set_if_neq(wanted);
+    }
+
+    if !active {
+        if switched {
+            for entity in q_blobs.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    }
+    if !switched && !lod.refresh.tick(time.delta()).just_finished() { return; }
+
+    for entity in q_blobs.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let cell = cfg.lod_cell_size.max(1);
+    let mut counts: HashMap<(i32, i32, u32), u32> = HashMap::new();
+    for (pos, stats, _) in q_creatures.iter() {
+        *counts.entry((pos.x.div_euclid(cell), pos.y.div_euclid(cell), stats.species_id)).or_default() += 1;
+    }
+
+    let half = (cell - 1) as f32 / 2.0;
+    let mut species: Vec<u32> = cfg.species.keys().copied().collect();
+    species.sort_unstable();
+    for ((cx, cy, species_id), count) in counts {
+        // Cell centre in grid space, each species shifted a little so all of them show
+        let index = species.iter().position(|&id| id == species_id).unwrap_or(0);
+        let shift = index as f32 / species.len() as f32;
+        let gx = (cx * cell) as f32 + half + shift;
+        let gy = (cy * cell) as f32 + half;
+        let at = Vec2::new((gx - gy) * (cfg.tile_w / 2.0), (gx + gy) * (cfg.tile_h / 2.0));
+
+        let [r, g, b] = cfg.s(species_id).appearance.blob_color;
+        let alpha = (0.35 + 0.1 * count as f32).min(0.9);
+        let size = cfg.tile_w * (0.6 + 0.35 * (count as f32).sqrt()).min(cell as f32);
+        commands.spawn((
+            Sprite::from_color(Color::srgba(r, g, b, alpha), Vec2::new(size, size / 2.0)),
+            Transform::from_xyz(at.x, at.y, 2.0),
+            LodBlob,
+        ));
+    }
+}
+
+// --- BALANCE GOVERNOR ---
+fn toggle_governor(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut cfg: ResMut<SimulationConfig>,
+    mut governor: ResMut<BalanceGovernor>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyG) { return; }
+    cfg.governor_enabled = !cfg.governor_enabled;
+    *governor = BalanceGovernor::default();
+    println!("Balance governor {}", if cfg.governor_enabled { "on" } else { "off" });
+}
+
+// O switches between Sandbox and Observer (challenges keep their mode)
+fn toggle_observer_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mode: Res<State<GameMode>>,
+    mut next_mode: ResMut<NextState<GameMode>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyO) { return; }
+    match mode.get() {
+        GameMode::Sandbox => next_mode.set(GameMode::Observer),
+        GameMode::Observer => next_mode.set(GameMode::Sandbox),
+        GameMode::Challenge | GameMode::Management | GameMode::Experiment => {}
+    }
+}
+
+// P cycles the scent overlay: off, then each channel in turn
+fn cycle_pheromone_overlay(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    cfg: Res<SimulationConfig>,
+    mut overlay: ResMut<PheromoneOverlay>,
+    mut notifications: ResMut<Notifications>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyP) { return; }
+
+    overlay.0 = match overlay.0 {
+        None => Pheromone::ALL.first().copied(),
+        Some(channel) => Pheromone::ALL.iter().skip_while(|&&c| c != channel).nth(1).copied(),
+    };
+    let label = overlay.0.map(|c| c.label()).unwrap_or("off");
+    notifications.push(format!("Scent overlay: {}", label), &cfg);
+}
+
+fn toggle_management_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mode: Res<State<GameMode>>,
+    mut next_mode: ResMut<NextState<GameMode>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyE) { return; }
+    match mode.get() {
+        GameMode::Sandbox => next_mode.set(GameMode::Management),
+        GameMode::Management => next_mode.set(GameMode::Sandbox),
+        GameMode::Challenge | GameMode::Observer | GameMode::Experiment => {}
+    }
+}
+
+// X: sandbox <-> evolution experiment (fitness tracking and reports)
+fn toggle_experiment_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mode: Res<State<GameMode>>,
+    mut next_mode: ResMut<NextState<GameMode>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyX) { return; }
+    match mode.get() {
+        GameMode::Sandbox => next_mode.set(GameMode::Experiment),
+        GameMode::Experiment => next_mode.set(GameMode::Sandbox),
+        GameMode::Challenge | GameMode::Observer | GameMode::Management => {}
+    }
+}
+
+// --- PICTURE IN PICTURE ---
+// V pins the selected creature (or unpins); a corner viewport follows it
+// wherever the main camera is
+fn toggle_pin(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    cfg: Res<SimulationConfig>,
+    selected: Res<SelectedCreature>,
+    mut pinned: ResMut<PinnedCreature>,
+    mut notifications: ResMut<Notifications>,
+    q_names: Query<&CreatureName>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyV) { return; }
+    pinned.0 = match (pinned.0, selected.0) {
+        (None, Some(entity)) => {
+            if let Ok(name) = q_names.get(entity) {
+                notifications.push(format!("Watching {}", name.0), &cfg);
+            }
+            Some(entity)
+        }
+        _ => None,
+    };
+}
+
+fn update_pip_camera(
+    mut pinned: ResMut<PinnedCreature>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_creatures: Query<&Transform, (With<Creature>, Without<Dead>, Without<PipCamera>)>,
+    mut q_pip: Query<(&mut Camera, &mut Transform), With<PipCamera>>,
+) {
+    let Ok((mut camera, mut transform)) = q_pip.single_mut() else { return; };
+    // Dead and gone: close the viewport
+    let followed = pinned.0.and_then(|e| q_creatures.get(e).ok());
+    if followed.is_none() {
+        pinned.0 = None;
+    }
+    let (Some(creature), Ok(window)) = (followed, q_window.single()) else {
+        camera.is_active = false;
+        return;
+    };
+
+    // A quarter of the window, bottom right
+    let window_size = window.physical_size();
+    let size = (window_size / 4).max(UVec2::ONE);
+    let margin = UVec2::splat(16).min(window_size - size);
+    let viewport = Viewport { physical_position: window_size - size - margin, physical_size: size, ..default() };
+    if camera.viewport.as_ref().map(|v| (v.physical_position, v.physical_size)) != Some((viewport.physical_position, viewport.physical_size)) {
+        camera.viewport = Some(viewport);
+    }
+    camera.is_active = true;
+    transform.translation.x = creature.translation.x;
+    transform.translation.y = creature.translation.y;
+}
+
+// Blood FX where a creature was killed (see DeathCause::bleeds)
+fn kill_fx_system(mut commands: Commands, cfg: Res<SimulationConfig>, mut deaths: MessageReader<DeathEvent>) {
+    for e in deaths.read() {
+        if !e.cause.bleeds() { continue; }
+        let (x, y) = e.pos;
+        let screen_x = (x - y) as f32 * (cfg.tile_w / 2.0);
+        let screen_y = (x + y) as f32 * (cfg.tile_h / 2.0);
+        commands.spawn((
+            Sprite::from_color(Color::srgb(0.8, 0.0, 0.0), Vec2::new(10.0, 40.0)),
+            Transform::from_xyz(screen_x, screen_y, 0.1).with_rotation(Quat::from_rotation_z(0.785)),
+            BloodStain(Timer::from_seconds(cfg.blood_fx_seconds, TimerMode::Once)),
+            GridPosition { x, y },
+        ));
+    }
+}
+
+// Blood is just FX and fades on a timer
+fn fade_blood_stains(mut commands: Commands, time: Res<Time>, mut q_blood: Query<(Entity, &mut BloodStain)>) {
+    for (entity, mut blood) in q_blood.iter_mut() {
+        blood.0.tick(time.delta());
+        if blood.0.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// A new or loaded world starts without the old one's leftovers
+fn clear_world_fx(
+    mut commands: Commands,
+    mut cleared: MessageReader<WorldCleared>,
+    q_blood: Query<Entity, With<BloodStain>>,
+) {
+    if cleared.read().count() == 0 { return; }
+    for entity in q_blood.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// --- ENTITY BUDGETS ---
+// With the FX hard cap on, blood gets whatever the soil markers leave of
+// fx_budget; the oldest stains go first
+fn cap_blood_stains(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    q_blood: Query<(Entity, &BloodStain)>,
+    q_markers: Query<(), (With<ExhaustedSoil>, Without<Dead>)>,
+) {
+    if !cfg.fx_hard_cap { return; }
+    let room = (cfg.fx_budget as usize).saturating_sub(q_markers.iter().count());
+    let excess = q_blood.iter().count().saturating_sub(room);
+    if excess == 0 { return; }
+
+    let mut stains: Vec<(f32, Entity)> = q_blood.iter().map(|(e, b)| (b.0.elapsed_secs(), e)).collect();
+    stains.sort_by(|a, b| b.0.total_cmp(&a.0));
+    for (_, entity) in stains.into_iter().take(excess) {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn update_budget_banner(
+    cfg: Res<SimulationConfig>,
+    q_creatures: Query<(), (With<Creature>, Without<Dead>)>,
+    q_plants: Query<(), (Or<(With<Plant>, With<Crop>)>, Without<Dead>)>,
+    q_blood: Query<(), With<BloodStain>>,
+    q_markers: Query<(), (With<ExhaustedSoil>, Without<Dead>)>,
+    q_corpses: Query<(), (With<Corpse>, Without<Dead>)>,
+    mut over_before: Local<Vec<&'static str>>,
+    mut q_text: Query<&mut Text, With<BudgetBannerText>>,
+) {
+    let categories = [
+        ("Creatures", q_creatures.iter().count(), cfg.creature_budget),
+        ("Plants", q_plants.iter().count(), cfg.plant_budget),
+        ("FX", q_blood.iter().count() + q_markers.iter().count(), cfg.fx_budget),
+        ("Corpses", q_corpses.iter().count(), cfg.corpse_budget),
+    ];
+    let over: Vec<_> = categories.iter().filter(|(_, count, budget)| *count > *budget as usize).collect();
+
+    // Log each category once when it goes over
+    for (name, count, budget) in &over {
+        if !over_before.contains(name) {
+            println!("Entity budget exceeded: {} {}/{}", name, count, budget);
+        }
+    }
+    *over_before = over.iter().map(|(name, _, _)| *name).collect();
+
+    let Ok(mut text) = q_text.single_mut() else { return; };
+    let banner = if over.is_empty() {
+        String::new()
+    } else {
+        let list: Vec<String> = over.iter().map(|(name, count, budget)| format!("{} {}/{}", name, count, budget)).collect();
+        format!("Over entity budget: {}", list.join(", "))
+    };
+    if **text != banner {
+        **text = banner;
+    }
+}
+
+// --- MILESTONES ---
+// Progress survives restarts in MILESTONES_FILE
+const MILESTONES_FILE: &str = "milestones.ron"; // in data_dir()
+
+// (id, title, target); progress is the best value ever reached
+const MILESTONE_DEFS: &[(&str, &str, f32)] = &[
+    ("hundred_sheep", "A hundred sheep alive at once", 100.0),
+    ("old_wolf", "A wolf lives to 5 minutes", 300.0),
+    ("drought_survivor", "Both species survive a drought", 1.0),
+    ("ten_generations", "Generation 10 is born", 10.0),
+];
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+struct Milestones {
+    progress: HashMap<String, f32>,
+    unlocked: HashMap<String, u64>, // id -> unix time
+    #[serde(skip)]
+    in_drought: bool,
+    #[serde(skip)]
+    dirty: bool, // progress moved since the last save
+}
+
+impl Milestones {
+    fn load() -> Self {
+        std::fs::read_to_string(data_path(MILESTONES_FILE))
+            .ok()
+            .and_then(|text| ron::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(text) => {
+                if let Err(err) = write_data_file(MILESTONES_FILE, text) {
+                    println!("Could not save {}: {}", MILESTONES_FILE, err);
+                }
+            }
+            Err(err) => println!("Could not save {}: {}", MILESTONES_FILE, err),
+        }
+    }
+
+    // Raise progress on `id`; returns the title if this unlocked it
+    fn record(&mut self, id: &str, value: f32) -> Option<&'static str> {
+        let (_, title, target) = MILESTONE_DEFS.iter().find(|(mid, _, _)| *mid == id)?;
+        let best = self.progress.entry(id.to_string()).or_insert(0.0);
+        if value <= *best { return None; }
+        *best = value;
+        self.dirty = true;
+
+        if value >= *target && !self.unlocked.contains_key(id) {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            self.unlocked.insert(id.to_string(), now);
+            return Some(title);
+        }
+        None
+    }
+}
+
+fn track_milestones(
+    time: Res<Time>,
+    mut since_save: Local<f32>,
+    cfg: Res<SimulationConfig>,
+    mut milestones: ResMut<Milestones>,
+    mut notifications: ResMut<Notifications>,
+    mut births: MessageReader<BirthEvent>,
+    mut weather: MessageReader<WeatherChanged>,
+    q_creatures: Query<(&CreatureStats, &Age), (With<Creature>, Without<Dead>)>,
+) {
+    let mut sheep = 0;
+    let mut wolves = 0;
+    let mut oldest_wolf: f32 = 0.0;
+    for (stats, age) in q_creatures.iter() {
+        match stats.species_id {
+            0 => sheep += 1,
+            1 => {
+                wolves += 1;
+                oldest_wolf = oldest_wolf.max(age.seconds_alive);
+            }
+            _ => {}
+        }
+    }
+
+    let mut updates: Vec<(&str, f32)> = vec![("hundred_sheep", sheep as f32), ("old_wolf", oldest_wolf)];
+
+    if let Some(top) = births.read().map(|e| e.generation).max() {
+        updates.push(("ten_generations", top as f32));
+    }
+
+    for e in weather.read() {
+        let was_drought = milestones.in_drought;
+        milestones.in_drought = e.kind == WeatherKind::Drought;
+        if was_drought && !milestones.in_drought && sheep > 0 && wolves > 0 {
+            updates.push(("drought_survivor", 1.0));
+        }
+    }
+
+    let mut unlocked_any = false;
+    for (id, value) in updates {
+        if let Some(title) = milestones.record(id, value) {
+            notifications.push(format!("Milestone: {}", title), &cfg);
+            unlocked_any = true;
+        }
+    }
+
+    // Unlocks are written straight away; creeping progress (wolf age) at most every 10s
+    *since_save += time.delta().as_secs_f32();
+    if milestones.dirty && (unlocked_any || *since_save >= 10.0) {
+        milestones.save();
+        milestones.dirty = false;
+        *since_save = 0.0;
+    }
+}
+
+#[derive(Component)]
+struct MilestonesPageRoot;
+
+#[derive(Component)]
+struct MilestonesPageText;
+
+fn setup_milestones_page(mut commands: Commands) {
+    commands
+        .spawn((
+            MilestonesPageRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(35.0),
+                top: Val::Percent(25.0),
+                width: Val::Px(360.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                row_gap: Val::Px(8.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Milestones (M)"),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                Text::new(""),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                MilestonesPageText,
+            ));
+        });
+}
+
+fn toggle_milestones_page(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut q_page: Query<&mut Visibility, With<MilestonesPageRoot>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyM) { return; }
+    let Ok(mut visibility) = q_page.single_mut() else { return; };
+    *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+}
+
+fn update_milestones_page(
+    milestones: Res<Milestones>,
+    q_page: Query<&Visibility, With<MilestonesPageRoot>>,
+    mut q_text: Query<&mut Text, With<MilestonesPageText>>,
+) {
+    let Ok(visibility) = q_page.single() else { return; };
+    if *visibility == Visibility::Hidden { return; }
+
+    let lines: Vec<String> = MILESTONE_DEFS
+        .iter()
+        .map(|(id, title, target)| {
+            let best = milestones.progress.get(*id).copied().unwrap_or(0.0);
+            let mark = if milestones.unlocked.contains_key(*id) { "[x]" } else { "[ ]" };
+            format!("{} {} ({:.0}/{:.0})", mark, title, best.min(*target), target)
+        })
+        .collect();
+
+    for mut text in q_text.iter_mut() {
+        **text = lines.join("\n");
+    }
+}
+
+// --- WORLD RECORDS ---
+// Unlike milestones these belong to the world: they go with the save
+#[derive(Component)]
+struct RecordsPanelRoot;
+
+#[derive(Component)]
+struct RecordsPanelText;
+
+fn setup_records_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            RecordsPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(5.0),
+                top: Val::Percent(25.0),
+                width: Val::Px(380.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                row_gap: Val::Px(8.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("World records (L)"),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                Text::new(""),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                RecordsPanelText,
+            ));
+        });
+}
+
+fn toggle_records_panel(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut q_panel: Query<&mut Visibility, With<RecordsPanelRoot>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyL) { return; }
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+    *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+}
+
+fn update_records_panel(
+    records: Res<WorldRecords>,
+    q_panel: Query<&Visibility, With<RecordsPanelRoot>>,
+    mut q_text: Query<&mut Text, With<RecordsPanelText>>,
+) {
+    let Ok(visibility) = q_panel.single() else { return; };
+    if *visibility == Visibility::Hidden { return; }
+
+    let lines: Vec<String> = RecordKind::ALL
+        .iter()
+        .map(|kind| match records.get(*kind) {
+            Some(r) => format!("{}: {} - {} (day {:.0})", kind.label(), kind.format_value(r.value), r.holder, r.day),
+            None => format!("{}: -", kind.label()),
+        })
+        .collect();
+
+    for mut text in q_text.iter_mut() {
+        set_text(&mut text, lines.join("\n"));
+    }
+}
+
+// --- BEHAVIOR EDITOR (B) ---
+// Each species' decision rules with their weights, in priority order, plus
+// whether its members are altruistic. Edits apply from the next move and last
+// until the behavior file is reloaded or a new game starts. Sandbox only, like
+// the config sliders.
+const BEHAVIOR_WEIGHT_STEP: i32 = 5;
+
+#[derive(Component)]
+struct BehaviorEditorRoot;
+
+#[derive(Component)]
+struct BehaviorEditorList;
+
+#[derive(Component)]
+struct RuleWeightButton {
+    species_id: u32,
+    rule: usize,
+    delta: i32,
+}
+
+#[derive(Component)]
+struct RaiseRuleButton {
+    species_id: u32,
+    rule: usize,
+}
+
+#[derive(Component)]
+struct AltruismButton(u32);
+
+fn describe_rule(rule: &BehaviorRule, cfg: &SimulationConfig) -> String {
+    let mut out = format!("{:?}", rule.goal);
+    if !rule.species.is_empty() {
+        let names: Vec<&str> = rule.species.iter().filter_map(|id| cfg.species.get(id)).map(|sc| sc.name.as_str()).collect();
+        out.push_str(&format!(" {}", names.join("/")));
+    }
+    if rule.target_adults_only { out.push_str(" (adults)"); }
+    match rule.stage {
+        StageFilter::Any => {}
+        StageFilter::Adult => out.push_str(", as adult"),
+        StageFilter::Baby => out.push_str(", as baby"),
+    }
+    if let Some(m) = rule.min_hunger { out.push_str(&format!(", hunger >= {:.0}", m)); }
+    if let Some(m) = rule.max_hunger { out.push_str(&format!(", hunger <= {:.0}", m)); }
+    out
+}
+
+fn setup_behavior_editor(mut commands: Commands) {
+    commands
+        .spawn((
+            BehaviorEditorRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(30.0),
+                top: Val::Percent(15.0),
+                width: Val::Px(420.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                row_gap: Val::Px(8.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Behavior editor (B)"),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                BehaviorEditorList,
+                Node {
+                    row_gap: Val::Px(4.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn toggle_behavior_editor(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut q_panel: Query<&mut Visibility, With<BehaviorEditorRoot>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::KeyB) { return; }
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+    *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+}
+
+// -/+ change a rule's weight, ^ swaps it with the one above; the altruism
+// button flips every living member (newborns take after their parent)
+fn behavior_editor_buttons(
+    mut behaviors: ResMut<SpeciesBehaviors>,
+    q_weight: Query<(&Interaction, &RuleWeightButton), Changed<Interaction>>,
+    q_raise: Query<(&Interaction, &RaiseRuleButton), Changed<Interaction>>,
+    q_altruism: Query<(&Interaction, &AltruismButton), Changed<Interaction>>,
+    mut q_creatures: Query<(&CreatureStats, &mut CreatureBehavior), Without<Dead>>,
+) {
+    for (interaction, button) in q_weight.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        if let Some(def) = behaviors.0.get_mut(&button.species_id) {
+            def.nudge_weight(button.rule, button.delta);
+        }
+    }
+    for (interaction, button) in q_raise.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        if let Some(def) = behaviors.0.get_mut(&button.species_id) {
+            def.raise(button.rule);
+        }
+    }
+    for (interaction, button) in q_altruism.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        let members: Vec<bool> =
+            q_creatures.iter().filter(|(stats, _)| stats.species_id == button.0).map(|(_, b)| b.altruistic).collect();
+        // Mostly altruistic species turn selfish, and the other way round
+        let turn_to = members.iter().filter(|a| **a).count() * 2 < members.len();
+        for (stats, mut behavior) in q_creatures.iter_mut() {
+            if stats.species_id == button.0 {
+                behavior.altruistic = turn_to;
+            }
+        }
+        behaviors.set_changed(); // so the editor redraws the count
+    }
+}
+
+// Rebuilt on opening and after every edit
+fn update_behavior_editor(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    behaviors: Res<SpeciesBehaviors>,
+    q_panel: Query<Ref<Visibility>, With<BehaviorEditorRoot>>,
+    q_list: Query<Entity, With<BehaviorEditorList>>,
+    q_creatures: Query<(&CreatureStats, &CreatureBehavior), Without<Dead>>,
+) {
+    let Ok(visibility) = q_panel.single() else { return; };
+    if *visibility == Visibility::Hidden || !(visibility.is_changed() || behaviors.is_changed()) { return; }
+    let Ok(list) = q_list.single() else { return; };
+
+    let mut species: Vec<u32> = behaviors.0.keys().copied().collect();
+    species.sort_unstable();
+    let small_button = |color: Color| {
+        (
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(color),
+        )
+    };
+    let label = |text: String, size: f32| (Text::new(text), TextFont { font_size: size, ..default() }, TextColor(Color::srgb(1.0, 1.0, 1.0)));
+
+    commands.entity(list).despawn_related::<Children>();
+    commands.entity(list).with_children(|p| {
+        for species_id in species {
+            let Some(sc) = cfg.species.get(&species_id) else { continue; };
+            let members: Vec<bool> =
+                q_creatures.iter().filter(|(stats, _)| stats.species_id == species_id).map(|(_, b)| b.altruistic).collect();
+            p.spawn(Node { column_gap: Val::Px(8.0), align_items: AlignItems::Center, ..default() }).with_children(|row| {
+                row.spawn(label(sc.name.clone(), 16.0));
+                row.spawn((AltruismButton(species_id), small_button(Color::srgb(0.2, 0.3, 0.2))))
+                    .with_children(|b| {
+                        let altruistic = members.iter().filter(|a| **a).count();
+                        b.spawn(label(format!("Altruistic: {} of {}", altruistic, members.len()), 13.0));
+                    });
+            });
+
+            for (i, rule) in behaviors.get(species_id).rules.iter().enumerate() {
+                p.spawn(Node { column_gap: Val::Px(4.0), align_items: AlignItems::Center, ..default() }).with_children(|row| {
+                    row.spawn((RaiseRuleButton { species_id, rule: i }, small_button(Color::srgb(0.2, 0.2, 0.25))))
+                        .with_children(|b| { b.spawn(label("^".to_string(), 13.0)); });
+                    row.spawn((
+                        RuleWeightButton { species_id, rule: i, delta: -BEHAVIOR_WEIGHT_STEP },
+                        small_button(Color::srgb(0.35, 0.15, 0.15)),
+                    ))
+                    .with_children(|b| { b.spawn(label("-".to_string(), 13.0)); });
+                    row.spawn(label(format!("{:>3}", rule.weight), 13.0));
+                    row.spawn((
+                        RuleWeightButton { species_id, rule: i, delta: BEHAVIOR_WEIGHT_STEP },
+                        small_button(Color::srgb(0.15, 0.35, 0.15)),
+                    ))
+                    .with_children(|b| { b.spawn(label("+".to_string(), 13.0)); });
+                    row.spawn(label(format!("{}. {}", i + 1, describe_rule(rule, &cfg)), 13.0));
+                });
+            }
+        }
+    });
+}
+
+// --- TIMELINE (F7) ---
+// A bar from day 0 to today with a tick per timeline entry (sheep in the top
+// half, wolves below). Hovering the bar scrubs it: the text lists what happened
+// around the day under the cursor instead of the latest entries.
+const TIMELINE_LINES: usize = 6;
+
+#[derive(Component)]
+struct TimelinePanelRoot;
+
+#[derive(Component)]
+struct TimelineBar;
+
+#[derive(Component)]
+struct TimelineCursor;
+
+#[derive(Component)]
+struct TimelineMarker(usize); // index into WorldTimeline::entries
+
+#[derive(Component)]
+struct TimelineText;
+
+fn timeline_color(kind: TimelineKind) -> Color {
+    match kind {
+        TimelineKind::Extinct => Color::srgb(0.95, 0.2, 0.2),
+        TimelineKind::Reintroduced(_) => Color::srgb(0.3, 0.9, 0.3),
+        TimelineKind::Peak(_) => Color::srgb(0.4, 0.7, 1.0),
+        TimelineKind::Trough(_) => Color::srgb(0.95, 0.7, 0.2),
+    }
+}
+
+fn setup_timeline_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            TimelinePanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(20.0),
+                bottom: Val::Px(60.0),
+                width: Val::Percent(60.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Timeline (F7, hover to scrub)"),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                TimelineBar,
+                RelativeCursorPosition::default(),
+                Node { width: Val::Percent(100.0), height: Val::Px(24.0), ..default() },
+                BackgroundColor(Color::srgba(0.3, 0.3, 0.3, 0.8)),
+            ))
+            .with_children(|bar| {
+                bar.spawn((
+                    TimelineCursor,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(100.0),
+                        width: Val::Px(2.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::WHITE),
+                ));
+            });
+            p.spawn((
+                Text::new(""),
+                TextFont { font_size: 13.0, ..default() },
+                TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                TimelineText,
+            ));
+        });
+}
+
+fn toggle_timeline_panel(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut q_panel: Query<&mut Visibility, With<TimelinePanelRoot>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::F7) { return; }
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+    *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+}
+
+fn update_timeline_panel(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    timeline: Res<WorldTimeline>,
+    q_panel: Query<&Visibility, With<TimelinePanelRoot>>,
+    q_bar: Query<(Entity, &RelativeCursorPosition), With<TimelineBar>>,
+    mut q_markers: Query<(Entity, &TimelineMarker, &mut Node, &mut BackgroundColor), Without<TimelineCursor>>,
+    mut q_cursor: Query<&mut Node, With<TimelineCursor>>,
+    mut q_text: Query<&mut Text, With<TimelineText>>,
+) {
+    let Ok(visibility) = q_panel.single() else { return; };
+    if *visibility == Visibility::Hidden { return; }
+    let Ok((bar, cursor)) = q_bar.single() else { return; };
+
+    let today = stats.days.max(1.0);
+    let at = |day: f32| Val::Percent((day / today * 100.0).clamp(0.0, 100.0));
+    // A row per configured species, in id order
+    let mut species: Vec<u32> = cfg.species.keys().copied().collect();
+    species.sort_unstable();
+    let row_height = 100.0 / species.len().max(1) as f32;
+    let row = |entry: &TimelineEntry| {
+        let index = species.iter().position(|&id| id == entry.species_id).unwrap_or(0);
+        Val::Percent(index as f32 * row_height)
+    };
+
+    // One marker per entry: stale ones go (a new world), missing ones are added,
+    // and all slide left as the days go by. Entries stay in day order, so a
+    // backdated peak can shift which entry a marker shows.
+    let mut have: HashSet<usize> = HashSet::new();
+    for (entity, marker, mut node, mut color) in q_markers.iter_mut() {
+        match timeline.entries.get(marker.0) {
+            Some(entry) => {
+                node.left = at(entry.day);
+                node.top = row(entry);
+                node.height = Val::Percent(row_height);
+                color.0 = timeline_color(entry.kind);
+                have.insert(marker.0);
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+    for (i, entry) in timeline.entries.iter().enumerate() {
+        if have.contains(&i) { continue; }
+        let marker = commands
+            .spawn((
+                TimelineMarker(i),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: at(entry.day),
+                    top: row(entry),
+                    width: Val::Px(3.0),
+                    height: Val::Percent(row_height),
+                    ..default()
+                },
+                BackgroundColor(timeline_color(entry.kind)),
+            ))
+            .id();
+        commands.entity(bar).add_child(marker);
+    }
+
+    // Normalized is centred on the bar: -0.5 at its left edge, 0.5 at its right
+    let scrub = cursor
+        .normalized
+        .filter(|p| p.x.abs() <= 0.5 && p.y.abs() <= 0.5)
+        .map(|p| (p.x + 0.5) * today);
+    if let Ok(mut node) = q_cursor.single_mut() {
+        node.left = at(scrub.unwrap_or(today));
+    }
+
+    let describe = |e: &TimelineEntry| format!("day {:.0}: {} {}", e.day, cfg.s(e.species_id).name, e.kind.describe());
+    let lines: Vec<String> = match scrub {
+        Some(day) => {
+            let mut near = timeline.around(day, today * 0.05);
+            near.truncate(TIMELINE_LINES);
+            near.sort_by(|a, b| a.day.total_cmp(&b.day));
+            let mut lines = vec![format!("Day {:.0}", day)];
+            lines.extend(near.into_iter().map(describe));
+            lines
+        }
+        None => timeline.entries.iter().rev().take(TIMELINE_LINES).rev().map(describe).collect(),
+    };
+
+    for mut text in q_text.iter_mut() {
+        set_text(&mut text, lines.join("\n"));
+    }
+}
+
+// --- SEASONS PANEL (F8) ---
+// Per metric, the average season of each kind side by side as bars scaled to
+// the highest of the four
+const SEASON_BAR_WIDTH: f32 = 20.0;
+
+#[derive(Component)]
+struct SeasonsPanelRoot;
+
+#[derive(Component)]
+struct SeasonsPanelText;
+
+fn setup_seasons_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            SeasonsPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                width: Val::Px(340.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Seasons compared (F8)"),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                Text::new(""),
+                TextFont { font_size: 13.0, ..default() },
+                TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                SeasonsPanelText,
+            ));
+        });
+}
+
+fn toggle_seasons_panel(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut q_panel: Query<&mut Visibility, With<SeasonsPanelRoot>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::F8) { return; }
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+    *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+}
+
+fn update_seasons_panel(
+    seasonal: Res<SeasonalStats>,
+    q_panel: Query<&Visibility, With<SeasonsPanelRoot>>,
+    mut q_text: Query<&mut Text, With<SeasonsPanelText>>,
+) {
+    let Ok(visibility) = q_panel.single() else { return; };
+    if *visibility == Visibility::Hidden { return; }
+
+    let metrics: [(&str, fn(&SeasonTally) -> f32); 5] = [
+        ("Births", |t| t.births as f32),
+        ("Starved", |t| t.starved as f32),
+        ("Eaten", |t| t.eaten as f32),
+        ("Other deaths", |t| t.other_deaths as f32),
+        ("Avg hunger", |t| t.average_hunger()),
+    ];
+    let mut lines = Vec::new();
+    if seasonal.tallies.is_empty() {
+        lines.push("No full day yet".to_string());
+    } else {
+        lines.push(format!("Means over {} seasons so far", seasonal.tallies.len()));
+    }
+    for (label, value) in metrics.iter().filter(|_| !seasonal.tallies.is_empty()) {
+        let means: Vec<Option<f32>> = Season::ALL.iter().map(|&season| seasonal.mean(season, value)).collect();
+        let top = means.iter().flatten().copied().fold(0.0_f32, f32::max);
+        lines.push(format!("{} per season", label));
+        for (season, mean) in Season::ALL.iter().zip(means) {
+            lines.push(match mean {
+                Some(mean) => {
+                    let bar = if top > 0.0 { "|".repeat((mean / top * SEASON_BAR_WIDTH).round() as usize) } else { String::new() };
+                    format!("  {:<7}{:>6.1}  {}", season.label(), mean, bar)
+                }
+                None => format!("  {:<7}     -", season.label()),
+            });
+        }
+    }
+
+    for mut text in q_text.iter_mut() {
+        set_text(&mut text, lines.join("\n"));
+    }
+}
+
+// --- END-OF-DAY DIGEST ---
+// The core's DayReport, popped up for day_digest_seconds (real time, so it
+// doesn't vanish at high speed before anyone can read it)
+#[derive(Component)]
+struct DayDigestRoot;
+
+#[derive(Component)]
+struct DayDigestText;
+
+fn setup_day_digest(mut commands: Commands) {
+    commands
+        .spawn((
+            DayDigestRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(35.0),
+                top: Val::Px(10.0),
+                width: Val::Px(380.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(4.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.15, 0.85)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                DayDigestText,
+            ));
+        });
+}
+
+fn update_day_digest(
+    time: Res<Time<Real>>,
+    cfg: Res<SimulationConfig>,
+    mut reports: MessageReader<DayReport>,
+    mut left: Local<f32>,
+    mut q_root: Query<&mut Visibility, With<DayDigestRoot>>,
+    mut q_text: Query<&mut Text, With<DayDigestText>>,
+) {
+    if let Some(report) = reports.read().last().filter(|_| cfg.day_digest_seconds > 0.0) {
+        let mut lines = vec![
+            format!("Day {} is over", report.day),
+            format!("Births: {}   Kills: {}", report.births, report.kills),
+        ];
+        let deaths: Vec<String> = report.deaths.iter().map(|(cause, n)| format!("{} {}", cause.label(), n)).collect();
+        lines.push(if deaths.is_empty() {
+            "Deaths: none".to_string()
+        } else {
+            format!("Deaths: {} ({})", report.deaths_total(), deaths.join(", "))
+        });
+        lines.push(format!("Average hunger: {:.0}", report.average_hunger));
+        let weather: Vec<&str> = report.weather.iter().map(|w| w.label()).collect();
+        lines.push(format!("Weather: {}", weather.join(" > ")));
+        let counts: Vec<String> = report.population.iter().map(|&(id, n)| format!("{} {}", cfg.s(id).name, n)).collect();
+        lines.push(format!("Alive: {}", counts.join(", ")));
+        for mut text in q_text.iter_mut() {
+            set_text(&mut text, lines.join("\n"));
+        }
+        *left = cfg.day_digest_seconds;
+    }
+
+    *left = (*left - time.delta_secs()).max(0.0);
+    let Ok(mut visibility) = q_root.single_mut() else { return; };
+    let wanted = if *left > 0.0 { Visibility::Visible } else { Visibility::Hidden };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+}
+
+// --- PROFILER ---
+// Wall time of each simulation system (from SystemTimings), of drawing and of
+// the whole frame. Measurements go to Bevy diagnostics, which keep the
+// smoothed history.
+const PROFILE_SYSTEM_PREFIX: &str = "living_world/system/";
+const PROFILE_DRAW: DiagnosticPath = DiagnosticPath::const_new("living_world/draw");
+const PROFILE_FRAME: DiagnosticPath = DiagnosticPath::const_new("living_world/frame");
+const PROFILE_ROWS: usize = 12; // slowest systems listed
+
+#[derive(Resource, Default)]
+struct ProfileClock {
+    frame_start: Option<Instant>,
+    draw_start: Option<Instant>,
+}
+
+#[derive(Component)]
+struct ProfilerPanelRoot;
+
+#[derive(Component)]
+struct ProfilerPanelText;
+
+fn profile_frame_start(mut clock: ResMut<ProfileClock>) {
+    clock.frame_start = Some(Instant::now());
+}
+
+// The simulation is done for this frame: file its systems' timings
+fn profile_draw_start(mut clock: ResMut<ProfileClock>, timings: Res<SystemTimings>, mut store: ResMut<DiagnosticsStore>) {
+    let now = Instant::now();
+    for (name, ms) in timings.take() {
+        let path = DiagnosticPath::new(format!("{}{}", PROFILE_SYSTEM_PREFIX, name));
+        if store.get(&path).is_none() {
+            store.add(Diagnostic::new(path.clone()).with_suffix(" ms"));
+        }
+        if let Some(diagnostic) = store.get_mut(&path) {
+            diagnostic.add_measurement(DiagnosticMeasurement { time: now, value: ms });
+        }
+    }
+    clock.draw_start = Some(now);
+}
+
+fn profile_frame_end(clock: Res<ProfileClock>, mut diagnostics: Diagnostics) {
+    if let Some(start) = clock.draw_start {
+        diagnostics.add_measurement(&PROFILE_DRAW, || start.elapsed().as_secs_f64() * 1000.0);
+    }
+    if let Some(start) = clock.frame_start {
+        diagnostics.add_measurement(&PROFILE_FRAME, || start.elapsed().as_secs_f64() * 1000.0);
+    }
+}
+
+fn add_profiler(app: &mut App) {
+    app.register_diagnostic(Diagnostic::new(PROFILE_DRAW).with_suffix(" ms"))
+        .register_diagnostic(Diagnostic::new(PROFILE_FRAME).with_suffix(" ms"))
+        .init_resource::<ProfileClock>()
+        .add_systems(Update, (
+            profile_frame_start.before(SimulationSet::Simulate),
+            profile_draw_start.after(SimulationSet::Summarize).before(SimulationSet::Observe),
+            profile_frame_end.after(SimulationSet::Observe),
+        ));
+}
+
+fn setup_profiler_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            ProfilerPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                width: Val::Px(360.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Frame profile (F3)"),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                Text::new(""),
+                TextFont { font_size: 13.0, ..default() },
+                TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                ProfilerPanelText,
+            ));
+        });
+}
+
+fn toggle_profiler(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut q_panel: Query<&mut Visibility, With<ProfilerPanelRoot>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::F3) { return; }
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+    *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+}
+
+fn update_profiler_panel(
+    store: Res<DiagnosticsStore>,
+    q_panel: Query<&Visibility, With<ProfilerPanelRoot>>,
+    mut q_text: Query<&mut Text, With<ProfilerPanelText>>,
+) {
+    let Ok(visibility) = q_panel.single() else { return; };
+    if *visibility == Visibility::Hidden { return; }
+
+    let smoothed = |path: &DiagnosticPath| store.get(path).and_then(|d| d.smoothed()).unwrap_or(0.0);
+    let frame_ms = smoothed(&PROFILE_FRAME).max(0.001);
+    // Bar: share of the whole frame
+    let row = |label: &str, ms: f64| {
+        let bar = "|".repeat(((ms / frame_ms) * 20.0).round().clamp(0.0, 20.0) as usize);
+        format!("{:<26}{:>6.2} ms  {}", label, ms, bar)
+    };
+
+    let mut systems: Vec<(&str, f64)> = store
+        .iter()
+        .filter_map(|d| {
+            let name = d.path().as_str().strip_prefix(PROFILE_SYSTEM_PREFIX)?;
+            Some((name, d.smoothed().unwrap_or(0.0)))
+        })
+        .collect();
+    systems.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let mut lines: Vec<String> = systems.iter().take(PROFILE_ROWS).map(|&(name, ms)| row(name, ms)).collect();
+    lines.push(row("Visuals & UI", smoothed(&PROFILE_DRAW)));
+    lines.push(format!("{:<26}{:>6.2} ms", "Whole frame", frame_ms));
+
+    for mut text in q_text.iter_mut() {
+        **text = lines.join("\n");
+    }
+}
+
+// --- POPULATION GENETICS PANEL (F4, F6 exports the history as CSV) ---
+#[derive(Component)]
+struct GeneticsPanelRoot;
+
+#[derive(Component)]
+struct GeneticsPanelText;
+
+fn setup_genetics_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            GeneticsPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(320.0),
+                bottom: Val::Px(10.0),
+                width: Val::Px(320.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(6.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            Visibility::Hidden,
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Population genetics (F4, F6: export CSV)"),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+            p.spawn((
+                Text::new(""),
+                TextFont { font_size: 13.0, ..default() },
+                TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                GeneticsPanelText,
+            ));
+        });
+}
+
+fn toggle_genetics_panel(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    mut q_panel: Query<&mut Visibility, With<GeneticsPanelRoot>>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::F4) { return; }
+    let Ok(mut visibility) = q_panel.single_mut() else { return; };
+    *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+}
+
+fn export_genetics_csv(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    cfg: Res<SimulationConfig>,
+    history: Res<GeneticsHistory>,
+    mut notifications: ResMut<Notifications>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::F6) { return; }
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let file = format!("{}/genetics_{}.csv", EXPORT_DIR, timestamp);
+    match write_data_file(&file, history.to_csv(&cfg)) {
+        Ok(path) => notifications.push(format!("Genetics exported to {}", path.display()), &cfg),
+        Err(err) => {
+            println!("Could not write {}: {}", file, err);
+            notifications.push("Genetics export failed".to_string(), &cfg);
+        }
+    }
+}
+
+// F10: the selected creature's family (ancestors and descendants) as GraphViz
+// DOT, or with nobody selected one file per species with everyone in it
+fn export_family_tree(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    selected: Res<SelectedCreature>,
+    genealogy: Res<Genealogy>,
+    mut notifications: ResMut<Notifications>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::F10) { return; }
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+
+    let trees: Vec<(String, HashSet<Entity>)> = match selected.0.filter(|e| genealogy.0.contains_key(e)) {
+        Some(root) => vec![(genealogy.0[&root].name.clone(), genealogy.family_of(root))],
+        None => {
+            let mut species: Vec<u32> = cfg.species.keys().copied().collect();
+            species.sort_unstable();
+            species
+                .into_iter()
+                .map(|id| {
+                    let members: HashSet<Entity> =
+                        genealogy.0.iter().filter(|(_, p)| p.species_id == id).map(|(&e, _)| e).collect();
+                    (cfg.s(id).name.clone(), members)
+                })
+                .filter(|(_, members)| !members.is_empty())
+                .collect()
+        }
+    };
+    if trees.is_empty() {
+        notifications.push("No family trees to export yet".to_string(), &cfg);
+        return;
+    }
+
+    for (label, members) in trees {
+        let file = format!("{}/family_{}_{}.dot", EXPORT_DIR, label.to_lowercase().replace(' ', "_"), timestamp);
+        match write_data_file(&file, genealogy.to_dot(&cfg, &members, stats.days)) {
+            Ok(path) => notifications.push(format!("Family tree ({} creatures) exported to {}", members.len(), path.display()), &cfg),
+            Err(err) => {
+                println!("Could not write {}: {}", file, err);
+                notifications.push("Family tree export failed".to_string(), &cfg);
+            }
+        }
+    }
+}
+
+// F2: the whole map, top-down, as a PNG with map_export_cell pixels per tile
+fn export_map_png(
+    keys: Res<ButtonInput<KeyCode>>,
+    focus: Res<TextBoxFocus>,
+    cfg: Res<SimulationConfig>,
+    capture: SnapshotQueries,
+    mut notifications: ResMut<Notifications>,
+) {
+    if focus.active.is_some() || !keys.just_pressed(KeyCode::F2) { return; }
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let file = format!("{}/map_{}.png", EXPORT_DIR, timestamp);
+    let image = MapImage::render(&capture.capture(), cfg.map_export_cell);
+    let written = image.to_png().map_err(std::io::Error::other).and_then(|bytes| write_data_file(&file, bytes));
+    match written {
+        Ok(path) => notifications.push(format!("Map ({}x{}) exported to {}", image.width, image.height, path.display()), &cfg),
+        Err(err) => {
+            println!("Could not write {}: {}", file, err);
+            notifications.push("Map export failed".to_string(), &cfg);
+        }
+    }
+}
+
+// Latest sample per species, with each trait's drift since the oldest one kept
+fn update_genetics_panel(
+    cfg: Res<SimulationConfig>,
+    history: Res<GeneticsHistory>,
+    q_panel: Query<&Visibility, With<GeneticsPanelRoot>>,
+    mut q_text: Query<&mut Text, With<GeneticsPanelText>>,
+) {
+    let Ok(visibility) = q_panel.single() else { return; };
+    if *visibility == Visibility::Hidden { return; }
+
+    let mut lines = Vec::new();
+    let mut species: Vec<u32> = cfg.species.keys().copied().collect();
+    species.sort();
+    for species_id in species {
+        let name = &cfg.s(species_id).name;
+        let (Some(latest), Some(first)) = (history.latest(species_id), history.first(species_id)) else {
+            lines.push(format!("{}: no data", name));
+            continue;
+        };
+        lines.push(format!("{} ({} alive, day {:.0})", name, latest.count, latest.day));
+        for ((label, now), then) in DNA_TRAITS.iter().zip(&latest.traits).zip(&first.traits) {
+            lines.push(format!(
+                "  {:<11}{:>7.3}  var {:>7.4}  {:+.3}",
+                label, now.mean, now.variance, now.mean - then.mean
+            ));
+        }
+        lines.push(format!(
+            "  {:<11}{:>7.3}  since day {:.0}: {:+.3}",
+            "Heterozyg.", latest.heterozygosity, first.day, latest.heterozygosity - first.heterozygosity
+        ));
+    }
+
+    for mut text in q_text.iter_mut() {
+        set_text(&mut text, lines.join("\n"));
+    }
+}
+
+fn update_notifications_ui(
+    time: Res<Time>,
+    mut notifications: ResMut<Notifications>,
+    mut q_text: Query<&mut Text, With<NotificationText>>,
+) {
+    let dt = time.delta().as_secs_f32();
+    for (_, left) in notifications.active.iter_mut() {
+        *left -= dt;
+    }
+    notifications.active.retain(|(_, left)| *left > 0.0);
+
+    let Ok(mut text) = q_text.single_mut() else { return; };
+    let joined = notifications.active.iter().map(|(t, _)| t.as_str()).collect::<Vec<_>>().join("\n");
+    if **text != joined {
+        **text = joined;
+    }
+}
+
+// Text panels refresh a few times a second instead of every frame:
+// nobody reads a counter at 60 Hz, and rebuilding the strings re-lays out the text
+#[derive(Resource)]
+struct UiRefresh(Timer);
+
+impl Default for UiRefresh {
+    fn default() -> Self {
+        Self(Timer::from_seconds(0.25, TimerMode::Repeating))
+    }
+}
+
+// Real time, so the panels keep up while the simulation is paused
+fn tick_ui_refresh(time: Res<Time<Real>>, mut refresh: ResMut<UiRefresh>) {
+    refresh.0.tick(time.delta());
+}
+
+fn ui_refresh_due(refresh: Res<UiRefresh>) -> bool {
+    refresh.0.just_finished()
+}
+
+// Only touches the Text when the string differs, so unchanged panels stay unchanged
+fn set_text(text: &mut Mut<Text>, value: String) {
+    if text.0 != value {
+        text.0 = value;
+    }
+}
+
+fn update_stats_ui(
+    diagnostics: Res<DiagnosticsStore>,
+    stats: Res<GameStats>,
+    cfg: Res<SimulationConfig>,
+    tool: Res<PlayerTool>,
+    weather: Res<Weather>,
+    preset: Res<ActivePreset>,
+    scenario: Res<ActiveScenario>,
+    mode: Res<State<GameMode>>,
+    resources: Res<PlayerResources>,
+    q_creatures: Query<&Creature, Without<Dead>>,
+    q_plants: Query<&Plant, Without<Dead>>,
+    q_exhausted: Query<&ExhaustedSoil>,
+    mut q_text: Query<&mut Text, With<StatsText>>,
+) {
+    // 1. Smoothed FPS and frame time (a single frame's dt jumps around too much to read)
+    let smoothed = |path: &DiagnosticPath| diagnostics.get(path).and_then(|d| d.smoothed()).unwrap_or(0.0);
+    let fps = smoothed(&FrameTimeDiagnosticsPlugin::FPS);
+    let frame_ms = smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME);
+
+    // 2. Count Entities
+    let creature_count = q_creatures.iter().count();
+    let plant_count = q_plants.iter().count();
+    let exhausted_count = q_exhausted.iter().count();
+
+    // 3. Update Text
+    for mut text in q_text.iter_mut() {
+        set_text(&mut text, format!(
+            "FPS: {:.0} ({:.1} ms)\nMode: {}\nWorld: {}\nChallenge: {}\nDays: {:.1} ({}, {})\nWeather: {}\nCreatures: {}\nPlants: {}\nExhausted Soil: {}\n{}\nTool: {}\nGovernor: {}",
+            fps,
+            frame_ms,
+            mode.get().label(),
+            preset.0.as_deref().map(preset_display_name).unwrap_or("Custom"),
+            scenario.0.as_ref().map(|run| run.def.name.as_str()).unwrap_or("-"),
+            stats.days,
+            stats.season(&cfg).label(),
+            if stats.is_night(&cfg) { "Night" } else { "Day" },
+            weather.kind.label(),
+            creature_count,
+            plant_count,
+            exhausted_count,
+            ResourceKind::ALL.iter().map(|&kind| format!("{}: {}", kind.label(), resources.get(kind))).collect::<Vec<_>>().join("  "),
+            tool.label(),
+            if cfg.governor_enabled { "On" } else { "Off" }
+        ));
+    }
+}
+
+// Describes the first creature on the hovered tile
+const INSPECTOR_LIFE_LINES: usize = 6;
+const GENE_BAR_WIDTH: usize = 10;
+
+fn update_inspector_ui(
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    cfg: Res<SimulationConfig>,
+    registry: Res<SpeciesRegistry>,
+    selected: Res<SelectedCreature>,
+    bios: Res<Biographies>,
+    q_creatures: Query<(Entity, &GridPosition, &CreatureStats, &CreatureName, &Generation, &Hunger, &Age, Option<&Mood>, Option<&Clade>, Option<&DecisionTrace>, (Option<&Dna>, Has<GeneEdited>)), (With<Creature>, Without<Dead>)>,
+    mut q_text: Query<&mut Text, With<InspectorText>>,
+) {
+    let Ok(mut text) = q_text.single_mut() else { return; };
+    let hovered = match (q_window.single(), q_camera.single()) {
+        (Ok(window), Ok((camera, camera_transform))) => hovered_tile(window, camera, camera_transform),
+        _ => None,
+    };
+
+    // The selected creature wins over whatever is under the mouse
+    let creature = selected
+        .0
+        .and_then(|e| q_creatures.get(e).ok())
+        .or_else(|| hovered.and_then(|(x, y)| q_creatures.iter().find(|(_, pos, ..)| pos.x == x && pos.y == y)));
+    let mut value = match creature {
+        Some((_, _, stats, name, generation, hunger, age, mood, clade, ..)) => format!(
+            "{} ({}, Gen {})\nHunger: {:.0}\nAge: {:.0}s ({})\nMood: {}",
+            name.0,
+            match clade {
+                Some(c) => registry.name(&cfg, c.0),
+                None => cfg.s(stats.species_id).name.clone(),
+            },
+            generation.0,
+            hunger.0,
+            age.seconds_alive,
+            age.stage().label(),
+            mood.map(|m| m.kind).unwrap_or_default().label(),
+        ),
+        None => String::new(),
+    };
+    if let Some((.., Some(trace), _)) = creature {
+        let goal = match (trace.goal, trace.target) {
+            (Some(goal), Some((x, y))) => format!(" ({:?} at {}, {})", goal, x, y),
+            _ => String::new(),
+        };
+        let scores: Vec<String> = MOVE_DIRECTIONS
+            .iter()
+            .zip(trace.scores)
+            .map(|((dx, dy), score)| {
+                let chosen = if trace.chosen == Some((*dx, *dy)) { "*" } else { "" };
+                match score {
+                    Some(score) => format!("{:+},{:+}: {}{}", dx, dy, score, chosen),
+                    None => format!("{:+},{:+}: -", dx, dy),
+                }
+            })
+            .collect();
+        value.push_str(&format!(
+            "\nDoing: {}{}\nMoves: {}\nPath: {} steps, {} replans",
+            if trace.state.is_empty() { "Waiting" } else { trace.state },
+            goal,
+            scores.join("  "),
+            trace.path.len(),
+            trace.replans
+        ));
+    }
+    // One bar per gene, filled across the range the trait may take
+    if let Some((.., (Some(dna), edited))) = creature {
+        let genes: Vec<String> = DNA_TRAITS
+            .iter()
+            .zip(dna.traits())
+            .zip(DNA_RANGES)
+            .map(|((label, gene), (lo, hi))| {
+                let filled = (((gene - lo) / (hi - lo)).clamp(0.0, 1.0) * GENE_BAR_WIDTH as f32).round() as usize;
+                format!(
+                    "{:<10} {}{} {:.2}",
+                    label,
+                    "|".repeat(filled),
+                    ".".repeat(GENE_BAR_WIDTH - filled),
+                    gene
+                )
+            })
+            .collect();
+        value.push_str(&format!(
+            "\nGenes{}:\n  {}",
+            if edited { " (modified)" } else { "" },
+            genes.join("\n  ")
+        ));
+    }
+    // The latest few lines of its story, newest last
+    if let Some(bio) = creature.and_then(|(entity, ..)| bios.0.get(&entity)) {
+        let recent: Vec<String> = bio
+            .entries
+            .iter()
+            .rev()
+            .take(INSPECTOR_LIFE_LINES)
+            .rev()
+            .map(|entry| format!("day {:.0}: {}", entry.day, entry.describe()))
+            .collect();
+        value.push_str(&format!("\nLife:\n  {}", recent.join("\n  ")));
+    }
+    set_text(&mut text, value);
+}
+
+// Bars scale to the slowest recent frame, but never below 30 FPS worth,
+// so a steady 60 FPS reads as a flat half-height line
+fn update_frame_sparkline(
+    diagnostics: Res<DiagnosticsStore>,
+    mut q_bars: Query<(&FrameSparkBar, &mut Node, &mut BackgroundColor)>,
+) {
+    let Some(frame_time) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else { return; };
+    let history: Vec<f64> = frame_time.values().copied().collect();
+    let recent = &history[history.len().saturating_sub(SPARKLINE_FRAMES)..];
+    let scale = recent.iter().copied().fold(1000.0 / 30.0, f64::max);
+    // Right-align: the newest frame is the last bar
+    let offset = SPARKLINE_FRAMES - recent.len();
+
+    for (bar, mut node, mut color) in q_bars.iter_mut() {
+        let ms = bar.0.checked_sub(offset).map(|i| recent[i]).unwrap_or(0.0);
+        node.height = Val::Percent((ms / scale * 100.0) as f32);
+        color.0 = if ms <= 1000.0 / 50.0 {
+            Color::srgb(0.3, 0.8, 0.3)
+        } else if ms <= 1000.0 / 30.0 {
+            Color::srgb(0.9, 0.8, 0.2)
+        } else {
+            Color::srgb(0.9, 0.25, 0.2)
+        };
+    }
+}
+
+fn update_species_stats_ui(
+    pop: Res<PopulationStats>,
+    q_creatures: Query<(Entity, &CreatureStats, &Generation, Option<&Founder>, Has<GeneEdited>), (With<Creature>, Without<Dead>)>,
+
+    mut text_params: ParamSet<(
+        Query<&mut Text, With<SpeciesStatsSheepText>>,
+        Query<&mut Text, With<SpeciesStatsWolfText>>,
+    )>,
+) {
+    let mut sheep_current: u32 = 0;
+    let mut wolf_current: u32 = 0;
+    let mut modified: HashMap<u32, u32> = HashMap::new();
+
+    // species_id -> (deepest generation alive, its founder, entity for a stable tie-break)
+    let mut deepest: HashMap<u32, (u32, &str, u64)> = HashMap::new();
+
+    for (entity, stats, generation, founder, edited) in q_creatures.iter() {
+        match stats.species_id {
+            0 => sheep_current += 1,
+            1 => wolf_current += 1,
+            _ => {}
+        }
+        if edited {
+            *modified.entry(stats.species_id).or_default() += 1;
+        }
+        let line = (generation.0, founder.map(|f| f.0.as_str()).unwrap_or("?"), entity.to_bits());
+        let best = deepest.entry(stats.species_id).or_insert(line);
+        if (line.0, std::cmp::Reverse(line.2)) > (best.0, std::cmp::Reverse(best.2)) {
+            *best = line;
+        }
+    }
+    let lineage = |species_id: u32| match deepest.get(&species_id) {
+        Some(&(generation, founder, _)) => format!("Max Gen: {}\nOldest line: {}", generation, founder),
+        None => "Max Gen: -\nOldest line: -".to_string(),
+    };
+    // Only shown once genes have been edited by hand
+    let edited = |species_id: u32| match modified.get(&species_id) {
+        Some(n) => format!("\nModified: {}", n),
+        None => String::new(),
+    };
+
+    let sheep_counters = pop.species.get(&0).copied().unwrap_or_default();
+    let wolf_counters = pop.species.get(&1).copied().unwrap_or_default();
+
+    // Sheep column text
+    for mut t in text_params.p0().iter_mut() {
+        set_text(&mut t, format!(
+            "Born: {}\nCurrent: {}\nTotal Ever: {}\nExtinctions: {}\n{}{}",
+            sheep_counters.born, sheep_current, sheep_counters.total_ever, sheep_counters.extinctions, lineage(0), edited(0)
+        ));
+    }
+
+    // Wolf column text
+    for mut t in text_params.p1().iter_mut() {
+        set_text(&mut t, format!(
+            "Born: {}\nCurrent: {}\nTotal Ever: {}\nExtinctions: {}\n{}{}",
+            wolf_counters.born, wolf_current, wolf_counters.total_ever, wolf_counters.extinctions, lineage(1), edited(1)
+        ));
+    }
+}
+
+
+// One column per derived species in the registry; a new game empties the
+// registry and with it the extra columns
+fn update_clade_columns(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    registry: Res<SpeciesRegistry>,
+    q_row: Query<Entity, With<SpeciesStatsRow>>,
+    q_columns: Query<(Entity, &CladeColumn)>,
+    q_creatures: Query<&Clade, (With<Creature>, Without<Dead>)>,
+    mut q_text: Query<(&CladeStatsText, &mut Text)>,
+) {
+    let Ok(row) = q_row.single() else { return; };
+
+    for (entity, column) in q_columns.iter() {
+        if registry.get(column.0).is_none() {
+            commands.entity(entity).despawn();
+        }
+    }
+    for info in &registry.derived {
+        if q_columns.iter().any(|(_, c)| c.0 == info.id) { continue; }
+        let [r, g, b] = info.color;
+        commands.entity(row).with_children(|parent| {
+            parent
+                .spawn((
+                    Node { flex_direction: FlexDirection::Column, row_gap: Val::Px(6.0), ..default() },
+                    CladeColumn(info.id),
+                ))
+                .with_children(|col| {
+                    col.spawn((
+                        Text::new(info.name.clone()),
+                        TextFont { font_size: 16.0, ..default() },
+                        TextColor(Color::srgb(r, g, b)),
+                    ));
+                    col.spawn((Text::new(""), TextFont { font_size: 14.0, ..default() }, CladeStatsText(info.id)));
+                });
+        });
+    }
+
+    let mut current: HashMap<u32, u32> = HashMap::new();
+    for clade in q_creatures.iter() {
+        *current.entry(clade.0).or_default() += 1;
+    }
+    for (clade, mut text) in q_text.iter_mut() {
+        let Some(info) = registry.get(clade.0) else { continue; };
+        set_text(&mut text, format!(
+            "From: {}\nSince day: {:.0}\nCurrent: {}",
+            registry.name(&cfg, info.parent),
+            info.founded_day,
+            current.get(&info.id).copied().unwrap_or(0)
+        ));
+    }
+}
+
+fn setup_chart(mut commands: Commands) {
+    // Container Node (Top Right)
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            width: Val::Px(150.0),
+            padding: UiRect::all(Val::Px(10.0)),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        })
+        .insert(BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)))
+        .with_children(|parent| {
+            // Header 1: Health
+            parent.spawn((
+                Text::new("Health Status"),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+
+            // Row 1: Healthy (White)
+            parent.spawn(Node { margin: UiRect::top(Val::Px(5.0)), ..default() }).with_children(|row| {
+                row.spawn((Node { width: Val::Px(10.0), height: Val::Px(10.0), ..default() }, BackgroundColor(Color::srgb(1.0, 1.0, 1.0))));
+                row.spawn((Text::new(" Healthy: 0"), TextFont { font_size: 14.0, ..default() }, ChartTextHealthy));
+            });
+
+            // Row 2: Hungry (Yellow)
+            parent.spawn(Node { margin: UiRect::top(Val::Px(5.0)), ..default() }).with_children(|row| {
+                row.spawn((Node { width: Val::Px(10.0), height: Val::Px(10.0), ..default() }, BackgroundColor(Color::srgb(1.0, 1.0, 0.0))));
+                row.spawn((Text::new(" Hungry: 0"), TextFont { font_size: 14.0, ..default() }, ChartTextHungry));
+            });
+
+            // Row 3: Critical (Red)
+            parent.spawn(Node { margin: UiRect::top(Val::Px(5.0)), ..default() }).with_children(|row| {
+                row.spawn((Node { width: Val::Px(10.0), height: Val::Px(10.0), ..default() }, BackgroundColor(Color::srgb(1.0, 0.0, 0.0))));
+                row.spawn((Text::new(" Critical: 0"), TextFont { font_size: 14.0, ..default() }, ChartTextCritical));
+            });
+
+            // --- SPACER ---
+            parent.spawn(Node { height: Val::Px(15.0), ..default() });
+
+            // Header 2: Demographics
+            parent.spawn((
+                Text::new("Demographics"),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+
+            parent.spawn((Text::new("Sheep | age | Wolf"), TextFont { font_size: 11.0, ..default() }));
+
+            // Pyramid: oldest bucket on top
+            for bucket in (0..PYRAMID_BUCKETS).rev() {
+                let label = if bucket + 1 == PYRAMID_BUCKETS {
+                    format!("{}+", bucket as f32 * PYRAMID_BUCKET_DAYS)
+                } else {
+                    format!("{}", bucket as f32 * PYRAMID_BUCKET_DAYS)
+                };
+                parent.spawn(Node { margin: UiRect::top(Val::Px(2.0)), align_items: AlignItems::Center, ..default() }).with_children(|row| {
+                    row.spawn(Node { width: Val::Px(PYRAMID_HALF_WIDTH), justify_content: JustifyContent::FlexEnd, ..default() }).with_children(|half| {
+                        half.spawn((
+                            Node { width: Val::Px(0.0), height: Val::Px(8.0), ..default() },
+                            BackgroundColor(Color::srgb(1.0, 1.0, 1.0)),
+                            PyramidBar { bucket, species_id: 0 },
+                        ));
+                    });
+                    row.spawn(Node { width: Val::Px(20.0), justify_content: JustifyContent::Center, ..default() }).with_children(|mid| {
+                        mid.spawn((Text::new(label), TextFont { font_size: 10.0, ..default() }));
+                    });
+                    row.spawn(Node { width: Val::Px(PYRAMID_HALF_WIDTH), ..default() }).with_children(|half| {
+                        half.spawn((
+                            Node { width: Val::Px(0.0), height: Val::Px(8.0), ..default() },
+                            BackgroundColor(Color::srgb(0.6, 0.3, 0.1)),
+                            PyramidBar { bucket, species_id: 1 },
+                        ));
+                    });
+                });
+            }
+        });
+}
+
+fn update_chart_ui(
+    q_creatures: Query<&Hunger, (With<Creature>, Without<Dead>)>,
+
+    // FIX: ParamSet lets us borrow &mut Text multiple times safely
+    mut text_params: ParamSet<(
+        Query<&mut Text, With<ChartTextHealthy>>,
+        Query<&mut Text, With<ChartTextHungry>>,
+        Query<&mut Text, With<ChartTextCritical>>,
+    )>,
+) {
+    let mut healthy = 0;
+    let mut hungry = 0;
+    let mut critical = 0;
+
+    for hunger in q_creatures.iter() {
+        if hunger.0 > 90.0 {
+            critical += 1;
+        } else if hunger.0 > 50.0 {
+            hungry += 1;
+        } else {
+            healthy += 1;
+        }
+    }
+
+    // Access p0, p1, p2... matching the order in the ParamSet above
+
+    // 1. Healthy
+    for mut text in text_params.p0().iter_mut() {
+        set_text(&mut text, format!(" Healthy: {}", healthy));
+    }
+
+    // 2. Hungry
+    for mut text in text_params.p1().iter_mut() {
+        set_text(&mut text, format!(" Hungry: {}", hungry));
+    }
+
+    // 3. Critical
+    for mut text in text_params.p2().iter_mut() {
+        set_text(&mut text, format!(" Critical: {}", critical));
+    }
+}
+
+// Recounted once per sim-day (and after a new game resets the clock);
+// bars are scaled to the fullest bucket of either species
+fn update_age_pyramid(
+    stats: Res<GameStats>,
+    mut last_day: Local<Option<u32>>,
+    q_creatures: Query<(&Age, &CreatureStats), (With<Creature>, Without<Dead>)>,
+    mut q_bars: Query<(&PyramidBar, &mut Node)>,
+) {
+    let day = stats.days as u32;
+    if *last_day == Some(day) { return; }
+    *last_day = Some(day);
+
+    let mut counts = [[0u32; PYRAMID_BUCKETS]; 2];
+    for (age, creature_stats) in q_creatures.iter() {
+        let bucket = ((age.seconds_alive / SECONDS_PER_DAY / PYRAMID_BUCKET_DAYS) as usize).min(PYRAMID_BUCKETS - 1);
+        counts[(creature_stats.species_id as usize).min(1)][bucket] += 1;
+    }
+    let fullest = counts.iter().flatten().copied().max().unwrap_or(0).max(1) as f32;
+
+    for (bar, mut node) in q_bars.iter_mut() {
+        let count = counts[(bar.species_id as usize).min(1)][bar.bucket] as f32;
+        node.width = Val::Px(count / fullest * PYRAMID_HALF_WIDTH);
+    }
+}
+
+fn setup_debug_panel(mut commands: Commands) {
+    commands.insert_resource(TextBoxFocus::default());
+
+    commands
+        .spawn((
+            DebugPanelRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                row_gap: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new("Debug Controls (F1)"),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+
+            // The rows start in the first column; reflow_debug_panel spreads them out
+            p.spawn(Node {
+                column_gap: Val::Px(DEBUG_COLUMN_GAP),
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::FlexStart,
+                ..default()
+            })
+            .with_children(|columns| {
+                let column = |index: usize| {
+                    (
+                        DebugColumn(index),
+                        Node {
+                            width: Val::Px(DEBUG_COLUMN_WIDTH),
+                            row_gap: Val::Px(10.0),
+                            flex_direction: FlexDirection::Column,
+                            display: if index == 0 { Display::Flex } else { Display::None },
+                            ..default()
+                        },
+                    )
+                };
+                columns.spawn(column(0)).with_children(|p| {
+                    // --- Row: Plant spawn chance slider ---
+                    debug_slider_row(
+                        p,
+                        "Plant Spawn Chance",
+                        ConfigField::PlantSpawnChance,
+                        0.0,
+                        0.25,
+                    );
+
+                    // --- Row: Sheep start count textbox ---
+                    debug_textbox_row(p, "Sheep Start Count", ConfigField::SheepStartCount);
+
+                    // --- Row: Wolf start count textbox ---
+                    debug_textbox_row(p, "Wolf Start Count", ConfigField::WolfStartCount);
+
+                    // --- Row: Sheep adult seconds slider ---
+                    debug_slider_row(
+                        p,
+                        "Sheep Adult Seconds",
+                        ConfigField::SheepAdultSeconds,
+                        1.0,
+                        60.0,
+                    );
+
+                    // --- Row: Wolf adult seconds slider ---
+                    debug_slider_row(
+                        p,
+                        "Wolf Adult Seconds",
+                        ConfigField::WolfAdultSeconds,
+                        1.0,
+                        60.0,
+                    );
+
+                    // --- Rows: per-species time scale (hunger and movement) ---
+                    debug_slider_row(p, "Sheep Time Scale", ConfigField::SheepTimeScale, 0.25, 4.0);
+                    debug_slider_row(p, "Wolf Time Scale", ConfigField::WolfTimeScale, 0.25, 4.0);
+
+                    // --- Rows: genes of the selected creature ---
+                    p.spawn((
+                        Text::new("Genes of the selected creature"),
+                        TextFont { font_size: 14.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                    for (trait_index, label) in DNA_TRAITS.iter().enumerate() {
+                        gene_edit_row(p, label, trait_index);
+                    }
+
+                    // --- Row: moving creatures around ---
+                    p.spawn((
+                        Text::new("Middle-drag a creature to carry it elsewhere"),
+                        TextFont { font_size: 14.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                    p.spawn((
+                        ScatterButton,
+                        Button,
+                        Node {
+                            padding: UiRect::all(Val::Px(6.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.3, 0.2, 0.15)),
+                    ))
+                    .with_children(|b| {
+                        b.spawn((
+                            Text::new("Scatter all creatures"),
+                            TextFont { font_size: 14.0, ..default() },
+                            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        ));
+                    });
+                });
+                for index in 1..DEBUG_COLUMNS {
+                    columns.spawn(column(index));
+                }
+            });
+        });
+}
+
+// --- GENE EDITING ---
+// Min/-/+/max per gene for the selected creature. Steps are a tenth of the
+// trait's range (one tile for sight); the creature is marked GeneEdited.
+#[derive(Clone, Copy)]
+enum GeneEdit {
+    Min,
+    Down,
+    Up,
+    Max,
+}
+
+#[derive(Component)]
+struct GeneButton {
+    trait_index: usize,
+    edit: GeneEdit,
+}
+
+fn gene_edit_row(parent: &mut ChildSpawnerCommands, label: &str, trait_index: usize) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(4.0),
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(label),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                Node { width: Val::Px(110.0), ..default() },
+            ));
+            for (edit, text) in [(GeneEdit::Min, "min"), (GeneEdit::Down, "-"), (GeneEdit::Up, "+"), (GeneEdit::Max, "max")] {
+                row.spawn((
+                    GeneButton { trait_index, edit },
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.2, 0.25)),
+                ))
+                .with_children(|b| {
+                    b.spawn((
+                        Text::new(text),
+                        TextFont { font_size: 13.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                });
+            }
+        });
+}
+
+fn gene_edit_buttons(
+    mut commands: Commands,
+    selected: Res<SelectedCreature>,
+    stats: Res<GameStats>,
+    cfg: Res<SimulationConfig>,
+    mut logger: ResMut<SimulationLogger>,
+    mut bios: ResMut<Biographies>,
+    q_buttons: Query<(&Interaction, &GeneButton), Changed<Interaction>>,
+    mut q_creatures: Query<(&mut Dna, &mut CreatureStats, &CreatureName, &Generation), (With<Creature>, Without<Dead>)>,
+) {
+    let Some(entity) = selected.0 else { return; };
+    for (interaction, button) in q_buttons.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        let Ok((mut dna, mut creature, name, generation)) = q_creatures.get_mut(entity) else { return; };
+        let Some(&(lo, hi)) = DNA_RANGES.get(button.trait_index) else { continue; };
+        let step = if button.trait_index == 2 { 1.0 } else { (hi - lo) / 10.0 };
+        let now = dna.traits()[button.trait_index];
+        let value = match button.edit {
+            GeneEdit::Min => lo,
+            GeneEdit::Down => now - step,
+            GeneEdit::Up => now + step,
+            GeneEdit::Max => hi,
+        };
+        *dna = dna.with_trait(button.trait_index, value);
+        creature.sight_range = dna.sight_range;
+        commands.entity(entity).insert(GeneEdited);
+
+        logger.log("Modified", stats.days, &cfg.s(creature.species_id).name, &name.0, generation.0, &dna);
+        bios.0.entry(entity).or_default().note(
+            stats.days,
+            format!("Genes edited ({})", DNA_TRAITS[button.trait_index]),
+            cfg.biography_length,
+        );
+    }
+}
+
+// --- DEBUG TELEPORT ---
+// With the debug panel open, middle-dragging a creature carries it to the tile
+// the button is let go over, and the scatter button deals every creature a
+// random tile. Either way only onto walkable land (dry or bridged, no fence or
+// gate), through core's teleport so position and history agree.
+#[derive(Component)]
+struct ScatterButton;
+
+fn walkable_land(
+    q_tiles: &Query<(&Tile, Has<Water>, Has<Bridge>)>,
+    q_blocked: &Query<&GridPosition, (Or<(With<Fence>, With<Gate>)>, Without<Creature>)>,
+) -> Vec<(i32, i32)> {
+    let blocked: HashSet<(i32, i32)> = q_blocked.iter().map(|p| (p.x, p.y)).collect();
+    let mut land: Vec<(i32, i32)> = q_tiles
+        .iter()
+        .filter(|(tile, water, bridge)| (!water || *bridge) && !blocked.contains(&(tile.x, tile.y)))
+        .map(|(tile, ..)| (tile.x, tile.y))
+        .collect();
+    land.sort_unstable();
+    land
+}
+
+fn drag_creature(
+    cfg: Res<SimulationConfig>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut gizmos: Gizmos,
+    mut dragging: Local<Option<Entity>>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    q_tiles: Query<(&Tile, Has<Water>, Has<Bridge>)>,
+    q_blocked: Query<&GridPosition, (Or<(With<Fence>, With<Gate>)>, Without<Creature>)>,
+    mut q_creatures: Query<(Entity, &mut GridPosition, &mut History, &mut MoveDecision, Option<&mut DecisionTrace>), (With<Creature>, Without<Dead>)>,
+) {
+    if !cfg.debug_panel_enabled {
+        *dragging = None;
+        return;
+    }
+    let hovered = match (q_window.single(), q_camera.single()) {
+        (Ok(window), Ok((camera, camera_transform))) => hovered_tile(window, camera, camera_transform),
+        _ => None,
+    };
+    if mouse_input.just_pressed(MouseButton::Middle) {
+        *dragging = hovered.and_then(|(x, y)| q_creatures.iter().find(|(_, pos, ..)| pos.x == x && pos.y == y).map(|(e, ..)| e));
+    }
+    let Some(entity) = *dragging else { return; };
+
+    if mouse_input.pressed(MouseButton::Middle) {
+        // A line from the creature to where it would land
+        if let (Ok((_, pos, ..)), Some((x, y))) = (q_creatures.get(entity), hovered) {
+            gizmos.line_2d(iso(&cfg, pos.x, pos.y), iso(&cfg, x, y), Color::srgb(1.0, 0.9, 0.2));
+        }
+        return;
+    }
+    *dragging = None;
+    let Some(to) = hovered else { return; };
+    if !walkable_land(&q_tiles, &q_blocked).contains(&to) { return; }
+    let Ok((_, mut pos, mut history, mut decision, trace)) = q_creatures.get_mut(entity) else { return; };
+    if (pos.x, pos.y) == to { return; }
+    teleport(&mut pos, &mut history, &mut decision, to);
+    // The old route starts somewhere else now
+    if let Some(mut trace) = trace { trace.path.clear(); }
+}
+
+fn scatter_button(
+    cfg: Res<SimulationConfig>,
+    sim_rng: Res<SimRng>,
+    mut notifications: ResMut<Notifications>,
+    q_button: Query<&Interaction, (Changed<Interaction>, With<ScatterButton>)>,
+    q_tiles: Query<(&Tile, Has<Water>, Has<Bridge>)>,
+    q_blocked: Query<&GridPosition, (Or<(With<Fence>, With<Gate>)>, Without<Creature>)>,
+    mut q_creatures: Query<(Entity, &mut GridPosition, &mut History, &mut MoveDecision, Option<&mut DecisionTrace>), (With<Creature>, Without<Dead>)>,
+) {
+    if !q_button.iter().any(|i| *i == Interaction::Pressed) { return; }
+    let land = walkable_land(&q_tiles, &q_blocked);
+
+    // Sorted so a seed scatters the same way every run
+    let mut creatures: Vec<Entity> = q_creatures.iter().map(|(e, ..)| e).collect();
+    creatures.sort_unstable();
+    let spots = scatter_spots(&mut sim_rng.stream("scatter"), &land, creatures.len());
+    for (&entity, &to) in creatures.iter().zip(&spots) {
+        let Ok((_, mut pos, mut history, mut decision, trace)) = q_creatures.get_mut(entity) else { continue; };
+        teleport(&mut pos, &mut history, &mut decision, to);
+        if let Some(mut trace) = trace { trace.path.clear(); }
+    }
+    notifications.push(format!("Scattered {} creatures", spots.len()), &cfg);
+}
+
+fn debug_slider_row(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    field: ConfigField,
+    min: f32,
+    max: f32,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(label),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+
+            row.spawn((
+                Node {
+                    height: Val::Px(24.0),
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(10.0),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+            ))
+                .with_children(|line| {
+                    // Track
+                    line.spawn((
+                        Slider { field, min, max },
+                        Node {
+                            width: Val::Px(220.0),
+                            height: Val::Px(10.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+                        Interaction::default(),
+                    ))
+                        .with_children(|track| {
+                            // Knob
+                            track.spawn((
+                                SliderKnob { field },
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    left: Val::Px(0.0),
+                                    top: Val::Px(-4.0),
+                                    width: Val::Px(12.0),
+                                    height: Val::Px(18.0),
+                                    ..default()
+                                },
+                                BackgroundColor(Color::srgb(0.8, 0.8, 0.8)),
+                            ));
+                        });
+
+                    // Value text
+                    line.spawn((
+                        SliderValueText { field },
+                        Text::new("0.00"),
+                        TextFont { font_size: 14.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                });
+        });
+}
+
+fn debug_textbox_row(parent: &mut ChildSpawnerCommands, label: &str, field: ConfigField) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(label),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+
+            row.spawn((
+                TextBox { field },
+                Node {
+                    width: Val::Px(140.0),
+                    height: Val::Px(26.0),
+                    padding: UiRect::horizontal(Val::Px(6.0)),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                Interaction::default(),
+            ))
+                .with_children(|tb| {
+                    tb.spawn((
+                        TextBoxText { field },
+                        Text::new(""),
+                        TextFont { font_size: 14.0, ..default() },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    ));
+                });
+        });
+}
+
+fn toggle_debug_panel(keys: Res<ButtonInput<KeyCode>>, mut cfg: ResMut<SimulationConfig>) {
+    if keys.just_pressed(KeyCode::F1) {
+        cfg.debug_panel_enabled = !cfg.debug_panel_enabled;
+    }
+}
+
+fn debug_panel_visibility(
+    cfg: Res<SimulationConfig>,
+    mode: Res<State<GameMode>>,
+    mut q: Query<&mut Visibility, With<DebugPanelRoot>>,
+) {
+    // In your Bevy build, single_mut() returns Result<Mut<_>, QuerySingleError>
+    let Ok(mut v) = q.single_mut() else { return; };
+
+    *v = if cfg.debug_panel_enabled && *mode.get() == GameMode::Sandbox {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
+
+// --- RESPONSIVE LAYOUT ---
+// Panels are laid out in pixels for a UI_REFERENCE_SIZE window. UiScale follows
+// the window (ui_scale fixes it instead), each panel is capped to the room
+// between its anchor and the far edges, and the debug panel spreads its rows
+// over more columns when they don't fit its height and there's width to spare.
+const UI_REFERENCE_SIZE: Vec2 = Vec2::new(1280.0, 720.0);
+const UI_AUTO_SCALE: (f32, f32) = (0.7, 1.5);
+const UI_EDGE_MARGIN: f32 = 10.0;
+const DEBUG_COLUMNS: usize = 3;
+const DEBUG_COLUMN_WIDTH: f32 = 360.0;
+const DEBUG_COLUMN_GAP: f32 = 20.0;
+
+fn ui_scale_system(cfg: Res<SimulationConfig>, q_window: Query<&Window, With<PrimaryWindow>>, mut scale: ResMut<UiScale>) {
+    let Ok(window) = q_window.single() else { return; };
+    let wanted = if cfg.ui_scale > 0.0 {
+        cfg.ui_scale
+    } else {
+        let fit = (window.width() / UI_REFERENCE_SIZE.x).min(window.height() / UI_REFERENCE_SIZE.y);
+        // In steps, so dragging the window edge doesn't relayout every frame
+        (fit.clamp(UI_AUTO_SCALE.0, UI_AUTO_SCALE.1) * 20.0).round() / 20.0
+    };
+    if scale.0 != wanted {
+        scale.0 = wanted;
+    }
+}
+
+// Room left on one axis by a panel's offset from either edge, in UI pixels
+fn room_along(near: Val, far: Val, side: f32) -> f32 {
+    let offset = match (near, far) {
+        (Val::Px(px), _) | (Val::Auto, Val::Px(px)) => px,
+        (Val::Percent(p), _) | (Val::Auto, Val::Percent(p)) => side * p / 100.0,
+        _ => 0.0,
+    };
+    (side - offset - UI_EDGE_MARGIN).max(0.0)
+}
+
+// Refitted when the window or scale changes and when a panel is spawned
+fn fit_panels_to_window(
+    scale: Res<UiScale>,
+    mut resized: MessageReader<WindowResized>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_added: Query<(), (Added<Node>, Without<ChildOf>)>,
+    mut q_roots: Query<&mut Node, Without<ChildOf>>,
+) {
+    if resized.read().count() == 0 && !scale.is_changed() && q_added.is_empty() { return; }
+    let Ok(window) = q_window.single() else { return; };
+    let size = Vec2::new(window.width(), window.height()) / scale.0.max(0.1);
+    for mut node in q_roots.iter_mut() {
+        if node.position_type != PositionType::Absolute { continue; }
+        node.max_width = Val::Px(room_along(node.left, node.right, size.x));
+        node.max_height = Val::Px(room_along(node.top, node.bottom, size.y));
+        node.overflow = Overflow::clip();
+    }
+}
+
+// As many columns as the rows need to fit 85% of the window's height, as long
+// as they take up no more than 60% of its width; the rows keep their order
+fn reflow_debug_panel(
+    mut commands: Commands,
+    scale: Res<UiScale>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    mut q_columns: Query<(Entity, &DebugColumn, &mut Node, Option<&Children>)>,
+    q_rows: Query<&ComputedNode>,
+) {
+    let Ok(window) = q_window.single() else { return; };
+    let size = Vec2::new(window.width(), window.height()) / scale.0.max(0.1);
+
+    let mut columns: Vec<(usize, Entity, Display, Vec<Entity>)> = q_columns
+        .iter()
+        .map(|(entity, column, node, children)| (column.0, entity, node.display, children.map(|c| c.to_vec()).unwrap_or_default()))
+        .collect();
+    columns.sort_by_key(|(index, ..)| *index);
+    let shown = columns.iter().filter(|(_, _, display, _)| *display != Display::None).count();
+    let rows: Vec<(Entity, f32)> = columns
+        .iter()
+        .flat_map(|(.., children)| children.iter().copied())
+        .map(|row| (row, q_rows.get(row).map(|n| n.size().y * n.inverse_scale_factor()).unwrap_or(0.0) + 10.0))
+        .collect();
+    let total: f32 = rows.iter().map(|(_, h)| h).sum();
+    // Nothing laid out yet
+    if total <= rows.len() as f32 * 10.0 { return; }
+
+    let needed = (total / (size.y * 0.85).max(1.0)).ceil() as usize;
+    let fits = ((size.x * 0.6) / (DEBUG_COLUMN_WIDTH + DEBUG_COLUMN_GAP)) as usize;
+    let wanted = needed.clamp(1, fits.clamp(1, DEBUG_COLUMNS));
+    if wanted == shown { return; }
+
+    // A row goes in the column its middle falls in, with the height split evenly
+    let share = total / wanted as f32;
+    let mut placed: Vec<Vec<Entity>> = vec![Vec::new(); DEBUG_COLUMNS];
+    let mut above = 0.0;
+    for (row, height) in rows {
+        let column = (((above + height / 2.0) / share) as usize).min(wanted - 1);
+        placed[column].push(row);
+        above += height;
+    }
+    for (index, entity, _, _) in &columns {
+        let Ok((.., mut node, _)) = q_columns.get_mut(*entity) else { continue; };
+        node.display = if *index < wanted { Display::Flex } else { Display::None };
+        commands.entity(*entity).add_children(&placed[(*index).min(DEBUG_COLUMNS - 1)]);
+    }
+}
+
+// ---- Slider behavior: click+drag on track ----
+/*fn val_to_px(v: Val) -> Option<f32> {
+    match v {
+        Val::Px(px) => Some(px),
+        _ => None, // Percent/Vw/Vh/Auto etc. not handled here
+    }
+}*/
+
+fn debug_slider_system(
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    mut cfg: ResMut<SimulationConfig>,
+    mouse: Res<ButtonInput<MouseButton>>,
+
+    mut params: ParamSet<(
+        Query<(
+            &GlobalTransform,
+            &ComputedNode,
+            &Node,
+            &Slider,
+            &Interaction,
+            &Children,
+        )>,
+        Query<(&mut Node, &SliderKnob)>,
+        Query<(&mut Text, &SliderValueText)>,
+    )>,
+) {
+    if !cfg.debug_panel_enabled {
+        return;
+    }
+
+    let Ok(window) = q_window.single() else { return; };
+    let Some(cursor) = window.cursor_position() else { return; };
+
+    // Update value texts
+    for (mut t, tag) in params.p2().iter_mut() {
+        let val = get_field_f32(&cfg, tag.field);
+        **t = match tag.field {
+            ConfigField::PlantSpawnChance => format!("{:.3}", val),
+            ConfigField::SheepAdultSeconds | ConfigField::WolfAdultSeconds => format!("{:.1}", val),
+            ConfigField::SheepTimeScale | ConfigField::WolfTimeScale => format!("{:.2}x", val),
+            _ => format!("{:.2}", val),
+        };
+    }
+
+    let track_width_px = |node: &Node| -> f32 {
+        match node.width {
+            Val::Px(px) => px,
+            _ => 220.0,
+        }
+    };
+
+    // -------- Pass 1: snapshot all knob updates we want to apply --------
+    // (child_entity, slider_field, left_px)
+    let mut knob_updates: Vec<(Entity, ConfigField, f32)> = Vec::new();
+
+    // If not dragging, we sync ALL knobs to cfg
+    if !mouse.pressed(MouseButton::Left) {
+        for (_gt, _computed, node, slider, _interaction, children) in params.p0().iter() {
+            let width_px = track_width_px(node).max(1.0);
+            let val = get_field_f32(&cfg, slider.field);
+            let t = ((val - slider.min) / (slider.max - slider.min)).clamp(0.0, 1.0);
+            let left_px = t * (width_px - 12.0);
+
+            for child in children.iter() {
+                knob_updates.push((child, slider.field, left_px));
+            }
+        }
+    } else {
+        // Dragging: only update pressed track(s)
+        for (gt, _computed, node, slider, interaction, children) in params.p0().iter() {
+            if *interaction != Interaction::Pressed {
+                continue;
+            }
+
+            let width_px = track_width_px(node).max(1.0);
+            let center = gt.translation().truncate();
+            let min_x = center.x - (width_px * 0.5);
+            let max_x = center.x + (width_px * 0.5);
+
+            let t = ((cursor.x - min_x) / (max_x - min_x)).clamp(0.0, 1.0);
+            let new_val = slider.min + t * (slider.max - slider.min);
+            set_field_f32(&mut cfg, slider.field, new_val);
+
+            let left_px = t * (width_px - 12.0);
+            for child in children.iter() {
+                knob_updates.push((child, slider.field, left_px));
+            }
+        }
+    }
+
+    // -------- Pass 2: apply knob updates (now we can mutably borrow p1 safely) --------
+    {
+        let mut q_knob = params.p1();
+        for (child_entity, field, left_px) in knob_updates {
+            if let Ok((mut knob_node, knob)) = q_knob.get_mut(child_entity) {
+                if knob.field == field {
+                    knob_node.left = Val::Px(left_px);
+                }
+            }
+        }
+    }
+}
+
+// ---- Textbox behavior: click focus + type + Enter commit ----
+fn debug_textbox_system(
+    mut cfg: ResMut<SimulationConfig>,
+    mut focus: ResMut<TextBoxFocus>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut key_evr: MessageReader<KeyboardInput>,
+    mut q_tb: Query<(&TextBox, &Interaction, &Children)>,
+    mut q_text: Query<(&mut Text, &TextBoxText)>,
+) {
+    // The new-game menu's code box handles its own typing
+    if !cfg.debug_panel_enabled || focus.active == Some(ConfigField::WorldCode) { return; }
+
+    // handle clicks to set focus
+    for (tb, interaction, children) in q_tb.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            focus.active = Some(tb.field);
+            focus.buffer.clear();
+
+            // seed buffer with current value
+            match tb.field {
+                ConfigField::SheepStartCount => focus.buffer = cfg.s(0).starting_count.to_string(),
+                ConfigField::WolfStartCount => focus.buffer = cfg.s(1).starting_count.to_string(),
+                _ => {}
+            }
+
+            // update visible text immediately
+            for child in children.iter() {
+                if let Ok((mut t, tag)) = q_text.get_mut(child) {
+                    if tag.field == tb.field {
+                        **t = focus.buffer.clone();
+                    }
+                }
+            }
+        }
+    }
+
+    // If no active textbox, still keep display updated from cfg
+    if focus.active.is_none() {
+        for (mut t, tag) in q_text.iter_mut() {
+            **t = match tag.field {
+                ConfigField::SheepStartCount => cfg.s(0).starting_count.to_string(),
+                ConfigField::WolfStartCount => cfg.s(1).starting_count.to_string(),
+                _ => "".to_string(),
+            };
+        }
+        return;
+    }
+
+    let active = focus.active.unwrap();
+
+    // typing
+    for ev in key_evr.read() {
+        if !ev.state.is_pressed() {
+            continue;
+        }
+
+        if let Key::Character(ref s) = ev.logical_key {
+            for c in s.chars() {
+                if c.is_ascii_digit() {
+                    focus.buffer.push(c);
+                }
+            }
+        }
+    }
+
+
+    // backspace
+    if keys.just_pressed(KeyCode::Backspace) {
+        focus.buffer.pop();
+    }
+
+    // cancel
+    if keys.just_pressed(KeyCode::Escape) {
+        focus.active = None;
+        focus.buffer.clear();
+        return;
+    }
+
+    // commit
+    if keys.just_pressed(KeyCode::Enter) {
+        if let Ok(v) = focus.buffer.parse::<u32>() {
+            match active {
+                ConfigField::SheepStartCount => cfg.s_mut(0).starting_count = v.clamp(0, 200),
+                ConfigField::WolfStartCount => cfg.s_mut(1).starting_count = v.clamp(0, 200),
+                _ => {}
+            }
+        }
+        focus.active = None;
+        focus.buffer.clear();
+        return;
+    }
+
+    // update visible text for active box
+    for (mut t, tag) in q_text.iter_mut() {
+        if tag.field == active {
+            **t = focus.buffer.clone();
+        }
+    }
+}
+
+// =========================
+// 5) FIELD GET/SET HELPERS (for sliders)
+// =========================
+fn get_field_f32(cfg: &SimulationConfig, field: ConfigField) -> f32 {
+    match field {
+        ConfigField::PlantSpawnChance => cfg.plant_spawn_chance_per_tick,
+        ConfigField::SheepAdultSeconds => cfg.s(0).adult_seconds,
+        ConfigField::WolfAdultSeconds => cfg.s(1).adult_seconds,
+        ConfigField::SheepTimeScale => cfg.s(0).time_scale,
+        ConfigField::WolfTimeScale => cfg.s(1).time_scale,
+        _ => 0.0,
+    }
+}
+
+fn set_field_f32(cfg: &mut SimulationConfig, field: ConfigField, val: f32) {
+    match field {
+        ConfigField::PlantSpawnChance => cfg.plant_spawn_chance_per_tick = val.clamp(0.0, 1.0),
+        ConfigField::SheepAdultSeconds => cfg.s_mut(0).adult_seconds = val.clamp(1.0, 600.0),
+        ConfigField::WolfAdultSeconds => cfg.s_mut(1).adult_seconds = val.clamp(1.0, 600.0),
+        // In steps of 0.05, so 1x and 2x can be hit exactly
+        ConfigField::SheepTimeScale => cfg.s_mut(0).time_scale = (val.clamp(0.25, 4.0) * 20.0).round() / 20.0,
+        ConfigField::WolfTimeScale => cfg.s_mut(1).time_scale = (val.clamp(0.25, 4.0) * 20.0).round() / 20.0,
+        _ => {}
+    }
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use living_world_app::{
+    cli_map, cli_preset, cli_scenario, cli_seed, cli_value, diff_report, record_trace, verify_determinism, LivingWorldPlugin, Lockstep,
+    SimulationConfig, TraceRow,
+};
+
+fn fail(err: String) -> ! {
+    eprintln!("{}", err);
+    std::process::exit(1);
+}
+
+fn read_trace(path: &str) -> Vec<TraceRow> {
+    std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| ron::from_str(&text).map_err(|e| e.to_string()))
+        .unwrap_or_else(|err| fail(format!("{}: {}", path, err)))
+}
+
+fn main() {
+    // Diagnostic: two headless runs from one seed must stay identical
+    if std::env::args().any(|a| a == "--verify-determinism") {
+        let seed = cli_seed().unwrap_or(1);
+        match verify_determinism(seed, 60 * 60, 60) {
+            Ok(()) => println!("seed {}: deterministic over 3600 ticks", seed),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Lockstep comparison: the same seed under the usual config (A) and with
+    // an override file on top (B), for --days sim-days
+    let days: u32 = cli_value("--days").and_then(|v| v.parse().ok()).unwrap_or(50);
+    let seed = cli_seed().unwrap_or(1);
+    let config = || SimulationConfig::load(cli_preset().as_deref(), None);
+    if let Some(path) = cli_value("--compare") {
+        let overrides = std::fs::read_to_string(&path).unwrap_or_else(|e| fail(format!("{}: {}", path, e)));
+        let a = config();
+        let b = a.with_overrides(&overrides).unwrap_or_else(|err| fail(format!("{}: {}", path, err)));
+        let mut lockstep = Lockstep::new(a.clone(), b, seed);
+        let (rows_a, rows_b) = lockstep.run_days(days);
+        print!("{}", diff_report(&a, &rows_a, &rows_b, lockstep.first_divergence));
+        return;
+    }
+
+    // Comparing two builds: each records a trace, then either diffs them
+    if let Some(path) = cli_value("--record-trace") {
+        let trace = record_trace(config(), seed, days);
+        let text = ron::ser::to_string_pretty(&trace, ron::ser::PrettyConfig::default()).unwrap_or_else(|e| fail(e.to_string()));
+        std::fs::write(&path, text).unwrap_or_else(|e| fail(format!("{}: {}", path, e)));
+        println!("seed {}: {} days recorded to {}", seed, days, path);
+        return;
+    }
+    if let Some(path_a) = cli_value("--compare-traces") {
+        let args: Vec<String> = std::env::args().collect();
+        let path_b = args
+            .iter()
+            .position(|a| *a == path_a)
+            .and_then(|i| args.get(i + 1))
+            .unwrap_or_else(|| fail("--compare-traces needs two trace files".to_string()));
+        print!("{}", diff_report(&config(), &read_trace(&path_a), &read_trace(path_b), None));
+        return;
+    }
+
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Living World Simulation".into(),
+                resolution: bevy::window::WindowResolution::new(1280, 720),
+                ..default()
+            }),
+            ..default()
+        }))
+        .add_plugins(LivingWorldPlugin { preset: cli_preset(), scenario: cli_scenario(), map: cli_map(), seed: cli_seed() })
+        .run();
+}
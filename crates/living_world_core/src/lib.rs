@@ -0,0 +1,8767 @@
+//! Living World simulation core: the ecosystem rules without any rendering.
+//!
+//! Add [`SimulationPlugin`] to an app to run the simulation, or drive it
+//! headless through [`Simulation`]. Events and per-tick summaries can be
+//! observed with the [`LivingWorldAppExt`] callbacks or by systems in
+//! [`SimulationSet::Observe`]. The Bevy frontend lives in `living_world_app`.
+
+// Bevy systems take every query and resource as an argument, so long signatures
+// and query types are normal here; nested `if`s are kept where they read better
+#![allow(clippy::type_complexity, clippy::too_many_arguments, clippy::collapsible_if)]
+
+use bevy::prelude::*;
+use bevy::ecs::system::{Adapt, AdapterSystem, RunSystemError, SystemParam};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// ========================
+// 1) CONFIG RESOURCE
+// =========================
+// Hot-reloaded from CONFIG_PATH and the player's SETTINGS_FILE (see SimulationConfig::load)
+pub const CONFIG_PATH: &str = "assets/config.ron";
+pub const SETTINGS_FILE: &str = "settings.ron"; // in data_dir()
+
+/// Every tuning value of the simulation. Build one with `Default` or
+/// [`SimulationConfig::from_overrides`].
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    // Map / tiles
+    pub map_size: i32,
+    pub map_image: Option<String>, // PNG painted in terrain colours (see MapBlueprint) instead of generated lakes/trees
+    pub tile_w: f32,
+    pub tile_h: f32,
+
+    // World / growth
+    pub plant_spawn_chance_per_tick: f32,
+    pub blood_fx_seconds: f32,
+    pub corpse_seconds: f32, // bodies that died where they lay stay this long (0 = none), see DeathCause::leaves_corpse
+
+    // Soil fertility after grazing (recovers along 1 - (1 - t)^exponent)
+    pub fertility_recovery_seconds: f32,  // base time from 0 back to full
+    pub fertility_recovery_exponent: f32, // > 1 = fast early, slow to full
+    pub overgraze_extra_per_graze: f32,   // recovery time multiplier added per recent graze
+    pub overgraze_decay_per_second: f32,  // how fast the soil forgets past grazing
+    pub exhausted_marker_until: f32,      // brown X shows until fertility reaches this
+
+    // Movement
+    pub move_tick_seconds: f32, // one tick of the shared MoveClock; move intervals round to whole ticks
+    pub base_move_seconds: f32,
+    pub reproduction_move_seconds: f32,
+    pub overfed_move_multiplier: f32,
+    pub elder_move_multiplier: f32,  // move interval multiplier for elders
+    pub elder_sight_multiplier: f32, // elders see this much of their sight range
+    pub baby_follow_distance: i32, // babies keep within this many tiles of their mother
+    pub adoption_radius: i32,      // altruistic adults this close take in an orphan
+    pub foster_meal: f32,          // hunger a foster parent's meal takes off each baby next to it
+
+    // Fleeing (all threats in sight push; see plan_creature_moves)
+    pub flee_lookahead: i32,   // steps of open ground counted around each escape tile
+    pub flee_room_weight: i32, // move score per reachable tile (dead ends score low)
+
+    // Swimming (species with can_swim)
+    pub swim_move_multiplier: f32,   // move interval multiplier while in water
+    pub swim_hunger_multiplier: f32, // hunger burn multiplier while in water
+    pub swim_score_penalty: i32,     // how much swimmers dislike entering water
+
+    // Water depth (shallow water only slows, deep water drowns non-swimmers)
+    pub water_paint_depth: f32,      // depth added per click of the Water tool
+    pub deep_water_threshold: f32,   // depth at/above which water counts as deep
+    pub max_water_depth: f32,
+    pub wading_move_multiplier: f32, // move interval multiplier in shallow water
+    pub wading_score_penalty: i32,   // how much creatures dislike stepping into shallows
+
+    // Weather / hydrology
+    pub weather_min_seconds: f32,
+    pub weather_max_seconds: f32,
+    pub weather_rain_chance: f32,    // 0..1, rolled when the weather changes
+    pub weather_drought_chance: f32, // 0..1
+    pub weather_storm_chance: f32,   // 0..1, rest of the roll is clear sky
+    pub hydrology_tick_seconds: f32,
+    pub rain_fill_rate: f32,           // depth gained per second while raining
+    pub rain_spread_chance: f32,       // per second, for land tiles next to water
+    pub rain_spread_depth: f32,        // depth of freshly flooded edge tiles
+    pub sun_evaporation_rate: f32,     // depth lost per second under clear sky
+    pub drought_evaporation_rate: f32, // depth lost per second in a drought
+
+    // Water pollution: creatures in or next to a body of water foul it, more so the smaller it is
+    pub pollution_per_visitor: f32,         // per second and visitor, spread over the body's tiles
+    pub pollution_per_corpse: f32,          // likewise for each body rotting in it or on its bank
+    pub pollution_recovery_per_second: f32,
+    pub pollution_rain_dilution: f32,       // extra recovery per second while it rains
+    pub pollution_kill_threshold: f32,      // 0..1; above this the fish die off and the shore plants wilt
+    pub pollution_plant_die_chance: f32,    // per second, for each plant next to foul water
+    pub drink_interval_seconds: f32,        // creatures in or next to water drink this often
+    pub infection_chance: f32,              // per drink of fully fouled water, scaled by its pollution
+    pub infection_seconds: f32,             // how long an infection lasts
+    pub infection_hunger_multiplier: f32,   // hunger burn while infected
+
+    // Trampling (footfall wear turns busy tiles into dirt)
+    pub footfall_per_step: f32,
+    pub footfall_decay_per_second: f32,
+    pub dirt_wear_threshold: f32,         // wear at/above which grass becomes dirt
+    pub dirt_recover_threshold: f32,      // wear at/below which dirt grows back
+    pub dirt_plant_spawn_multiplier: f32, // plant spawn chance multiplier on dirt
+
+    // Plants near water (spawn chance multiplier by distance to nearest water)
+    pub shore_plant_multiplier: f32,   // right next to water
+    pub dry_plant_multiplier: f32,     // far from (or without any) water
+    pub water_influence_distance: i32, // tiles over which shore -> dry fades
+
+    // Sunlight (plants don't grow at night; seasons scale growth)
+    pub daylight_fraction: f32,     // part of each day (from dawn) that is daytime
+    pub days_per_season: f32,
+    pub season_growth: [f32; 4],    // Spring, Summer, Autumn, Winter
+
+    // Worldgen
+    pub lake_count: u32,
+    pub lake_radius: i32, // deep in the middle, shallow towards the rim
+
+    // Trees and shade (only shade-tolerant plants grow in shade)
+    pub tree_count: u32,
+    pub shade_radius: i32,
+    pub shade_plant_multiplier: f32, // shade-tolerant plants grow slower than sun plants
+
+    // Storms (creatures this close to a tree or hut are sheltered)
+    pub shelter_radius: i32,
+    pub shelter_capacity: u32,        // creatures one hut takes in; trees have no limit
+    pub storm_graze_hunger: f32,      // in a storm, only this hungry or worse still eats plants
+    pub lightning_kill_chance: f32,   // per second, for each exposed creature during a storm
+
+    // Parasites (ticks picked up walking through plants) and the birds that eat them
+    pub parasite_attach_chance: f32,   // per step onto a plant tile
+    pub parasite_max: u32,             // ticks one creature can carry
+    pub parasite_hunger_per_tick: f32, // extra hunger burn per tick carried (0.1 = +10%)
+    pub parasite_groom_seconds: f32,   // resting (digesting or in a hut) sheds a tick this often
+    pub bird_count: u32,
+    pub bird_move_seconds: f32,
+    pub bird_sight: i32,
+    pub bird_starve_seconds: f32,      // a bird that goes this long without a tick or insect flies off
+
+    // Pollinators: insects carry seeds between sun plants, birds eat insects
+    pub insect_count: u32,
+    pub insect_hatch_seconds: f32,         // a missing insect is replaced this often
+    pub insect_move_seconds: f32,
+    pub insect_range: i32,                 // how far an insect looks for its next plant
+    pub pollination_max_visits: f32,
+    pub pollination_decay_per_second: f32, // visits a plant forgets per second
+    pub pollinated_seed_chance: f32,       // per second and recent visit, scaled by sunlight
+
+    // Mood: seeing one of its own killed stresses a creature, losing a child makes it grieve
+    pub mood_stress_seconds: f32,
+    pub mood_grief_seconds: f32,
+    pub mood_well_fed_hunger: f32,       // eating down to this settles a stressed creature
+    pub stressed_breed_multiplier: f32,  // on reproduction chance, per stressed parent
+    pub grieving_breed_multiplier: f32,
+    pub stressed_move_noise: f32,        // on the random part of move scoring (more erratic)
+    pub grieving_move_noise: f32,        // (less than 1: follows its targets listlessly)
+
+    // Alarm calls: a creature that spots something it flees from warns its own kind
+    pub alarm_hearing_range: i32, // 0 turns alarm calls off
+    pub alarm_seconds: f32,       // how long the warned keep running from the reported spot
+
+    // Memory brain (Intelligence::Memory): remembered food and danger spots fade after this
+    pub memory_seconds: f32,
+
+    // Grazing memory: sheep steer off tiles they grazed bare until fertility is back to this
+    pub grazing_memory_recovered: f32,
+    pub grazing_memory_tiles: usize, // the oldest are forgotten beyond this many
+    pub grazed_tile_penalty: i32,    // move score for stepping onto a remembered tile
+
+    // Pheromones (what each species lays and follows is in its behavior file)
+    pub pheromone_decay_per_second: f32, // fraction lost per second
+    pub pheromone_max: f32,              // per tile and channel
+    pub territory_outline_level: f32,    // selection overlay: Territory scent from here counts as marked
+
+    // Player crops
+    pub crop_stage_seconds: f32,  // daylight seconds per growth stage
+    pub crop_trample_chance: f32, // chance a creature stepping on an unripe crop destroys it
+
+    // Shepherd avatar (H to toggle, arrow keys to move)
+    pub shepherd_move_seconds: f32,
+    pub shepherd_follow_radius: i32, // sheep this close drift after the shepherd
+    pub shepherd_follow_weight: i32,
+    pub shepherd_scare_radius: i32,  // wolves this close back off
+    pub shepherd_scare_weight: i32,
+    pub shepherd_shears: bool,       // full fleeces next to the shepherd get shorn
+
+    // Wool (sheep): regrows from 0 (just shorn) to 1 (full fleece)
+    pub wool_regrow_per_second: f32,
+    pub wool_shear_min: f32,   // shearing takes at least this much
+    pub wool_per_fleece: f32,  // wool gained for a full fleece
+
+    // Economy: what harvesting earns and what management mode charges
+    pub starting_seeds: u32,
+    pub meat_per_sheep: u32,  // butchering a sheep
+    pub seeds_per_crop: u32,  // pulling up a ripe crop
+    pub fence_cost: u32,      // wool
+    pub gate_cost: u32,       // wool
+    pub shelter_cost: u32,    // wool
+    pub crop_cost: u32,       // seeds
+    pub rain_cost: u32,       // meat, for the call-rain god power
+    pub smite_cost: u32,      // meat, for smiting a creature
+
+    // Hunger
+    pub hunger_starve_threshold: f32,
+    pub sheep_hunger_burn_adult: f32,
+    pub sheep_hunger_burn_baby: f32,
+    pub wolf_hunger_burn_adult: f32,
+    pub wolf_hunger_burn_baby: f32,
+
+    // Eating rules
+    pub eat_skip_if_hunger_below: f32, // "already full" threshold
+    // Meals take their nutrition off hunger, down to 0; only kills gorge below it
+    pub plant_nutrition: f32,
+    pub fern_nutrition: f32, // shade-tolerant plants are smaller meals
+    pub prey_nutrition: f32, // times the prey's size_multiplier
+    pub max_gorge: f32,      // how far below 0 a kill can take hunger
+
+    // Desperation: hunters this hungry or worse turn Desperate
+    pub desperation_hunger: f32,
+    pub desperate_scent_range: i32,    // extra tiles a desperate hunter finds prey at
+    pub risky_prey_size: f32,          // adult prey this big (size_multiplier) only desperate hunters take on
+    pub risky_prey_injury_chance: f32, // per kill of risky prey
+    pub injury_seconds: f32,
+    pub injured_move_multiplier: f32,  // move interval multiplier while injured
+
+    // Grass: biomass (0..1) on every land tile, grazed a little at a time by sheep.
+    // Plants are the berries and shrubs on top of it.
+    pub grass_regrow_rate: f32,        // logistic growth per second, scaled by sunlight and fertility
+    pub grass_seed_level: f32,         // grazed-bare ground still regrows from this
+    pub grass_graze_hunger: f32,       // sheep graze once at least this hungry
+    pub grass_graze_rate: f32,         // biomass a grazing sheep eats per second
+    pub grass_hunger_per_biomass: f32, // hunger relieved per unit eaten
+
+    // Predator appetite
+    pub surplus_killing: bool,   // false: predators only hunt and kill once at least hunt_min_hunger
+    pub hunt_min_hunger: f32,
+    pub satiation_seconds: f32,  // after digesting, prey is ignored entirely for this long
+
+    // Shore fishing: creatures whose Fish rule applies, with no prey in sight, try their luck
+    // at deep water. There are no fish creatures; the catch is a flat chance per attempt.
+    pub fishing_seconds: f32,        // one attempt takes this long, standing still
+    pub fishing_success_chance: f32, // 0..1 per attempt
+    pub fishing_meal: f32,           // hunger a catch takes off
+
+    // Wolf berry mechanics
+    pub wolf_berry_stun_ticks: u32,
+
+    // Wolf fruit preference weights when very low health (hunger >= 70)
+    pub wolf_low_health_hunger_threshold: f32,
+    pub wolf_low_health_weight_fruit: i32,
+    pub wolf_low_health_weight_meat: i32,
+
+    // Balance governor (demo mode, toggled with G): every interval it nudges
+    // plant spawn chance and wolf reproduction within the bounds below
+    pub governor_enabled: bool,
+    pub governor_interval_seconds: f32,
+    pub governor_step: f32, // fraction of each bound range moved per nudge
+    pub governor_sheep_low: u32,
+    pub governor_sheep_high: u32,
+    pub governor_wolf_low: u32,
+    pub governor_wolf_high: u32,
+    pub governor_plant_chance_bounds: (f32, f32),
+    pub governor_wolf_reproduction_bounds: (f32, f32),
+
+    // Population caps (per-species caps live in SpeciesConfig::max_population)
+    pub max_total_population: Option<u32>,
+    pub over_cap_plant_multiplier: f32, // plant spawn chance multiplier while any cap is hit (1.0 = off)
+
+    // Tile occupancy: moves onto a full tile score crowded_tile_penalty lower,
+    // and babies aren't born onto one
+    pub max_creatures_per_tile: Option<u32>,
+    pub crowded_tile_penalty: i32,
+
+    // Extinction: optionally reintroduce a few founders some time after a species dies out
+    pub extinction_restock_enabled: bool,
+    pub extinction_restock_delay_seconds: f32,
+    pub extinction_restock_count: u32,
+    pub notification_seconds: f32,
+
+    // Saving (F5 quicksave, F9 quickload)
+    pub save_format: SaveFormat,
+    pub save_compression_level: i32, // zstd level for SaveFormat::Compressed
+    pub autosave_seconds: f32,       // 0 = no autosave
+    pub snapshot_limit: usize,       // in-memory snapshots kept (T panel)
+    pub rewind_seconds: f32,         // sim-seconds Z can step back through; 0 = off
+    pub rewind_step_seconds: f32,    // sim-seconds between rewind points
+    pub ghost_step_seconds: f32,     // sim-seconds between recorded ghost frames
+    pub world_tab_limit: usize,      // worlds open at once in the app, the live one included
+    pub split_refresh_seconds: f32,  // split screen: how often the other world's map is redrawn (real seconds)
+    pub split_map_cell: u32,         // split screen: pixels per tile side of that map
+    pub ui_scale: f32,               // app UI size; 0 = follow the window size
+
+    // Population genetics (F4 panel): one sample per species per sim-day
+    pub genetics_history_days: u32, // older samples are dropped
+
+    // Speciation: part of a clade that is cut off from the rest and has drifted far enough becomes its own species
+    pub speciation_check_days: u32,    // 0 turns speciation off
+    pub speciation_link_distance: i32, // creatures this close (tiles) count as one breeding group
+    pub speciation_min_group: u32,     // smaller groups never split off
+    pub speciation_divergence: f32,    // mean relative trait distance between the groups
+
+    // Evolution experiment mode (X): fitness per creature, ranked every fitness_report_days
+    pub fitness_report_days: u32,
+    pub fitness_top_count: usize,     // genomes reported and exported per species
+    pub fitness_offspring_weight: f32,
+    pub fitness_kill_weight: f32,
+    pub fitness_lifespan_weight: f32, // per day lived
+    pub seed_genomes: Vec<SeedGenome>, // founders take these in turn (see TOP_GENOMES_FILE)
+
+    // Biographies: life events kept per creature (runs of the same one count as one)
+    pub biography_length: usize,
+    pub herd_link_distance: i32, // world records: creatures this close (tiles) count as one herd or pack
+    // Timeline: a peak or trough is marked once numbers turn by this share of it (and at least timeline_min_swing)
+    pub timeline_swing: f32,
+    pub timeline_min_swing: u32,
+    pub day_digest_seconds: f32, // end-of-day summary pop-up stays up this long (0 = log only)
+    pub map_export_cell: u32,    // map PNG export: pixels per tile side
+
+    // Entity budgets: a warning banner shows while a category is over its budget
+    pub creature_budget: u32,
+    pub plant_budget: u32, // crops included
+    pub fx_budget: u32,    // blood stains + exhausted soil markers
+    pub corpse_budget: u32, // the oldest corpses rot away early beyond this
+    pub fx_hard_cap: bool, // drop FX beyond fx_budget instead of only warning
+
+    // Camera (mouse wheel zoom) and level of detail when zoomed far out
+    pub camera_zoom_bounds: (f32, f32), // camera scale: min (close) / max (far)
+    pub lod_zoom_threshold: f32,        // from this camera scale creatures draw as per-cell blobs
+    pub lod_cell_size: i32,             // tiles per blob cell side
+    pub compass_group_cell: i32,        // edge compass: creatures this close (cells of this many tiles) count as one pack/herd
+
+    // Species configs (keyed by species_id)
+    pub species: HashMap<u32, SpeciesConfig>,
+
+    // Floating labels above creatures, per category
+    pub label_names: bool,
+    pub label_hungry: bool,   // "!" above the species' hungry_hunger
+    pub label_breeding: bool, // "+" on reproduction cooldown
+    pub label_parasites: bool, // "~" per tick carried
+    pub label_stunned: bool,  // "*" while berry-stunned
+    pub hunger_bars: BarMode, // tiny bar of how full each creature is
+    pub trail_length: usize,  // tiles of breadcrumb trail each creature keeps (drawn for the selection, or all with the debug panel)
+
+    // Debug UI
+    pub debug_panel_enabled: bool,
+}
+
+// Config goes through ron::Value to be merged, and a Value can't hold an enum
+// variant (a bare `Hover` reads back as a unit). Enums in the config are
+// strings instead: `hunger_bars: "Always"`.
+macro_rules! string_enum {
+    ($ty:ident { $($variant:ident),+ $(,)? }) => {
+        impl From<$ty> for String {
+            fn from(value: $ty) -> Self {
+                match value {
+                    $($ty::$variant => stringify!($variant).to_string(),)+
+                }
+            }
+        }
+
+        impl TryFrom<String> for $ty {
+            type Error = String;
+
+            fn try_from(text: String) -> Result<Self, String> {
+                match text.as_str() {
+                    $(stringify!($variant) => Ok($ty::$variant),)+
+                    _ => Err(format!("unknown {} `{}`", stringify!($ty), text)),
+                }
+            }
+        }
+    };
+}
+
+// When the app draws per-creature bars
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum BarMode {
+    Never,
+    #[default]
+    Hover,  // the hovered tile's and the selected creature
+    Always,
+}
+
+string_enum!(BarMode { Never, Hover, Always });
+
+// Movement AI levels, dumbest first
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Intelligence {
+    Greedy,      // one step at a time straight towards the target, fences or not
+    #[default]
+    Pathfinding, // walks around fences and closed gates to reach the target
+    Memory,      // pathfinding, plus remembers where it last ate and where one of its own was killed
+}
+
+string_enum!(Intelligence { Greedy, Pathfinding, Memory });
+
+impl Intelligence {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Intelligence::Greedy => "Greedy",
+            Intelligence::Pathfinding => "Pathfinding",
+            Intelligence::Memory => "Memory",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SpeciesConfig {
+    pub name: String,
+    pub starting_count: u32,
+
+    // Baby->Adult->Elder timing
+    pub adult_seconds: f32,
+    pub elder_seconds: f32, // of total age
+    #[serde(default)]
+    pub lifespan_seconds: f32, // elders die of old age past this total age (0 = never)
+
+    // Reproduction
+    pub reproduction_chance: f32, // 0..1
+    pub reproduction_cooldown_seconds: f32,
+    pub max_population: Option<u32>, // no births at or above this many living
+
+    // Sight
+    pub sight_range: i32,
+
+    // How its movement AI finds its way (see Intelligence)
+    pub intelligence: Intelligence,
+
+    // Water: swimmers cross it slowly instead of drowning
+    pub can_swim: bool,
+
+    // Grazers nibble the grass they stand on and remember the tiles they ate bare
+    #[serde(default)]
+    pub grazer: bool,
+
+    // Experiments: its hunger burns and it steps this many times as fast as
+    // usual (2.0: wolves at double speed), everything else at normal pace
+    pub time_scale: f32,
+
+    // AI rules (RON); the built-in copy is used if the file can't be read
+    pub behavior_path: String,
+
+    // How the app draws it
+    pub appearance: Appearance,
+}
+
+// Sprite size and colours (sRGB, 0..1) for one species. Digesting, overfed,
+// clade and elder tints go on top of these.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Appearance {
+    pub size: f32, // adult sprite side before the size gene; babies are half
+    pub color: [f32; 3],
+    pub hungry_color: [f32; 3],   // above hungry_hunger
+    pub starving_color: [f32; 3], // above starving_hunger
+    pub hungry_hunger: f32,
+    pub starving_hunger: f32,
+    pub cooldown_colors: ([f32; 3], [f32; 3]), // pulses between these after breeding
+    pub blob_color: [f32; 3],                  // zoomed-out level-of-detail blobs
+}
+
+impl Appearance {
+    pub fn color_for(&self, hunger: f32) -> [f32; 3] {
+        if hunger > self.starving_hunger {
+            self.starving_color
+        } else if hunger > self.hungry_hunger {
+            self.hungry_color
+        } else {
+            self.color
+        }
+    }
+}
+
+// Copies of the shipped behavior files, compiled in as a fallback
+pub const BUILTIN_BEHAVIORS: &[(&str, &str)] = &[
+    ("assets/behaviors/sheep.ron", include_str!("../../../assets/behaviors/sheep.ron")),
+    ("assets/behaviors/wolves.ron", include_str!("../../../assets/behaviors/wolves.ron")),
+];
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        let mut species = HashMap::new();
+
+        // Sheep: species_id = 0
+        species.insert(
+            0,
+            SpeciesConfig {
+                name: "Sheep".into(),
+                starting_count: 20,          // INCREASED: Give prey a head start
+                adult_seconds: 10.0,
+                elder_seconds: 120.0,
+                lifespan_seconds: 240.0,
+                reproduction_chance: 0.15,   // INCREASED: Sheep breed faster
+                reproduction_cooldown_seconds: 20.0, // FASTER: Sheep recover quickly
+                max_population: Some(150),
+                sight_range: 8,
+                intelligence: Intelligence::Pathfinding,
+                can_swim: false,
+                grazer: true,
+                time_scale: 1.0,
+                behavior_path: "assets/behaviors/sheep.ron".into(),
+                appearance: Appearance {
+                    size: 20.0,
+                    color: [1.0, 1.0, 1.0],
+                    hungry_color: [1.0, 1.0, 0.0],
+                    starving_color: [1.0, 0.0, 0.0],
+                    hungry_hunger: 50.0,
+                    starving_hunger: 90.0,
+                    cooldown_colors: ([0.5, 0.0, 1.0], [1.0, 0.0, 0.5]), // purple pulse
+                    blob_color: [0.95, 0.95, 0.95],
+                },
+            },
+        );
+
+        // Wolves: species_id = 1
+        species.insert(
+            1,
+            SpeciesConfig {
+                name: "Wolves".into(),
+                starting_count: 6,           // INCREASED: 4 was too fragile
+                adult_seconds: 15.0,         // FASTER: Maturation
+                elder_seconds: 180.0,
+                lifespan_seconds: 320.0,
+                reproduction_chance: 0.10,
+                reproduction_cooldown_seconds: 40.0, // REDUCED: Was 70.0 (too long)
+                max_population: Some(40),
+                sight_range: 12,             // INCREASED: Predators have better eyes
+                intelligence: Intelligence::Pathfinding,
+                can_swim: true,
+                grazer: false,
+                time_scale: 1.0,
+                behavior_path: "assets/behaviors/wolves.ron".into(),
+                appearance: Appearance {
+                    size: 22.0,
+                    color: [0.4, 0.2, 0.1],
+                    hungry_color: [0.8, 0.4, 0.0],
+                    starving_color: [1.0, 0.0, 0.0],
+                    hungry_hunger: 50.0,
+                    starving_hunger: 90.0,
+                    cooldown_colors: ([0.0, 0.0, 0.0], [0.3, 0.15, 0.05]), // black to dark brown
+                    blob_color: [0.55, 0.3, 0.15],
+                },
+            },
+        );
+
+        Self {
+            map_size: 20,
+            map_image: None,
+            tile_w: 64.0,
+            tile_h: 32.0,
+
+            plant_spawn_chance_per_tick: 0.10, // INCREASED: More food for sheep = more sheep
+            blood_fx_seconds: 30.0,
+            corpse_seconds: 60.0,
+
+            fertility_recovery_seconds: 20.0,
+            fertility_recovery_exponent: 2.0,
+            overgraze_extra_per_graze: 0.5,
+            overgraze_decay_per_second: 0.01,
+            exhausted_marker_until: 0.5,
+
+            move_tick_seconds: 0.05,
+            base_move_seconds: 0.2,
+            reproduction_move_seconds: 0.5,
+
+            // CRITICAL FIX: Was 6.6. This made wolves basically immobile after eating.
+            // Now they just move slightly slower (1.5x slower).
+            overfed_move_multiplier: 1.5,
+            elder_move_multiplier: 1.5,
+            elder_sight_multiplier: 0.6,
+            baby_follow_distance: 2,
+            adoption_radius: 3,
+            foster_meal: 20.0,
+
+            flee_lookahead: 3,
+            flee_room_weight: 4,
+
+            swim_move_multiplier: 2.5,
+            swim_hunger_multiplier: 2.0,
+            swim_score_penalty: 40,
+
+            water_paint_depth: 0.5,
+            deep_water_threshold: 1.0,
+            max_water_depth: 2.0,
+            wading_move_multiplier: 1.8,
+            wading_score_penalty: 15,
+
+            weather_min_seconds: 40.0,
+            weather_max_seconds: 120.0,
+            weather_rain_chance: 0.35,
+            weather_drought_chance: 0.2,
+            weather_storm_chance: 0.1,
+            hydrology_tick_seconds: 1.0,
+            rain_fill_rate: 0.02,
+            rain_spread_chance: 0.02,
+            rain_spread_depth: 0.2,
+            sun_evaporation_rate: 0.003,
+            drought_evaporation_rate: 0.02,
+
+            pollution_per_visitor: 0.05,
+            pollution_per_corpse: 0.2,
+            pollution_recovery_per_second: 0.005,
+            pollution_rain_dilution: 0.02,
+            pollution_kill_threshold: 0.6,
+            pollution_plant_die_chance: 0.05,
+            drink_interval_seconds: 2.0,
+            infection_chance: 0.5,
+            infection_seconds: 30.0,
+            infection_hunger_multiplier: 1.5,
+
+            footfall_per_step: 1.0,
+            footfall_decay_per_second: 0.05,
+            dirt_wear_threshold: 12.0,
+            dirt_recover_threshold: 4.0,
+            dirt_plant_spawn_multiplier: 0.2,
+
+            shore_plant_multiplier: 2.5,
+            dry_plant_multiplier: 0.5,
+            water_influence_distance: 8,
+
+            daylight_fraction: 0.65,
+            days_per_season: 10.0,
+            season_growth: [1.0, 1.2, 0.7, 0.25],
+
+            lake_count: 0,
+            lake_radius: 3,
+
+            tree_count: 20,
+            shade_radius: 1,
+            shade_plant_multiplier: 0.6,
+
+            shelter_radius: 1,
+            shelter_capacity: 4,
+            storm_graze_hunger: 70.0,
+            lightning_kill_chance: 0.002,
+
+            parasite_attach_chance: 0.05,
+            parasite_max: 5,
+            parasite_hunger_per_tick: 0.1,
+            parasite_groom_seconds: 4.0,
+            bird_count: 6,
+            bird_move_seconds: 0.1,
+            bird_sight: 8,
+            bird_starve_seconds: 60.0,
+
+            insect_count: 20,
+            insect_hatch_seconds: 2.0,
+            insect_move_seconds: 0.15,
+            insect_range: 6,
+            pollination_max_visits: 5.0,
+            pollination_decay_per_second: 0.05,
+            pollinated_seed_chance: 0.01,
+
+            mood_stress_seconds: 10.0,
+            mood_grief_seconds: 30.0,
+            mood_well_fed_hunger: 20.0,
+            stressed_breed_multiplier: 0.5,
+            grieving_breed_multiplier: 0.1,
+            stressed_move_noise: 2.0,
+            grieving_move_noise: 0.5,
+
+            alarm_hearing_range: 12,
+            alarm_seconds: 3.0,
+
+            memory_seconds: 30.0,
+
+            grazing_memory_recovered: 0.8,
+            grazing_memory_tiles: 12,
+            grazed_tile_penalty: 25,
+
+            pheromone_decay_per_second: 0.05,
+            pheromone_max: 5.0,
+            territory_outline_level: 1.0,
+
+            crop_stage_seconds: 8.0,
+            crop_trample_chance: 0.3,
+
+            shepherd_move_seconds: 0.15,
+            shepherd_follow_radius: 6,
+            shepherd_follow_weight: 15,
+            shepherd_scare_radius: 5,
+            shepherd_scare_weight: 40,
+            shepherd_shears: true,
+
+            wool_regrow_per_second: 0.01,
+            wool_shear_min: 0.5,
+            wool_per_fleece: 3.0,
+
+            starting_seeds: 5,
+            meat_per_sheep: 5,
+            seeds_per_crop: 2,
+            fence_cost: 1,
+            gate_cost: 2,
+            shelter_cost: 6,
+            crop_cost: 1,
+            rain_cost: 10,
+            smite_cost: 3,
+
+            hunger_starve_threshold: 100.0,
+
+            sheep_hunger_burn_adult: 3.0,
+            sheep_hunger_burn_baby: 1.5,
+
+            // CRITICAL FIX: Wolves shouldn't burn energy significantly faster than sheep.
+            // Hunting is hard enough without a hyper-metabolism.
+            wolf_hunger_burn_adult: 3.5,
+            wolf_hunger_burn_baby: 1.75,
+
+            eat_skip_if_hunger_below: 5.0,
+            plant_nutrition: 100.0,
+            fern_nutrition: 60.0,
+            prey_nutrition: 150.0,
+            max_gorge: 50.0,
+
+            desperation_hunger: 80.0,
+            desperate_scent_range: 6,
+            risky_prey_size: 1.2,
+            risky_prey_injury_chance: 0.5,
+            injury_seconds: 20.0,
+            injured_move_multiplier: 1.5,
+
+            grass_regrow_rate: 0.02,
+            grass_seed_level: 0.02,
+            grass_graze_hunger: 20.0,
+            grass_graze_rate: 0.05,
+            grass_hunger_per_biomass: 20.0,
+
+            surplus_killing: true,
+            hunt_min_hunger: 30.0,
+            satiation_seconds: 5.0,
+
+            fishing_seconds: 3.0,
+            fishing_success_chance: 0.3,
+            fishing_meal: 40.0,
+            wolf_berry_stun_ticks: 2,
+
+            wolf_low_health_hunger_threshold: 70.0,
+            wolf_low_health_weight_fruit: 80,
+            wolf_low_health_weight_meat: 50,
+
+            governor_enabled: false,
+            governor_interval_seconds: 10.0,
+            governor_step: 0.1,
+            governor_sheep_low: 8,
+            governor_sheep_high: 40,
+            governor_wolf_low: 3,
+            governor_wolf_high: 10,
+            governor_plant_chance_bounds: (0.04, 0.20),
+            governor_wolf_reproduction_bounds: (0.02, 0.15),
+
+            max_total_population: Some(200),
+            over_cap_plant_multiplier: 0.5,
+
+            max_creatures_per_tile: Some(4),
+            crowded_tile_penalty: 200,
+
+            extinction_restock_enabled: false,
+            extinction_restock_delay_seconds: 30.0,
+            extinction_restock_count: 4,
+            notification_seconds: 6.0,
+
+            save_format: SaveFormat::Compressed,
+            save_compression_level: 3,
+            autosave_seconds: 300.0,
+            snapshot_limit: 8,
+            rewind_seconds: 30.0,
+            rewind_step_seconds: 2.0,
+            ghost_step_seconds: 0.5,
+            world_tab_limit: 4,
+            split_refresh_seconds: 0.25,
+            split_map_cell: 6,
+            ui_scale: 0.0,
+
+            genetics_history_days: 365,
+
+            speciation_check_days: 5,
+            speciation_link_distance: 6,
+            speciation_min_group: 8,
+            speciation_divergence: 0.15,
+
+            fitness_report_days: 10,
+            fitness_top_count: 5,
+            fitness_offspring_weight: 1.0,
+            fitness_kill_weight: 0.5,
+            fitness_lifespan_weight: 0.1,
+            seed_genomes: Vec::new(),
+
+            biography_length: 20,
+            herd_link_distance: 3,
+            timeline_swing: 0.3,
+            timeline_min_swing: 5,
+            day_digest_seconds: 8.0,
+            map_export_cell: 4,
+
+            creature_budget: 400,
+            plant_budget: 1500,
+            fx_budget: 500,
+            corpse_budget: 100,
+            fx_hard_cap: false,
+
+            camera_zoom_bounds: (0.5, 8.0),
+            lod_zoom_threshold: 4.0,
+            lod_cell_size: 4,
+            compass_group_cell: 6,
+
+            species,
+            label_names: false,
+            label_hungry: true,
+            label_breeding: true,
+            label_parasites: true,
+            label_stunned: true,
+            hunger_bars: BarMode::Hover,
+            trail_length: 12,
+
+            debug_panel_enabled: true,
+        }
+    }
+}
+
+impl SimulationConfig {
+    // How much a mood scales the chance to breed
+    pub fn mood_breed_multiplier(&self, mood: MoodKind) -> f32 {
+        match mood {
+            MoodKind::Content => 1.0,
+            MoodKind::Stressed => self.stressed_breed_multiplier,
+            MoodKind::Grieving => self.grieving_breed_multiplier,
+        }
+    }
+
+    // Spread of the random part of a creature's move scores
+    pub fn mood_move_noise(&self, mood: MoodKind) -> i32 {
+        let multiplier = match mood {
+            MoodKind::Content => 1.0,
+            MoodKind::Stressed => self.stressed_move_noise,
+            MoodKind::Grieving => self.grieving_move_noise,
+        };
+        ((20.0 * multiplier).round() as i32).max(1)
+    }
+
+    // Elders don't see as far
+    pub fn sight_for(&self, stats: &CreatureStats, age: &Age) -> i32 {
+        if age.is_elder {
+            ((stats.sight_range as f32 * self.elder_sight_multiplier).round() as i32).max(1)
+        } else {
+            stats.sight_range
+        }
+    }
+
+    // Whether a predator this hungry goes after prey at all (digesting and satiated ones never do)
+    pub fn wants_prey(&self, hunger: f32, full: bool) -> bool {
+        !full && (self.surplus_killing || hunger >= self.hunt_min_hunger)
+    }
+
+    // Whether one more birth of `species_id` is allowed, given living counts
+    pub fn births_allowed(&self, species_id: u32, species_count: u32, total: u32) -> bool {
+        let under_species = self.s(species_id).max_population.map(|cap| species_count < cap).unwrap_or(true);
+        let under_total = self.max_total_population.map(|cap| total < cap).unwrap_or(true);
+        under_species && under_total
+    }
+
+    pub fn is_risky_prey(&self, age: &Age, dna: &Dna) -> bool {
+        age.is_adult && dna.size_multiplier >= self.risky_prey_size
+    }
+
+    pub fn tile_full(&self, occupants: u32) -> bool {
+        self.max_creatures_per_tile.map(|cap| occupants >= cap).unwrap_or(false)
+    }
+
+    // Defaults, then the preset (if any), then CONFIG_PATH and SETTINGS_FILE, then the scenario's
+    // own overrides (so a challenge's rules hold). Override files only need the
+    // fields they change (nested species entries too).
+    pub fn load(preset: Option<&str>, scenario: Option<&ron::Value>) -> Self {
+        match Self::from_layers(preset, scenario) {
+            Ok(cfg) => cfg,
+            Err(err) => {
+                println!("Config unusable ({}), using defaults", err);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn from_layers(preset: Option<&str>, scenario: Option<&ron::Value>) -> Result<Self, String> {
+        let defaults = ron::to_string(&Self::default()).map_err(|e| e.to_string())?;
+        let mut merged: ron::Value = ron::from_str(&defaults).map_err(|e| e.to_string())?;
+
+        if let Some(id) = preset {
+            let path = preset_path(id);
+            let text = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+            let overrides: ron::Value = ron::from_str(&text).map_err(|e| format!("{}: {}", path, e))?;
+            merge_ron_value(&mut merged, overrides);
+        }
+
+        // The local config file and the player's settings are optional
+        let settings = data_path(SETTINGS_FILE);
+        for path in [std::path::Path::new(CONFIG_PATH), settings.as_path()] {
+            if let Ok(text) = std::fs::read_to_string(path) {
+                let overrides: ron::Value = ron::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+                merge_ron_value(&mut merged, overrides);
+            }
+        }
+
+        if let Some(overrides) = scenario {
+            merge_ron_value(&mut merged, overrides.clone());
+        }
+
+        merged.into_rust::<Self>().map_err(|e| e.to_string())
+    }
+
+    /// The defaults with a RON override (same format as `assets/config.ron`)
+    /// laid on top. Unknown fields are ignored, so old saved configs load too.
+    pub fn from_overrides(text: &str) -> Result<Self, String> {
+        Self::default().with_overrides(text)
+    }
+
+    /// This config with a RON override laid on top, as in [`Self::from_overrides`]
+    pub fn with_overrides(&self, text: &str) -> Result<Self, String> {
+        let defaults = ron::to_string(self).map_err(|e| e.to_string())?;
+        let mut merged: ron::Value = ron::from_str(&defaults).map_err(|e| e.to_string())?;
+        let overrides: ron::Value = ron::from_str(text).map_err(|e| e.to_string())?;
+        merge_ron_value(&mut merged, overrides);
+        merged.into_rust::<Self>().map_err(|e| e.to_string())
+    }
+
+    // Fields that shape the world at startup; a reload leaves them alone
+    // (and says so) because they only mean something for a fresh map.
+    pub fn restart_only_changes(&self, new: &SimulationConfig) -> Vec<String> {
+        let mut changed = Vec::new();
+        if self.map_size != new.map_size { changed.push("map_size".to_string()); }
+        if self.map_image != new.map_image { changed.push("map_image".to_string()); }
+        if self.tile_w != new.tile_w { changed.push("tile_w".to_string()); }
+        if self.tile_h != new.tile_h { changed.push("tile_h".to_string()); }
+        if self.tree_count != new.tree_count { changed.push("tree_count".to_string()); }
+        if self.lake_count != new.lake_count { changed.push("lake_count".to_string()); }
+        if self.lake_radius != new.lake_radius { changed.push("lake_radius".to_string()); }
+        for (id, sc) in self.species.iter() {
+            if new.species.get(id).map(|n| n.starting_count != sc.starting_count).unwrap_or(false) {
+                changed.push(format!("{}.starting_count", sc.name));
+            }
+        }
+        changed
+    }
+
+    // Take everything live from `new` except the restart-only fields and runtime UI state
+    pub fn apply_reload(&mut self, mut new: SimulationConfig) {
+        new.map_size = self.map_size;
+        new.map_image = self.map_image.clone();
+        new.tile_w = self.tile_w;
+        new.tile_h = self.tile_h;
+        new.tree_count = self.tree_count;
+        new.lake_count = self.lake_count;
+        new.lake_radius = self.lake_radius;
+        for (id, sc) in self.species.iter() {
+            if let Some(n) = new.species.get_mut(id) {
+                n.starting_count = sc.starting_count;
+            }
+        }
+        new.debug_panel_enabled = self.debug_panel_enabled;
+        *self = new;
+    }
+
+    pub fn s(&self, id: u32) -> &SpeciesConfig {
+        self.species.get(&id).expect("Missing SpeciesConfig")
+    }
+    pub fn s_mut(&mut self, id: u32) -> &mut SpeciesConfig {
+        self.species.get_mut(&id).expect("Missing SpeciesConfig")
+    }
+}
+
+// --- PRESETS ---
+// (file id, display name); files live at assets/presets/<id>.ron and use the
+// same override format as CONFIG_PATH
+pub const PRESETS: &[(&str, &str)] = &[
+    ("classic", "Classic"),
+    ("wolf-heavy", "Wolf-heavy"),
+    ("lush-valley", "Lush valley"),
+    ("harsh-winter", "Harsh winter world"),
+];
+
+pub fn preset_path(id: &str) -> String {
+    format!("assets/presets/{}.ron", id)
+}
+
+pub fn preset_display_name(id: &str) -> &str {
+    PRESETS.iter().find(|(pid, _)| *pid == id).map(|(_, name)| *name).unwrap_or(id)
+}
+
+/// `--preset <id>` on the command line
+pub fn cli_preset() -> Option<String> {
+    cli_value("--preset")
+}
+
+/// `--scenario <id>` on the command line
+pub fn cli_scenario() -> Option<String> {
+    cli_value("--scenario")
+}
+
+/// `--map <id>` on the command line
+pub fn cli_map() -> Option<String> {
+    cli_value("--map")
+}
+
+/// `--seed <n>` from the command line: every new game starts from it
+pub fn cli_seed() -> Option<u64> {
+    cli_value("--seed").and_then(|v| v.parse().ok())
+}
+
+pub fn cli_value(flag: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
+#[derive(Resource, Default)]
+pub struct ActivePreset(pub Option<String>);
+
+// --- GAME MODES ---
+// Sandbox: everything on. Challenge: tools yes, at management prices, but no
+// config tweaking or governor help. Observer: hands off, the governor keeps the
+// world going.
+#[derive(States, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameMode {
+    #[default]
+    Sandbox,
+    Challenge,
+    Observer,
+    Management, // the sandbox, but building and god powers cost resources
+    Experiment, // the sandbox, with fitness tracked and the fittest genomes reported
+}
+
+impl GameMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Sandbox => "Sandbox",
+            GameMode::Challenge => "Challenge",
+            GameMode::Observer => "Observer",
+            GameMode::Management => "Management",
+            GameMode::Experiment => "Experiment",
+        }
+    }
+
+    /// Whether building and god powers cost resources: in a challenge and in management
+    pub fn charges(&self) -> bool {
+        matches!(self, GameMode::Challenge | GameMode::Management)
+    }
+}
+
+// Present for one frame when the world should be torn down and regenerated
+#[derive(Resource)]
+pub struct NewGameRequest {
+    pub preset: Option<String>,
+    pub scenario: Option<String>, // its own preset takes over from `preset`
+    pub map: Option<String>,      // id of a map file in MAP_DIR
+    pub code: Option<WorldCode>,  // seed and world settings to start from
+}
+
+// --- WORLD CODES ---
+// A short string for the seed and the settings that shape a new world, so
+// another player can generate the same start: Crockford base32 of [version,
+// preset, the numbers as varints, checksum], in dash-separated groups of five.
+pub const WORLD_CODE_VERSION: u8 = 1;
+const WORLD_CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct WorldCode {
+    pub seed: u64,
+    pub preset: Option<String>, // one of PRESETS
+    pub map_size: i32,
+    pub tree_count: u32,
+    pub lake_count: u32,
+    pub lake_radius: i32,
+    pub starting_counts: [u32; 2], // sheep, wolves
+}
+
+impl WorldCode {
+    pub fn of(cfg: &SimulationConfig, seed: u64, preset: Option<&str>) -> Self {
+        Self {
+            seed,
+            preset: preset.filter(|id| PRESETS.iter().any(|(p, _)| p == id)).map(str::to_string),
+            map_size: cfg.map_size,
+            tree_count: cfg.tree_count,
+            lake_count: cfg.lake_count,
+            lake_radius: cfg.lake_radius,
+            starting_counts: [cfg.s(0).starting_count, cfg.s(1).starting_count],
+        }
+    }
+
+    // The world settings only; the seed goes to SimRng and the preset to the config load
+    pub fn apply(&self, cfg: &mut SimulationConfig) {
+        cfg.map_size = self.map_size;
+        cfg.tree_count = self.tree_count;
+        cfg.lake_count = self.lake_count;
+        cfg.lake_radius = self.lake_radius;
+        cfg.s_mut(0).starting_count = self.starting_counts[0];
+        cfg.s_mut(1).starting_count = self.starting_counts[1];
+    }
+
+    pub fn encode(&self) -> String {
+        let preset = self.preset.as_deref().and_then(|id| PRESETS.iter().position(|(p, _)| *p == id)).map_or(0, |i| i + 1);
+        let mut bytes = vec![WORLD_CODE_VERSION, preset as u8];
+        let numbers = [
+            self.seed,
+            self.map_size.max(0) as u64,
+            self.tree_count as u64,
+            self.lake_count as u64,
+            self.lake_radius.max(0) as u64,
+            self.starting_counts[0] as u64,
+            self.starting_counts[1] as u64,
+        ];
+        for mut n in numbers {
+            // LEB128: seven bits a byte, high bit set while more follow
+            loop {
+                let low = (n & 0x7f) as u8;
+                n >>= 7;
+                if n == 0 {
+                    bytes.push(low);
+                    break;
+                }
+                bytes.push(low | 0x80);
+            }
+        }
+        bytes.push(world_code_checksum(&bytes));
+
+        let (mut chars, mut acc, mut bits) = (Vec::new(), 0u32, 0);
+        for b in bytes {
+            acc = (acc << 8) | b as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                chars.push(WORLD_CODE_ALPHABET[((acc >> bits) & 31) as usize] as char);
+            }
+        }
+        if bits > 0 {
+            chars.push(WORLD_CODE_ALPHABET[((acc << (5 - bits)) & 31) as usize] as char);
+        }
+        chars.chunks(5).map(|group| group.iter().collect::<String>()).collect::<Vec<_>>().join("-")
+    }
+
+    // Case, dashes and spaces don't matter; O reads as 0 and I/L as 1
+    pub fn decode(text: &str) -> Result<Self, String> {
+        let mut bytes = Vec::new();
+        let (mut acc, mut bits) = (0u32, 0);
+        for c in text.chars().filter(|c| !c.is_whitespace() && *c != '-') {
+            let c = match c.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                c => c,
+            };
+            let value = WORLD_CODE_ALPHABET.iter().position(|&a| a as char == c).ok_or_else(|| format!("'{}' isn't used in world codes", c))?;
+            acc = (acc << 5) | value as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((acc >> bits) as u8);
+            }
+        }
+        // The last character's spare bits are always 0, so a typo there shows too
+        if acc & ((1 << bits) - 1) != 0 {
+            return Err("mistyped code (checksum doesn't match)".to_string());
+        }
+        let Some((&checksum, body)) = bytes.split_last() else { return Err("empty code".to_string()); };
+        if body.len() < 2 || world_code_checksum(body) != checksum {
+            return Err("mistyped code (checksum doesn't match)".to_string());
+        }
+        if body[0] != WORLD_CODE_VERSION {
+            return Err(format!("code from another build (version {})", body[0]));
+        }
+        let preset = match body[1] as usize {
+            0 => None,
+            i => Some(PRESETS.get(i - 1).ok_or_else(|| format!("unknown preset #{}", i))?.0.to_string()),
+        };
+
+        let mut numbers = Vec::new();
+        let (mut n, mut shift) = (0u64, 0);
+        for &b in &body[2..] {
+            if shift > 63 { return Err("number too long".to_string()); }
+            n |= ((b & 0x7f) as u64) << shift;
+            shift += 7;
+            if b & 0x80 == 0 {
+                numbers.push(n);
+                (n, shift) = (0, 0);
+            }
+        }
+        let &[seed, map_size, tree_count, lake_count, lake_radius, sheep, wolves] = &numbers[..] else {
+            return Err("incomplete code".to_string());
+        };
+        let small = |n: u64| u32::try_from(n).map_err(|_| format!("{} is out of range", n));
+        let code = Self {
+            seed,
+            preset,
+            map_size: small(map_size)? as i32,
+            tree_count: small(tree_count)?,
+            lake_count: small(lake_count)?,
+            lake_radius: small(lake_radius)? as i32,
+            starting_counts: [small(sheep)?, small(wolves)?],
+        };
+        if !(1..=1000).contains(&code.map_size) {
+            return Err(format!("map_size {} is out of range", code.map_size));
+        }
+        Ok(code)
+    }
+}
+
+// CRC-8 (polynomial 0x07). It catches every burst of up to 8 flipped bits, and
+// one mistyped character flips at most 5 neighbouring ones.
+fn world_code_checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |crc, &b| {
+        (0..8).fold(crc ^ b, |crc, _| if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 })
+    })
+}
+
+// --- SCENARIOS ---
+// (file id, display name); files live at assets/scenarios/<id>.ron
+pub const SCENARIOS: &[(&str, &str)] = &[
+    ("keep-alive-50", "Keep both alive for 50 days"),
+    ("sheep-boom", "200 sheep, no wolf extinction"),
+];
+
+pub fn scenario_path(id: &str) -> String {
+    format!("assets/scenarios/{}.ron", id)
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum Condition {
+    Days(f32),                                  // at least this many days have passed
+    Population { species: u32, at_least: u32 }, // that many living at once
+    Extinct(u32),                               // no living member of the species
+}
+
+impl Condition {
+    pub fn met(&self, day: f32, counts: &HashMap<u32, u32>) -> bool {
+        let living = |id: &u32| counts.get(id).copied().unwrap_or(0);
+        match self {
+            Condition::Days(days) => day >= *days,
+            Condition::Population { species, at_least } => living(species) >= *at_least,
+            Condition::Extinct(species) => living(species) == 0,
+        }
+    }
+
+    pub fn label(&self, cfg: &SimulationConfig) -> String {
+        let name = |id: &u32| cfg.species.get(id).map(|sc| sc.name.clone()).unwrap_or_else(|| format!("species {}", id));
+        match self {
+            Condition::Days(days) => format!("{:.0} days pass", days),
+            Condition::Population { species, at_least } => format!("{} {} alive", at_least, name(species)),
+            Condition::Extinct(species) => format!("{} die out", name(species)),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ScenarioDef {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub preset: Option<String>,
+    // Config overrides in the CONFIG_PATH format, applied last
+    #[serde(default)]
+    pub config: Option<ron::Value>,
+    pub win: Vec<Condition>,  // all at once
+    pub lose: Vec<Condition>, // any one
+}
+
+impl ScenarioDef {
+    pub fn load(id: &str) -> Result<Self, String> {
+        let path = scenario_path(id);
+        let text = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+        ron::from_str(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+}
+
+pub struct ScenarioRun {
+    pub def: ScenarioDef,
+    pub outcome: Option<bool>, // Some(true) = won
+    pub peak: HashMap<u32, u32>,
+}
+
+#[derive(Resource, Default)]
+pub struct ActiveScenario(pub Option<ScenarioRun>);
+
+// Recursively lay `over` onto `base`: maps merge key by key, anything else replaces
+pub fn merge_ron_value(base: &mut ron::Value, over: ron::Value) {
+    match (base, over) {
+        (ron::Value::Map(base_map), ron::Value::Map(over_map)) => {
+            for (key, value) in over_map.into_iter() {
+                let merged = match base_map.remove(&key) {
+                    Some(mut old) => {
+                        merge_ron_value(&mut old, value);
+                        old
+                    }
+                    None => value,
+                };
+                base_map.insert(key, merged);
+            }
+        }
+        // `()` = empty override file
+        (_, ron::Value::Unit) => {}
+        (slot, value) => *slot = value,
+    }
+}
+
+// ========================
+// 2) BEHAVIOR DEFINITIONS (assets/behaviors/*.ron)
+// =========================
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Goal {
+    Food, // nearest plant
+    Mate, // nearest same-species partner that is free to breed
+    Prey, // nearest creature of `species`
+    Flee, // run from the nearest creature of `species`
+    Shelter, // during storms: nearest tree or hut with room, then stay put there
+    Fish,    // nearest land tile next to deep water, then stay put and fish
+    Mother,  // babies: back to the mother (or any adult of their kind) when they stray
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum StageFilter {
+    #[default]
+    Any,
+    Adult,
+    Baby,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BehaviorRule {
+    pub goal: Goal,
+    #[serde(default)]
+    pub species: Vec<u32>,
+    #[serde(default)]
+    pub min_hunger: Option<f32>,
+    #[serde(default)]
+    pub max_hunger: Option<f32>,
+    #[serde(default)]
+    pub stage: StageFilter,
+    #[serde(default)]
+    pub target_adults_only: bool,
+    pub weight: i32,
+}
+
+impl BehaviorRule {
+    pub fn applies(&self, hunger: f32, is_adult: bool) -> bool {
+        if self.min_hunger.map(|m| hunger < m).unwrap_or(false) { return false; }
+        if self.max_hunger.map(|m| hunger > m).unwrap_or(false) { return false; }
+        match self.stage {
+            StageFilter::Any => true,
+            StageFilter::Adult => is_adult,
+            StageFilter::Baby => !is_adult,
+        }
+    }
+}
+
+// Lays `deposit` on every tile stepped onto, and scores moves by `weight` per unit
+// of scent on the tile (negative keeps away from it)
+#[derive(Deserialize, Clone, Debug)]
+pub struct ScentRule {
+    pub channel: Pheromone,
+    #[serde(default)]
+    pub deposit: f32,
+    #[serde(default)]
+    pub weight: f32,
+    #[serde(default)]
+    pub min_hunger: Option<f32>,
+    #[serde(default)]
+    pub max_hunger: Option<f32>,
+}
+
+impl ScentRule {
+    pub fn applies(&self, hunger: f32) -> bool {
+        self.min_hunger.map(|m| hunger >= m).unwrap_or(true) && self.max_hunger.map(|m| hunger <= m).unwrap_or(true)
+    }
+}
+
+// Ordered rules: the first one that applies and finds a target wins.
+// Scent rules all apply on top.
+#[derive(Deserialize, Clone, Default, Debug)]
+pub struct BehaviorDef {
+    pub rules: Vec<BehaviorRule>,
+    #[serde(default)]
+    pub scents: Vec<ScentRule>,
+}
+
+// Live tuning (the app's behavior editor); a reload from file undoes it
+pub const MAX_RULE_WEIGHT: i32 = 100;
+
+impl BehaviorDef {
+    pub fn nudge_weight(&mut self, rule: usize, delta: i32) {
+        if let Some(r) = self.rules.get_mut(rule) {
+            r.weight = (r.weight + delta).clamp(0, MAX_RULE_WEIGHT);
+        }
+    }
+
+    // Earlier rules win ties for who decides, so this is the rule's priority
+    pub fn raise(&mut self, rule: usize) {
+        if rule > 0 && rule < self.rules.len() {
+            self.rules.swap(rule - 1, rule);
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SpeciesBehaviors(pub HashMap<u32, BehaviorDef>);
+
+impl SpeciesBehaviors {
+    pub fn load(cfg: &SimulationConfig) -> Self {
+        let mut defs = HashMap::new();
+        for (&id, sc) in cfg.species.iter() {
+            let from_file = std::fs::read_to_string(&sc.behavior_path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| ron::from_str::<BehaviorDef>(&text).map_err(|e| e.to_string()));
+
+            let def = match from_file {
+                Ok(def) => def,
+                Err(err) => {
+                    println!("Behavior file {} unusable ({}), using built-in rules for {}", sc.behavior_path, err, sc.name);
+                    BUILTIN_BEHAVIORS
+                        .iter()
+                        .find(|(path, _)| *path == sc.behavior_path)
+                        .map(|(_, text)| ron::from_str::<BehaviorDef>(text).expect("Built-in behavior must parse"))
+                        .unwrap_or_default()
+                }
+            };
+            defs.insert(id, def);
+        }
+        Self(defs)
+    }
+
+    pub fn get(&self, species_id: u32) -> &BehaviorDef {
+        self.0.get(&species_id).expect("Missing BehaviorDef")
+    }
+
+    // The food web: a species hunts whatever its Prey rules list
+    pub fn hunts(&self, predator: u32, prey: u32) -> bool {
+        self.0
+            .get(&predator)
+            .map(|def| def.rules.iter().any(|r| r.goal == Goal::Prey && r.species.contains(&prey)))
+            .unwrap_or(false)
+    }
+
+    // A Fish rule that applies is what sends a creature fishing, and its
+    // thresholds are the only ones fishing_system goes by
+    pub fn fishes(&self, species_id: u32, hunger: f32, is_adult: bool) -> bool {
+        self.0
+            .get(&species_id)
+            .map(|def| def.rules.iter().any(|r| r.goal == Goal::Fish && r.applies(hunger, is_adult)))
+            .unwrap_or(false)
+    }
+
+    pub fn is_hunter(&self, species_id: u32) -> bool {
+        self.0
+            .get(&species_id)
+            .map(|def| def.rules.iter().any(|r| r.goal == Goal::Prey && !r.species.is_empty()))
+            .unwrap_or(false)
+    }
+}
+
+pub fn load_behaviors(mut commands: Commands, cfg: Res<SimulationConfig>) {
+    commands.insert_resource(SpeciesBehaviors::load(&cfg));
+}
+
+// --- HOT RELOAD ---
+// Polls the config and behavior files' modification times and re-applies them.
+// Behavior rules and most config fields take effect immediately;
+// see SimulationConfig::restart_only_changes for the exceptions.
+#[derive(Resource)]
+pub struct FileWatcher {
+    pub timer: Timer,
+    pub seen: HashMap<String, Option<SystemTime>>,
+}
+
+impl Default for FileWatcher {
+    fn default() -> Self {
+        Self { timer: Timer::from_seconds(1.0, TimerMode::Repeating), seen: HashMap::new() }
+    }
+}
+
+pub fn modified_time(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+pub fn hot_reload_system(
+    time: Res<Time>,
+    mut watcher: ResMut<FileWatcher>,
+    mut cfg: ResMut<SimulationConfig>,
+    mut behaviors: ResMut<SpeciesBehaviors>,
+    preset: Res<ActivePreset>,
+    scenario: Res<ActiveScenario>,
+) {
+    watcher.timer.tick(time.delta());
+    if !watcher.timer.just_finished() {
+        return;
+    }
+
+    // Returns true when `path` changed since the last poll (first sighting only records it)
+    let mut changed = |path: &str| -> bool {
+        let now = modified_time(path);
+        match watcher.seen.insert(path.to_string(), now) {
+            Some(before) => before != now,
+            None => false,
+        }
+    };
+
+    let config_changed = changed(CONFIG_PATH) | changed(&data_path(SETTINGS_FILE).to_string_lossy());
+    let preset_changed = preset.0.as_deref().map(|id| changed(&preset_path(id))).unwrap_or(false);
+    if config_changed || preset_changed {
+        let scenario_overrides = scenario.0.as_ref().and_then(|run| run.def.config.as_ref());
+        match SimulationConfig::from_layers(preset.0.as_deref(), scenario_overrides) {
+            Ok(new_cfg) => {
+                let pending = cfg.restart_only_changes(&new_cfg);
+                cfg.apply_reload(new_cfg);
+                println!("Reloaded config");
+                if !pending.is_empty() {
+                    println!("  (applies on restart: {})", pending.join(", "));
+                }
+            }
+            Err(err) => println!("Config reload failed, keeping current config: {}", err),
+        }
+    }
+
+    let behavior_paths: Vec<String> = cfg.species.values().map(|sc| sc.behavior_path.clone()).collect();
+    let mut behaviors_changed = false;
+    for path in &behavior_paths {
+        behaviors_changed |= changed(path);
+    }
+    if behaviors_changed {
+        *behaviors = SpeciesBehaviors::load(&cfg);
+        println!("Reloaded behavior rules");
+    }
+}
+
+// --- GENETICS COMPONENTS ---
+
+#[derive(Component, Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Dna {
+    pub move_speed_seconds: f32, // Lower is faster
+    pub metabolism_rate: f32,    // Hunger per second (Lower is better)
+    pub sight_range: i32,        // Higher is better
+    pub size_multiplier: f32,    // Cosmetic + maybe dominance?
+}
+
+impl Dna {
+    // In DNA_TRAITS order
+    pub fn traits(&self) -> [f32; 4] {
+        [self.move_speed_seconds, self.metabolism_rate, self.sight_range as f32, self.size_multiplier]
+    }
+}
+
+pub const DNA_TRAITS: [&str; 4] = ["Speed", "Metabolism", "Sight", "Size"];
+
+// (lowest, highest) per trait for display and hand edits: mutate_dna's clamps
+// (sight isn't clamped there; 1 to 20 tiles covers any map)
+pub const DNA_RANGES: [(f32, f32); 4] = [(0.05, 5.0), (0.5, 20.0), (1.0, 20.0), (0.5, 2.0)];
+
+impl Dna {
+    // One trait set by hand (DNA_TRAITS order), kept inside DNA_RANGES
+    pub fn with_trait(mut self, index: usize, value: f32) -> Self {
+        let Some(&(lo, hi)) = DNA_RANGES.get(index) else { return self; };
+        let value = value.clamp(lo, hi);
+        match index {
+            0 => self.move_speed_seconds = value,
+            1 => self.metabolism_rate = value,
+            2 => self.sight_range = value.round() as i32,
+            _ => self.size_multiplier = value,
+        }
+        self
+    }
+}
+
+// Genes changed by hand (the app's debug genome editor), so results from it
+// can be told apart. Not passed on: offspring are ordinary again.
+#[derive(Component)]
+pub struct GeneEdited;
+
+#[derive(Component)]
+pub struct Generation(pub u32); // one more than the deeper parent's; founders are 0
+
+// Name of the generation-0 ancestor at the top of its deepest line (the parent
+// with the higher generation, the mother on a tie). Founders are their own.
+#[derive(Component, Clone)]
+pub struct Founder(pub String);
+
+// Sub-key for an animal's own SimRng streams. Entity ids depend on everything
+// the World spawned before, so two worlds loading one snapshot number their
+// animals differently; this comes from the animal's name and where it appeared.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StreamKey(pub u64);
+
+impl StreamKey {
+    pub fn new(name: &str, (x, y): (i32, i32)) -> Self {
+        let mut hasher = std::hash::DefaultHasher::new();
+        (name, x, y).hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+// Set at birth, or to the foster parent on adoption. Saved as the mother's
+// place in the snapshot's creature list.
+#[derive(Component)]
+pub struct Mother(pub Entity);
+
+// Baby taken in by a foster parent (its Mother now), who shares meals with it
+#[derive(Component)]
+pub struct Fostered;
+
+#[derive(Component)]
+pub struct CreatureName(pub String);
+
+// --- POPULATION GENETICS ---
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TraitStats {
+    pub mean: f32,
+    pub variance: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct GeneticsSample {
+    pub day: f32,
+    pub species_id: u32,
+    pub count: u32,
+    pub traits: [TraitStats; 4], // DNA_TRAITS order
+    pub heterozygosity: f32,
+}
+
+impl GeneticsSample {
+    // Traits blend rather than come in discrete alleles, so heterozygosity is the
+    // expected one (1 - sum of p^2) over values binned at 5% of the species mean,
+    // averaged over the traits
+    pub fn measure(day: f32, species_id: u32, genomes: &[Dna]) -> Option<Self> {
+        if genomes.is_empty() { return None; }
+        let n = genomes.len() as f32;
+        let values: Vec<[f32; 4]> = genomes.iter().map(Dna::traits).collect();
+
+        let mut traits = [TraitStats::default(); 4];
+        let mut heterozygosity = 0.0;
+        for (i, stats) in traits.iter_mut().enumerate() {
+            let mean = values.iter().map(|v| v[i]).sum::<f32>() / n;
+            let variance = values.iter().map(|v| (v[i] - mean).powi(2)).sum::<f32>() / n;
+            *stats = TraitStats { mean, variance };
+
+            let bin_width = mean.abs().max(f32::EPSILON) * 0.05;
+            let mut bins: HashMap<i64, u32> = HashMap::new();
+            for v in &values {
+                *bins.entry((v[i] / bin_width).round() as i64).or_insert(0) += 1;
+            }
+            let homozygosity: f32 = bins.values().map(|&c| (c as f32 / n).powi(2)).sum();
+            heterozygosity += (1.0 - homozygosity) / DNA_TRAITS.len() as f32;
+        }
+
+        Some(Self { day, species_id, count: genomes.len() as u32, traits, heterozygosity })
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct GeneticsHistory {
+    pub samples: Vec<GeneticsSample>, // oldest first
+    pub last_day: Option<u32>,
+}
+
+impl GeneticsHistory {
+    pub fn first(&self, species_id: u32) -> Option<&GeneticsSample> {
+        self.samples.iter().find(|s| s.species_id == species_id)
+    }
+
+    pub fn latest(&self, species_id: u32) -> Option<&GeneticsSample> {
+        self.samples.iter().rev().find(|s| s.species_id == species_id)
+    }
+
+    pub fn to_csv(&self, cfg: &SimulationConfig) -> String {
+        let mut out = String::from("Day,Species,Count");
+        for name in DNA_TRAITS {
+            out.push_str(&format!(",{0} mean,{0} variance", name));
+        }
+        out.push_str(",Heterozygosity\n");
+        for sample in &self.samples {
+            out.push_str(&format!(
+                "{:.1},{},{}",
+                sample.day,
+                cfg.s(sample.species_id).name,
+                sample.count
+            ));
+            for t in &sample.traits {
+                out.push_str(&format!(",{:.4},{:.4}", t.mean, t.variance));
+            }
+            out.push_str(&format!(",{:.4}\n", sample.heterozygosity));
+        }
+        out
+    }
+}
+
+// --- SPECIATION ---
+// Derived clade ids start here, well clear of the species ids
+pub const FIRST_DERIVED_CLADE: u32 = 100;
+
+const CLADE_COLORS: [[f32; 3]; 6] = [
+    [0.2, 0.6, 1.0],
+    [1.0, 0.4, 0.7],
+    [0.3, 0.9, 0.4],
+    [1.0, 0.6, 0.1],
+    [0.6, 0.3, 0.9],
+    [0.1, 0.9, 0.9],
+];
+
+// Only on creatures of a derived species; everyone else is in their base
+// species' clade (clade id == species_id). Saved with the creature, and the
+// registry with the world.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Clade(pub u32);
+
+pub fn clade_of(stats: &CreatureStats, clade: Option<&Clade>) -> u32 {
+    clade.map(|c| c.0).unwrap_or(stats.species_id)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CladeInfo {
+    pub id: u32,
+    pub species_id: u32, // ecological role: what it eats, who hunts it
+    pub parent: u32,     // clade it split from
+    pub name: String,
+    pub color: [f32; 3],
+    pub founded_day: f32,
+}
+
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct SpeciesRegistry {
+    pub derived: Vec<CladeInfo>, // in order of appearance
+    pub last_check_day: Option<u32>,
+}
+
+impl SpeciesRegistry {
+    pub fn get(&self, clade: u32) -> Option<&CladeInfo> {
+        self.derived.iter().find(|c| c.id == clade)
+    }
+
+    pub fn name(&self, cfg: &SimulationConfig, clade: u32) -> String {
+        match self.get(clade) {
+            Some(info) => info.name.clone(),
+            None => cfg.s(clade).name.clone(),
+        }
+    }
+
+    fn found(&mut self, cfg: &SimulationConfig, species_id: u32, parent: u32, day: f32) -> &CladeInfo {
+        let index = self.derived.len();
+        let siblings = self.derived.iter().filter(|c| c.species_id == species_id).count();
+        // Base species is "A", its first offshoot "B", then "C"...
+        let suffix = if siblings < 25 { ((b'B' + siblings as u8) as char).to_string() } else { (siblings + 1).to_string() };
+        self.derived.push(CladeInfo {
+            id: FIRST_DERIVED_CLADE + index as u32,
+            species_id,
+            parent,
+            name: format!("{} {}", cfg.s(species_id).name, suffix),
+            color: CLADE_COLORS[index % CLADE_COLORS.len()],
+            founded_day: day,
+        });
+        &self.derived[index]
+    }
+}
+
+/// Part of a clade split off and became a species of its own
+#[derive(Message, Clone)]
+pub struct SpeciationEvent {
+    pub clade: u32,
+    pub parent: u32,
+    pub species_id: u32,
+    pub name: String,
+    pub members: u32,
+    pub day: f32,
+}
+
+// Mean relative distance between two groups' trait means
+pub fn trait_divergence(a: &[Dna], b: &[Dna]) -> f32 {
+    let means = |genomes: &[Dna]| {
+        let mut sum = [0.0; 4];
+        for dna in genomes {
+            for (total, v) in sum.iter_mut().zip(dna.traits()) {
+                *total += v;
+            }
+        }
+        sum.map(|total| total / genomes.len().max(1) as f32)
+    };
+    let (ma, mb) = (means(a), means(b));
+    ma.iter()
+        .zip(&mb)
+        .map(|(x, y)| (x - y).abs() / ((x.abs() + y.abs()) / 2.0).max(f32::EPSILON))
+        .sum::<f32>()
+        / DNA_TRAITS.len() as f32
+}
+
+// --- EVOLUTION EXPERIMENT ---
+// Written at every fitness report in the config override format: copy its
+// seed_genomes into CONFIG_PATH or a preset to start a run from the winners
+pub const TOP_GENOMES_FILE: &str = "saves/top_genomes.ron"; // in data_dir()
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeedGenome {
+    pub species_id: u32,
+    pub dna: Dna,
+}
+
+#[derive(Clone, Debug)]
+pub struct FitnessRecord {
+    pub name: String,
+    pub species_id: u32,
+    pub generation: u32,
+    pub dna: Dna,
+    pub born_day: f32,
+    pub died_day: Option<f32>,
+    pub offspring: u32,
+    pub kills: u32,
+}
+
+impl FitnessRecord {
+    pub fn lifespan_days(&self, today: f32) -> f32 {
+        self.died_day.unwrap_or(today) - self.born_day
+    }
+
+    pub fn score(&self, cfg: &SimulationConfig, today: f32) -> f32 {
+        self.offspring as f32 * cfg.fitness_offspring_weight
+            + self.kills as f32 * cfg.fitness_kill_weight
+            + self.lifespan_days(today) * cfg.fitness_lifespan_weight
+    }
+}
+
+// Everyone seen while the experiment runs, living or dead
+#[derive(Resource, Default)]
+pub struct FitnessLedger {
+    pub records: HashMap<Entity, FitnessRecord>,
+    pub last_report_day: Option<u32>,
+}
+
+impl FitnessLedger {
+    // Fittest first; ties go to the older entity so the order is stable
+    pub fn ranking(&self, cfg: &SimulationConfig, species_id: u32, today: f32) -> Vec<(Entity, &FitnessRecord)> {
+        let mut ranked: Vec<(Entity, &FitnessRecord)> = self
+            .records
+            .iter()
+            .filter(|(_, r)| r.species_id == species_id)
+            .map(|(e, r)| (*e, r))
+            .collect();
+        ranked.sort_by(|(ea, a), (eb, b)| {
+            b.score(cfg, today).total_cmp(&a.score(cfg, today)).then(ea.to_bits().cmp(&eb.to_bits()))
+        });
+        ranked
+    }
+}
+
+// --- LOGGING RESOURCE ---
+#[derive(Resource)]
+pub struct SimulationLogger {
+    pub file: Option<File>,
+}
+
+impl Default for SimulationLogger {
+    fn default() -> Self {
+        // Create a unique filename based on time
+        let start = SystemTime::now();
+        let timestamp = start.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let path = data_path(&format!("{}/sim_log_{}.csv", LOG_DIR, timestamp));
+
+        let mut file = ensure_parent(&path).and_then(|_| File::create(&path)).ok();
+
+        // Write CSV Header
+        if let Some(ref mut f) = file {
+            writeln!(f, "Event,Time,Species,Name,Gen,Speed,Metabolism,Sight").ok();
+        }
+
+        Self { file }
+    }
+}
+
+impl SimulationLogger {
+    // Renamed 'gen' -> 'generation'
+    pub fn log(&mut self, event_type: &str, time: f32, species: &str, name: &str, generation: u32, dna: &Dna) {
+        if let Some(ref mut f) = self.file {
+            writeln!(
+                f,
+                "{},{:.2},{},{},{},{:.3},{:.3},{}",
+                event_type, time, species, name, generation,
+                dna.move_speed_seconds, dna.metabolism_rate, dna.sight_range
+            ).ok();
+        }
+    }
+
+    // Species-wide events with no single creature behind them
+    pub fn log_species(&mut self, event_type: &str, time: f32, species: &str) {
+        if let Some(ref mut f) = self.file {
+            writeln!(f, "{},{:.2},{},,,,,", event_type, time, species).ok();
+        }
+    }
+
+    pub fn log_day(&mut self, report: &DayReport) {
+        if let Some(ref mut f) = self.file {
+            writeln!(f, "Day summary,{:.2},,{},,,,", report.day as f32, report.describe()).ok();
+        }
+    }
+
+    // One line of a creature's life story, dumped when it dies
+    pub fn log_life(&mut self, what: &str, time: f32, species: &str, name: &str, generation: u32) {
+        if let Some(ref mut f) = self.file {
+            writeln!(f, "Life: {},{:.2},{},{},{},,,", what, time, species, name, generation).ok();
+        }
+    }
+}
+
+// --- COMPONENTS ---
+// This tags an entity as being a "Tile" at a specific grid location
+#[derive(Component)]
+pub struct Tile {
+    pub x: i32,
+    pub y: i32,
+}
+
+// Grid coordinate -> tile entity, filled once by spawn_map
+#[derive(Resource, Default)]
+pub struct TileIndex(pub HashMap<(i32, i32), Entity>);
+
+// How fouled a water tile is, 0 (clean) to 1; shared across the body of water
+#[derive(Component)]
+pub struct Pollution(pub f32);
+
+impl Pollution {
+    pub fn is_toxic(&self, cfg: &SimulationConfig) -> bool {
+        self.0 > cfg.pollution_kill_threshold
+    }
+}
+
+// Sick from drinking foul water: burns hunger faster until it wears off
+#[derive(Component)]
+pub struct Infected(pub f32); // seconds left
+
+// Grass biomass on a tile, 0 (bare) to 1 (lush)
+#[derive(Component)]
+pub struct Grass(pub f32);
+
+impl Default for Grass {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+// Accumulated creature traffic on a tile; decays while nobody walks on it
+#[derive(Component, Default)]
+pub struct Footfall {
+    pub wear: f32,
+    pub dirt: bool,
+}
+
+// Manhattan distance from each tile to the nearest Water tile.
+// Rebuilt (multi-source BFS) only when water is added or removed.
+#[derive(Resource, Default)]
+pub struct WaterDistanceField {
+    pub dist: HashMap<(i32, i32), i32>, // missing = no water anywhere
+}
+
+impl WaterDistanceField {
+    pub fn plant_multiplier(&self, x: i32, y: i32, cfg: &SimulationConfig) -> f32 {
+        let Some(&d) = self.dist.get(&(x, y)) else { return cfg.dry_plant_multiplier; };
+        let falloff = cfg.water_influence_distance.max(1) as f32;
+        let t = (1.0 - (d - 1).max(0) as f32 / falloff).clamp(0.0, 1.0);
+        cfg.dry_plant_multiplier + (cfg.shore_plant_multiplier - cfg.dry_plant_multiplier) * t
+    }
+}
+
+// 1. Tag for the creature
+#[derive(Component)]
+#[require(MoveDecision)]
+pub struct Creature;
+
+// Step picked by plan_creature_moves, applied by apply_creature_moves
+#[derive(Component, Default)]
+pub struct MoveDecision(pub Option<(i32, i32)>);
+
+// The steps plan_creature_moves scores, in order
+pub const MOVE_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (-1, 0), (1, 0)];
+
+/// Why a creature moved the way it did, filled in by the planner each time it
+/// plans for a creature that carries one. Frontends add it to the creature
+/// they're inspecting; nobody else pays for it.
+#[derive(Component, Default, Clone, Debug)]
+pub struct DecisionTrace {
+    pub state: &'static str,
+    pub goal: Option<Goal>,
+    pub target: Option<(i32, i32)>,
+    pub scores: [Option<i32>; 4], // per MOVE_DIRECTIONS; None = off the map or walled off
+    pub chosen: Option<(i32, i32)>,
+    pub path: Vec<(i32, i32)>, // tiles it means to walk to the target, next step first
+    pub replans: u32,          // times the path didn't carry on from the last one
+}
+
+fn trace_state(trace: &mut Option<Mut<DecisionTrace>>, state: &'static str) {
+    if let Some(trace) = trace {
+        **trace = DecisionTrace { state, replans: trace.replans, ..default() };
+    }
+}
+
+// 2. Logic Position (Where they actually are in the grid)
+#[derive(Component)]
+pub struct GridPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+// 3. When they next get to step, on the shared MoveClock (so they don't move at light speed).
+// Fresh movers start unscheduled and get a staggered slot on their first frame.
+#[derive(Component, Default)]
+pub struct NextMove(pub Option<u64>);
+
+// One clock paces everything that walks: it ticks every `move_tick_seconds`, and
+// movers wait a whole number of its ticks between steps instead of each running a timer
+#[derive(Resource, Default)]
+pub struct MoveClock {
+    pub tick: u64,
+    pub carry: f32, // seconds towards the next tick
+}
+
+impl MoveClock {
+    pub fn ticks_for(cfg: &SimulationConfig, seconds: f32) -> u64 {
+        (seconds / cfg.move_tick_seconds.max(0.001)).round().max(1.0) as u64
+    }
+
+    pub fn is_due(&self, next: &NextMove) -> bool {
+        next.0.is_some_and(|at| self.tick >= at)
+    }
+
+    pub fn after(&self, interval: u64) -> NextMove {
+        NextMove(Some(self.tick + interval))
+    }
+}
+
+pub fn advance_move_clock(time: Res<Time>, cfg: Res<SimulationConfig>, mut clock: ResMut<MoveClock>) {
+    let step = cfg.move_tick_seconds.max(0.001);
+    clock.carry += time.delta_secs();
+    while clock.carry >= step {
+        clock.carry -= step;
+        clock.tick += 1;
+    }
+}
+
+// Impassable for creatures (plants ignore it)
+#[derive(Component)]
+pub struct Fence;
+
+// Player-built hut: up to `shelter_capacity` creatures next to it sit out storms
+// inside, and sheep in it are safe from wolves at night
+#[derive(Component, Default)]
+pub struct Shelter {
+    pub occupants: Vec<Entity>,
+}
+
+// Inside a Shelter (handed out by shelter_occupancy_system)
+#[derive(Component)]
+pub struct Sheltered;
+
+// A gap in a fence that only lets one species through (None = everyone)
+#[derive(Component, Clone, Copy)]
+pub struct Gate {
+    pub allowed: Option<u32>,
+}
+
+impl Gate {
+    pub fn allows(&self, species_id: u32) -> bool {
+        self.allowed.map(|id| id == species_id).unwrap_or(true)
+    }
+}
+
+// Player avatar: sheep follow it, wolves keep their distance
+#[derive(Component)]
+pub struct Shepherd;
+
+// Small birds that pick ticks off the animals and eat insects. Not a species
+// (no breeding or genes), but they live off what they eat: the flock only
+// grows back towards `bird_count` while there's food, and a bird that goes
+// hungry too long leaves.
+#[derive(Component, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Bird {
+    pub hunger: f32, // seconds since its last meal
+}
+
+// Pollinator flying from sun plant to sun plant (scenery like birds)
+#[derive(Component, Default)]
+pub struct Insect {
+    pub target: Option<Entity>, // plant it's heading for
+}
+
+// Recent pollinator visits, fading over time; pollinated plants seed next to themselves
+#[derive(Component)]
+pub struct Pollination(pub f32);
+
+#[derive(Component)]
+pub struct Water {
+    pub depth: f32, // 0..max_water_depth; below deep_water_threshold is shallow
+}
+
+impl Water {
+    pub fn is_deep(&self, cfg: &SimulationConfig) -> bool {
+        self.depth >= cfg.deep_water_threshold
+    }
+}
+
+// Walkable planks laid over a Water tile: no drowning, no water penalty
+#[derive(Component)]
+pub struct Bridge;
+
+#[derive(Component)]
+pub struct Plant;
+
+// Plant that grows under tree cover (the only kind that does)
+#[derive(Component)]
+pub struct ShadeTolerant;
+
+#[derive(Component)]
+pub struct Tree;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum CropStage {
+    Seed,
+    Sprout,
+    Ripe, // becomes a regular (edible) Plant
+}
+
+// Player-sown crop; grows with the sun and can be trampled until ripe
+#[derive(Component)]
+pub struct Crop {
+    pub stage: CropStage,
+    pub growth: f32, // seconds of growth into the current stage
+}
+
+// Tile under a tree's canopy
+#[derive(Component)]
+pub struct Shaded;
+
+#[derive(Component)]
+pub struct Hunger(pub f32); // Value from 0.0 (Full) to 100.0 (Starving)
+
+// Marked for the reaper; the cause decides what's left behind (see DeathCause)
+#[derive(Component)]
+pub struct Dead {
+    pub cause: DeathCause,
+}
+
+// What's left of a creature that died where it lay; rots away after corpse_seconds
+#[derive(Component)]
+pub struct Corpse {
+    pub species_id: u32,
+    pub cause: DeathCause,
+    pub seconds_left: f32,
+}
+
+// Visual marker (brown X) on a freshly grazed tile; the tile's Fertility drives it
+#[derive(Component)]
+pub struct ExhaustedSoil;
+
+// How ready a tile is to grow plants again (1.0 = fully fertile)
+#[derive(Component)]
+pub struct Fertility {
+    pub level: f32,
+    pub recovered_from: f32,  // seconds spent recovering since the last graze
+    pub recovery_seconds: f32,
+    pub graze_pressure: f32,  // recent grazes, slowly forgotten
+}
+
+impl Default for Fertility {
+    fn default() -> Self {
+        Self { level: 1.0, recovered_from: 0.0, recovery_seconds: 0.0, graze_pressure: 0.0 }
+    }
+}
+
+impl Fertility {
+    // Overgrazing: every graze the soil still remembers stretches the next recovery
+    pub fn graze(&mut self, cfg: &SimulationConfig) {
+        self.level = 0.0;
+        self.recovered_from = 0.0;
+        self.recovery_seconds =
+            cfg.fertility_recovery_seconds * (1.0 + cfg.overgraze_extra_per_graze * self.graze_pressure);
+        self.graze_pressure += 1.0;
+    }
+}
+
+#[derive(Resource)]
+pub struct GameStats {
+    pub days: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub const ALL: [Season; 4] = [Season::Spring, Season::Summer, Season::Autumn, Season::Winter];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+}
+
+impl GameStats {
+    // Each day starts at dawn
+    pub fn is_night(&self, cfg: &SimulationConfig) -> bool {
+        self.days.fract() >= cfg.daylight_fraction
+    }
+
+    pub fn season(&self, cfg: &SimulationConfig) -> Season {
+        Season::ALL[self.season_index(cfg) as usize % 4]
+    }
+
+    // Seasons since day 0: year = index / 4
+    pub fn season_index(&self, cfg: &SimulationConfig) -> u32 {
+        (self.days / cfg.days_per_season.max(0.01)) as u32
+    }
+
+    // Plant growth multiplier from the sun: 0 at night, seasonal during the day
+    pub fn sunlight(&self, cfg: &SimulationConfig) -> f32 {
+        if self.is_night(cfg) {
+            return 0.0;
+        }
+        cfg.season_growth[self.season(cfg) as usize]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum WeatherKind {
+    Clear,   // slow evaporation
+    Rain,    // ponds fill and creep outwards
+    Drought, // fast evaporation
+    Storm,   // rains like Rain; creatures take shelter and lightning strikes the exposed
+}
+
+impl WeatherKind {
+    pub fn is_wet(&self) -> bool {
+        matches!(self, WeatherKind::Rain | WeatherKind::Storm)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WeatherKind::Clear => "Clear",
+            WeatherKind::Rain => "Rain",
+            WeatherKind::Drought => "Drought",
+            WeatherKind::Storm => "Storm",
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Weather {
+    pub kind: WeatherKind,
+    pub timer: Timer, // time left until the next weather roll
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self {
+            kind: WeatherKind::Clear,
+            timer: Timer::from_seconds(60.0, TimerMode::Once),
+        }
+    }
+}
+
+// Seeded randomness for everything that decides the simulation (not cosmetics).
+// Systems don't share one generator, since their run order isn't fixed: each
+// draws its own stream from the seed, the tick and a name, so two runs with the
+// same seed play out the same.
+#[derive(Resource)]
+pub struct SimRng {
+    pub seed: u64,
+    pub fixed_seed: Option<u64>, // from --seed; otherwise every new game rolls one
+    pub tick: u64,
+}
+
+impl SimRng {
+    pub fn new(fixed_seed: Option<u64>) -> Self {
+        Self { seed: fixed_seed.unwrap_or_else(rand::random), fixed_seed, tick: 0 }
+    }
+
+    pub fn reseed(&mut self) {
+        *self = Self::new(self.fixed_seed);
+    }
+
+    pub fn stream(&self, name: &str) -> SmallRng {
+        self.stream_for(name, 0)
+    }
+
+    // Several independent streams under one name, e.g. one per entity
+    pub fn stream_for(&self, name: &str, sub: u64) -> SmallRng {
+        let mut hasher = std::hash::DefaultHasher::new();
+        (self.seed, self.tick, name, sub).hash(&mut hasher);
+        SmallRng::seed_from_u64(hasher.finish())
+    }
+}
+
+// First thing every frame, so all of Update sees the same tick
+pub fn advance_sim_rng(mut rng: ResMut<SimRng>) {
+    rng.tick += 1;
+}
+
+// Set by embedders that bring their own config: new games use it as is
+#[derive(Resource)]
+pub struct FixedConfig(pub SimulationConfig);
+
+// Present on headless apps: nothing is saved, logged to file or hot-reloaded
+#[derive(Resource)]
+pub struct Headless;
+
+pub fn on_disk(headless: Option<Res<Headless>>) -> bool {
+    headless.is_none()
+}
+
+// Population sample from the previous governor tick, for trends
+#[derive(Resource, Default)]
+pub struct BalanceGovernor {
+    pub elapsed: f32,
+    pub last: Option<(u32, u32)>, // (sheep, wolves)
+}
+
+// Defines physical limits
+#[derive(Component)]
+pub struct CreatureStats {
+    pub sight_range: i32, // How many tiles away they can see
+    pub species_id: u32,  // 0 = White Squares, 1 = Red Triangles, etc.
+}
+
+// Defines logic flags
+#[derive(Component)]
+pub struct CreatureBehavior {
+    pub scared_of_water: bool,
+    pub altruistic: bool, // If true, won't eat if healthy + friend is nearby
+}
+
+#[derive(Component)]
+pub struct Age {
+    pub seconds_alive: f32,
+    pub is_adult: bool, // elders too
+    pub is_elder: bool, // past breeding: slower and short-sighted
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LifeStage {
+    Juvenile,
+    Adult,
+    Elder,
+}
+
+impl LifeStage {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LifeStage::Juvenile => "Juvenile",
+            LifeStage::Adult => "Adult",
+            LifeStage::Elder => "Elder",
+        }
+    }
+}
+
+impl Age {
+    pub fn stage(&self) -> LifeStage {
+        if self.is_elder {
+            LifeStage::Elder
+        } else if self.is_adult {
+            LifeStage::Adult
+        } else {
+            LifeStage::Juvenile
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ReproductionCooldown(pub Timer);
+
+#[derive(Component)]
+pub struct History {
+    pub last_x: i32,
+    pub last_y: i32,
+    pub trail: VecDeque<(i32, i32)>, // tiles it stood on before, newest last (not saved)
+}
+
+impl History {
+    pub fn new(x: i32, y: i32) -> Self {
+        History { last_x: x, last_y: y, trail: VecDeque::new() }
+    }
+
+    // Leaving (x, y): remember it, keeping only the newest `keep` tiles
+    pub fn record(&mut self, x: i32, y: i32, keep: usize) {
+        self.last_x = x;
+        self.last_y = y;
+        self.trail.push_back((x, y));
+        while self.trail.len() > keep {
+            self.trail.pop_front();
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Digesting; // State 1: Immobile, waiting for hunger > 0
+
+// A starving hunter: no thought of mates, smells prey beyond sight and takes
+// on prey it would normally leave alone (see desperation_system)
+#[derive(Component)]
+pub struct Desperate;
+
+// Hurt by risky prey: moves slower until the timer runs out
+#[derive(Component)]
+pub struct Injured(pub Timer);
+
+#[derive(Component)]
+pub struct Overfed(pub Timer); // State 2: Slow movement for 5 ticks
+
+#[derive(Component)]
+pub struct Satiated(pub Timer); // After digesting: prey is ignored until it runs out
+
+// Intelligence::Memory only: a spot plus the seconds until it's forgotten
+#[derive(Component, Default)]
+pub struct Memory {
+    pub food: Option<((i32, i32), f32)>,   // where it last ate (or killed)
+    pub danger: Option<((i32, i32), f32)>, // where it saw one of its own killed
+}
+
+// Tiles a sheep grazed bare, kept clear of until their fertility recovers
+#[derive(Component, Default)]
+pub struct GrazingMemory(pub Vec<(i32, i32)>);
+
+// Heard (or gave) an alarm call: Flee rules for `threat_species` run from `threat`
+// even when it's out of sight
+#[derive(Component)]
+pub struct Alarmed {
+    pub threat: (i32, i32),
+    pub threat_species: u32,
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct Swimming; // In deep water (swimmers only): slow + hungry
+
+#[derive(Component)]
+pub struct Wading; // In shallow water: slow, but safe for everyone
+
+#[derive(Component)]
+pub struct Fishing(pub Timer); // Standing on the shore until the attempt is over
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MoodKind {
+    #[default]
+    Content,
+    Stressed,
+    Grieving,
+}
+
+impl MoodKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MoodKind::Content => "Content",
+            MoodKind::Stressed => "Stressed",
+            MoodKind::Grieving => "Grieving",
+        }
+    }
+}
+
+// Not saved: loaded creatures start out content
+#[derive(Component, Default)]
+pub struct Mood {
+    pub kind: MoodKind,
+    pub left: f32, // seconds until it settles back to content
+}
+
+// Parents of everything born this game, for grief. Not saved either.
+#[derive(Resource, Default)]
+pub struct Lineage(pub HashMap<Entity, (Entity, Entity)>);
+
+#[derive(Component)]
+pub struct Wool(pub f32); // Sheep only: 0 = just shorn, 1 = full fleece
+
+#[derive(Component)]
+pub struct Parasites {
+    pub count: u32,    // ticks attached; each one adds to hunger burn
+    pub groomed: f32,  // seconds spent resting towards shedding the next one
+}
+
+//#[derive(Component)]
+//struct WolfPart;
+
+#[derive(Default, Resource)]
+pub struct PopulationStats {
+    // species_id -> counters
+    pub species: HashMap<u32, SpeciesCounters>,
+}
+
+#[derive(Default, Clone, Copy, Serialize, Deserialize)]
+pub struct SpeciesCounters {
+    pub born: u32,        // born via reproduction
+    pub total_ever: u32,  // total spawned ever (initial + births)
+    pub extinctions: u32, // times the species hit zero
+}
+
+// --- PHEROMONES ---
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Pheromone {
+    Trail,     // foragers' paths
+    Territory, // marks that others keep away from
+}
+
+impl Pheromone {
+    pub const ALL: [Pheromone; 2] = [Pheromone::Trail, Pheromone::Territory];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Pheromone::Trail => "Trail",
+            Pheromone::Territory => "Territory",
+        }
+    }
+}
+
+/// Scent on the ground, per tile and channel. Fades quickly, so it isn't saved.
+#[derive(Resource, Default)]
+pub struct PheromoneField(pub HashMap<(i32, i32), [f32; Pheromone::ALL.len()]>);
+
+impl PheromoneField {
+    pub fn get(&self, pos: (i32, i32), channel: Pheromone) -> f32 {
+        self.0.get(&pos).map(|levels| levels[channel as usize]).unwrap_or(0.0)
+    }
+
+    pub fn deposit(&mut self, pos: (i32, i32), channel: Pheromone, amount: f32, max: f32) {
+        let level = &mut self.0.entry(pos).or_default()[channel as usize];
+        *level = (*level + amount).min(max);
+    }
+}
+
+// --- PLAYER RESOURCES ---
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum ResourceKind {
+    Wool,
+    Meat,
+    Seeds,
+}
+
+impl ResourceKind {
+    pub const ALL: [ResourceKind; 3] = [ResourceKind::Wool, ResourceKind::Meat, ResourceKind::Seeds];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResourceKind::Wool => "Wool",
+            ResourceKind::Meat => "Meat",
+            ResourceKind::Seeds => "Seeds",
+        }
+    }
+}
+
+/// What the player has gathered this game
+#[derive(Resource, Default, Clone)]
+pub struct PlayerResources(pub HashMap<ResourceKind, u32>);
+
+impl PlayerResources {
+    pub fn get(&self, kind: ResourceKind) -> u32 {
+        self.0.get(&kind).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, kind: ResourceKind, amount: u32) {
+        *self.0.entry(kind).or_default() += amount;
+    }
+
+    /// Takes `amount` if there's that much, otherwise leaves the pool alone
+    pub fn spend(&mut self, kind: ResourceKind, amount: u32) -> bool {
+        let have = self.0.entry(kind).or_default();
+        if *have < amount { return false; }
+        *have -= amount;
+        true
+    }
+}
+
+// --- SIMULATION EVENTS ---
+// Core systems only report what happened; logging, stats, notifications and FX
+// all listen for these instead.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum DeathCause {
+    Starvation,
+    Drowning,
+    Predation,
+    Lightning,
+    Butchered,
+    OldAge,
+    Smitten,
+    Removed, // plants, crops, markers and rotted corpses: no DeathEvent
+}
+
+impl DeathCause {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeathCause::Starvation => "Starved",
+            DeathCause::Drowning => "Drowned",
+            DeathCause::Predation => "Eaten",
+            DeathCause::Lightning => "Struck by lightning",
+            DeathCause::Butchered => "Butchered",
+            DeathCause::OldAge => "Died of old age",
+            DeathCause::Smitten => "Smitten",
+            DeathCause::Removed => "Removed",
+        }
+    }
+
+    // Eaten, butchered or washed away creatures leave nothing to find
+    pub fn leaves_corpse(&self) -> bool {
+        matches!(self, DeathCause::Starvation | DeathCause::Lightning | DeathCause::OldAge | DeathCause::Smitten)
+    }
+
+    pub fn bleeds(&self) -> bool {
+        matches!(self, DeathCause::Predation | DeathCause::Butchered)
+    }
+}
+
+#[derive(Message, Clone)]
+pub struct BirthEvent {
+    pub entity: Entity,
+    pub parents: (Entity, Entity),
+    pub species_id: u32,
+    pub name: String,
+    pub generation: u32,
+    pub dna: Dna,
+    pub pos: (i32, i32),
+}
+
+#[derive(Message, Clone)]
+pub struct DeathEvent {
+    pub entity: Entity,
+    pub species_id: u32,
+    pub name: String,
+    pub generation: u32,
+    pub dna: Dna,
+    pub pos: (i32, i32),
+    pub cause: DeathCause,
+}
+
+/// A creature spotted something it flees from and called out to its own kind
+#[derive(Message, Clone, Copy)]
+pub struct AlarmEvent {
+    pub caller: Entity,
+    pub species_id: u32,
+    pub pos: (i32, i32),
+    pub threat: (i32, i32),
+    pub threat_species: u32,
+}
+
+/// An altruistic adult took in an orphaned baby of its kind
+#[derive(Message, Clone)]
+pub struct AdoptionEvent {
+    pub baby: Entity,
+    pub foster: Entity,
+    pub species_id: u32,
+    pub name: String,
+    pub foster_name: String,
+    pub generation: u32,
+    pub dna: Dna,
+}
+
+/// A creature ate a plant
+#[derive(Message, Clone, Copy)]
+pub struct EatEvent {
+    pub entity: Entity,
+    pub species_id: u32,
+    pub pos: (i32, i32),
+}
+
+#[derive(Message, Clone, Copy)]
+pub struct KillEvent {
+    pub predator: Entity,
+    pub prey: Entity,
+    pub pos: (i32, i32),
+}
+
+#[derive(Message, Clone, Copy)]
+pub struct WeatherChanged {
+    pub kind: WeatherKind,
+}
+
+/// The world was wiped for a new game or a load. Frontends drop whatever
+/// they keep per world (effects, caches) when they see it.
+#[derive(Message, Clone, Copy)]
+pub struct WorldCleared;
+
+/// A species just lost its last living member
+#[derive(Message)]
+pub struct ExtinctionEvent {
+    pub species_id: u32,
+    pub day: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct ExtinctionWatch {
+    pub alive: HashSet<u32>,
+    pub restock_in: HashMap<u32, f32>, // species_id -> seconds until founders return
+}
+
+// Short-lived messages shown at the top of the screen
+#[derive(Resource, Default)]
+pub struct Notifications {
+    pub active: VecDeque<(String, f32)>, // text, seconds left
+}
+
+impl Notifications {
+    pub fn push(&mut self, text: String, cfg: &SimulationConfig) {
+        self.active.push_back((text, cfg.notification_seconds));
+        while self.active.len() > 3 {
+            self.active.pop_front();
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct BerryStun(pub Timer); // short immobile state after eating berries
+
+// ========================
+// PLUGIN
+// ========================
+/// The simulation rules: world generation, creatures, weather, scenarios,
+/// saves. Nothing is drawn; frontends attach their own visuals to the
+/// entities this spawns and read the events and resources it keeps up to date.
+#[derive(Default)]
+pub struct SimulationPlugin {
+    /// Preset id from `assets/presets/` to start with
+    pub preset: Option<String>,
+    /// Challenge from `assets/scenarios/` to start with (overrides `preset`)
+    pub scenario: Option<String>,
+    /// Map file from [`MAP_DIR`] to start the first game on
+    pub map: Option<String>,
+    /// Seed for every new game; `None` picks a fresh one each time
+    pub seed: Option<u64>,
+    /// Use this config for every new game instead of reading the config,
+    /// preset and scenario files
+    pub config: Option<SimulationConfig>,
+    /// No file output (CSV log, saves) and no config hot reload.
+    /// See [`headless_app`].
+    pub headless: bool,
+}
+
+/// Update ordering for frontends and embedders: input that changes the world
+/// goes in `Simulate`, anything that only reads it (rendering, UI, observers)
+/// in `Observe`, which sees this frame's events and summary.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimulationSet {
+    Simulate,
+    Summarize,
+    Observe,
+}
+
+/// Milliseconds each simulation system took on its latest run, by system
+/// name, for a frontend's profiler. Every system `SimulationPlugin` adds is
+/// timed; [`SystemTimings::take`] collects the runs since the last call.
+#[derive(Resource, Clone, Default)]
+pub struct SystemTimings(Arc<Mutex<HashMap<&'static str, f64>>>);
+
+impl SystemTimings {
+    /// `system`, recording how long each run takes
+    pub fn timed<M, S: IntoSystem<(), (), M>>(&self, system: S) -> AdapterSystem<Timed, S::System> {
+        let name = std::any::type_name::<S>().rsplit("::").next().unwrap_or("system");
+        let system = IntoSystem::into_system(system);
+        let debug_name = system.name();
+        AdapterSystem::new(Timed { name, timings: self.clone() }, system, debug_name)
+    }
+
+    pub fn take(&self) -> HashMap<&'static str, f64> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+/// See [`SystemTimings::timed`]
+pub struct Timed {
+    name: &'static str,
+    timings: SystemTimings,
+}
+
+impl<S: System<In = ()>> Adapt<S> for Timed {
+    type In = ();
+    type Out = S::Out;
+
+    fn adapt(
+        &mut self,
+        input: (),
+        run_system: impl FnOnce(()) -> Result<S::Out, RunSystemError>,
+    ) -> Result<S::Out, RunSystemError> {
+        let start = Instant::now();
+        let result = run_system(input);
+        self.timings.0.lock().unwrap().insert(self.name, start.elapsed().as_secs_f64() * 1000.0);
+        result
+    }
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        let preset = self.preset.clone();
+        let scenario = self.scenario.clone();
+        let map = self.map.clone();
+        let t = SystemTimings::default();
+
+        if let Some(config) = &self.config {
+            app.insert_resource(FixedConfig(config.clone()));
+        }
+        if self.headless {
+            app.insert_resource(Headless)
+                .insert_resource(SimulationLogger { file: None });
+        } else {
+            app.insert_resource(SimulationLogger::default());
+        }
+        app
+            .insert_resource(self.config.clone().unwrap_or_else(|| SimulationConfig::load(preset.as_deref(), None)))
+            .insert_resource(SimRng::new(self.seed))
+            .add_systems(First, advance_sim_rng)
+            .insert_resource(NewGameRequest { preset, scenario, map, code: None })
+            .init_resource::<ActivePreset>()
+            .init_resource::<ActiveScenario>()
+            .init_state::<GameMode>()
+            .add_systems(OnEnter(GameMode::Observer), enter_observer_mode)
+            .add_systems(OnEnter(GameMode::Challenge), enter_challenge_mode)
+            .init_resource::<BalanceGovernor>()
+            .init_resource::<ExtinctionWatch>()
+            .init_resource::<Notifications>()
+            .add_message::<ExtinctionEvent>()
+            .add_message::<BirthEvent>()
+            .add_message::<DeathEvent>()
+            .add_message::<EatEvent>()
+            .add_message::<AlarmEvent>()
+            .add_message::<AdoptionEvent>()
+            .add_message::<SpeciationEvent>()
+            .add_message::<KillEvent>()
+            .add_message::<WeatherChanged>()
+            .add_message::<WorldCleared>()
+            .add_message::<TickSummary>()
+            .add_message::<DayReport>()
+            .init_resource::<SimulationHooks>()
+            .init_resource::<TickCounter>()
+            .configure_sets(Update, (SimulationSet::Simulate, SimulationSet::Summarize, SimulationSet::Observe).chain())
+            .insert_resource(t.clone())
+            .init_resource::<FileWatcher>()
+            .insert_resource(PopulationStats::default())
+            .insert_resource(GameStats { days: 0.0 })
+            .init_resource::<PlayerResources>()
+            .init_resource::<Lineage>()
+            .init_resource::<PheromoneField>()
+            .init_resource::<GeneticsHistory>()
+            .init_resource::<Biographies>()
+            .init_resource::<WorldRecords>()
+            .init_resource::<WorldTimeline>()
+            .init_resource::<DayLedger>()
+            .init_resource::<SeasonalStats>()
+            .init_resource::<Genealogy>()
+            .init_resource::<SpeciesRegistry>()
+            .init_resource::<FitnessLedger>()
+            .init_resource::<Weather>()
+            .init_resource::<TileIndex>()
+            .init_resource::<WaterDistanceField>()
+            .init_resource::<MoveClock>()
+
+            // The world itself is generated on the first frame (and on every new game)
+            .add_systems(Startup, load_behaviors)
+            .add_systems(Update, (start_new_game, spawn_map, spawn_trees, finish_new_game)
+                .chain()
+                .run_if(resource_exists::<NewGameRequest>))
+            // Last thing in the frame, so the loaded world's first step is the next frame's
+            .add_systems(Update, apply_load_request.run_if(resource_exists::<LoadRequest>).after(SimulationSet::Observe))
+
+            .add_systems(Update, (
+                t.timed(hot_reload_system).run_if(on_disk.and(not(resource_exists::<FixedConfig>))),
+                t.timed(balance_governor_system).run_if(not(in_state(GameMode::Challenge))),
+
+                // World
+                (
+                    t.timed(advance_clock),
+                    // Birds go for where the hosts ended up
+                    (
+                        t.timed(advance_move_clock),
+                        t.timed(plan_creature_moves),
+                        t.timed(apply_creature_moves),
+                        t.timed(pheromone_deposit_system),
+                        t.timed(shelter_occupancy_system),
+                        t.timed(insect_system),
+                        t.timed(bird_system),
+                    ).chain(),
+                    t.timed(trampling_system),
+                    (t.timed(water_distance_system), t.timed(plant_growth_system)).chain(),
+                    (t.timed(grass_growth_system), t.timed(plant_seeding_system)),
+                    t.timed(crop_growth_system),
+                    (t.timed(weather_system), t.timed(hydrology_system), t.timed(water_pollution_system)).chain(),
+                    (t.timed(bird_flock_system), t.timed(insect_hatch_system)),
+                    t.timed(pheromone_decay_system),
+                    t.timed(handle_drowning),
+                    (t.timed(corpse_decay_system), t.timed(reaper_system)).chain(),
+                    (t.timed(handle_exhaustion), t.timed(cap_soil_markers)).chain(),
+                ),
+
+                // Creature life
+                (
+                    t.timed(creature_state_update),
+                    t.timed(creature_eating),
+                    t.timed(grass_grazing_system),
+                    t.timed(fishing_system),
+                    t.timed(predator_hunting_system),
+                    t.timed(creature_reproduction),
+                    t.timed(lightning_system),
+                    t.timed(parasite_system),
+                    t.timed(drinking_system),
+                    (t.timed(wool_growth_system), t.timed(shepherd_shearing_system)).chain(),
+                    (t.timed(alarm_call_system), t.timed(alarm_response_system)).chain(),
+                    (t.timed(memory_system), t.timed(grazing_memory_system)),
+                    t.timed(desperation_system),
+                    (t.timed(adoption_system), t.timed(foster_feeding_system)),
+                    (t.timed(speciation_system), t.timed(record_speciations)).chain(),
+                    // Not while a new world is being generated, or the empty frame reads as extinction
+                    (
+                        t.timed(detect_extinctions),
+                        t.timed(record_extinctions),
+                        t.timed(evaluate_scenario),
+                        t.timed(restock_extinct_species),
+                    )
+                        .chain()
+                        .run_if(not(resource_exists::<NewGameRequest>).and(not(resource_exists::<LoadRequest>))),
+                ),
+
+                // Event listeners
+                (
+                    t.timed(log_simulation_events),
+                    t.timed(biography_system),
+                    t.timed(world_records_system),
+                    t.timed(timeline_system),
+                    (t.timed(day_report_system), t.timed(seasonal_stats_system)).chain(),
+                    t.timed(genealogy_system),
+                    t.timed(count_births),
+                    t.timed(weather_notifications),
+                    t.timed(mood_system),
+                    t.timed(genetics_sampling_system),
+                    (t.timed(fitness_tracking_system), t.timed(fitness_report_system))
+                        .chain()
+                        .run_if(in_state(GameMode::Experiment)),
+                ),
+            ).in_set(SimulationSet::Simulate))
+
+            .add_systems(Update, emit_tick_summary.in_set(SimulationSet::Summarize))
+            .add_systems(Update, run_simulation_hooks.in_set(SimulationSet::Observe));
+    }
+}
+
+// ========================
+// HOOKS
+// ========================
+/// One frame of the simulation at a glance, sent every Update
+#[derive(Message, Clone, Debug)]
+pub struct TickSummary {
+    pub tick: u64,
+    pub day: f32,
+    pub population: HashMap<u32, u32>, // species_id -> living
+    pub plants: u32,
+    pub weather: WeatherKind,
+    pub births: u32,
+    pub deaths: u32,
+}
+
+#[derive(Resource, Default)]
+pub struct TickCounter(pub u64);
+
+pub type Hook<T> = Box<dyn FnMut(&T) + Send + Sync>;
+
+// Callbacks registered through LivingWorldAppExt
+#[derive(Resource, Default)]
+pub struct SimulationHooks {
+    pub birth: Vec<Hook<BirthEvent>>,
+    pub death: Vec<Hook<DeathEvent>>,
+    pub eat: Vec<Hook<EatEvent>>,
+    pub kill: Vec<Hook<KillEvent>>,
+    pub weather: Vec<Hook<WeatherChanged>>,
+    pub extinction: Vec<Hook<ExtinctionEvent>>,
+    pub tick: Vec<Hook<TickSummary>>,
+}
+
+/// Subscribe plain callbacks to simulation events. They run once per event,
+/// after the simulation has finished the frame.
+pub trait LivingWorldAppExt {
+    fn on_birth(&mut self, f: impl FnMut(&BirthEvent) + Send + Sync + 'static) -> &mut Self;
+    fn on_death(&mut self, f: impl FnMut(&DeathEvent) + Send + Sync + 'static) -> &mut Self;
+    fn on_eat(&mut self, f: impl FnMut(&EatEvent) + Send + Sync + 'static) -> &mut Self;
+    fn on_kill(&mut self, f: impl FnMut(&KillEvent) + Send + Sync + 'static) -> &mut Self;
+    fn on_weather_changed(&mut self, f: impl FnMut(&WeatherChanged) + Send + Sync + 'static) -> &mut Self;
+    fn on_extinction(&mut self, f: impl FnMut(&ExtinctionEvent) + Send + Sync + 'static) -> &mut Self;
+    fn on_tick(&mut self, f: impl FnMut(&TickSummary) + Send + Sync + 'static) -> &mut Self;
+}
+
+impl LivingWorldAppExt for App {
+    fn on_birth(&mut self, f: impl FnMut(&BirthEvent) + Send + Sync + 'static) -> &mut Self {
+        self.world_mut().get_resource_or_init::<SimulationHooks>().birth.push(Box::new(f));
+        self
+    }
+
+    fn on_death(&mut self, f: impl FnMut(&DeathEvent) + Send + Sync + 'static) -> &mut Self {
+        self.world_mut().get_resource_or_init::<SimulationHooks>().death.push(Box::new(f));
+        self
+    }
+
+    fn on_eat(&mut self, f: impl FnMut(&EatEvent) + Send + Sync + 'static) -> &mut Self {
+        self.world_mut().get_resource_or_init::<SimulationHooks>().eat.push(Box::new(f));
+        self
+    }
+
+    fn on_kill(&mut self, f: impl FnMut(&KillEvent) + Send + Sync + 'static) -> &mut Self {
+        self.world_mut().get_resource_or_init::<SimulationHooks>().kill.push(Box::new(f));
+        self
+    }
+
+    fn on_weather_changed(&mut self, f: impl FnMut(&WeatherChanged) + Send + Sync + 'static) -> &mut Self {
+        self.world_mut().get_resource_or_init::<SimulationHooks>().weather.push(Box::new(f));
+        self
+    }
+
+    fn on_extinction(&mut self, f: impl FnMut(&ExtinctionEvent) + Send + Sync + 'static) -> &mut Self {
+        self.world_mut().get_resource_or_init::<SimulationHooks>().extinction.push(Box::new(f));
+        self
+    }
+
+    fn on_tick(&mut self, f: impl FnMut(&TickSummary) + Send + Sync + 'static) -> &mut Self {
+        self.world_mut().get_resource_or_init::<SimulationHooks>().tick.push(Box::new(f));
+        self
+    }
+}
+
+pub fn emit_tick_summary(
+    mut counter: ResMut<TickCounter>,
+    game_stats: Res<GameStats>,
+    weather: Res<Weather>,
+    q_creatures: Query<&CreatureStats, (With<Creature>, Without<Dead>)>,
+    q_plants: Query<(), (With<Plant>, Without<Dead>)>,
+    mut births: MessageReader<BirthEvent>,
+    mut deaths: MessageReader<DeathEvent>,
+    mut summaries: MessageWriter<TickSummary>,
+) {
+    counter.0 += 1;
+
+    let mut population: HashMap<u32, u32> = HashMap::new();
+    for stats in q_creatures.iter() {
+        *population.entry(stats.species_id).or_default() += 1;
+    }
+
+    summaries.write(TickSummary {
+        tick: counter.0,
+        day: game_stats.days,
+        population,
+        plants: q_plants.iter().count() as u32,
+        weather: weather.kind,
+        births: births.read().count() as u32,
+        deaths: deaths.read().count() as u32,
+    });
+}
+
+pub fn run_simulation_hooks(
+    mut hooks: ResMut<SimulationHooks>,
+    mut births: MessageReader<BirthEvent>,
+    mut deaths: MessageReader<DeathEvent>,
+    mut eats: MessageReader<EatEvent>,
+    mut kills: MessageReader<KillEvent>,
+    mut weather: MessageReader<WeatherChanged>,
+    mut extinctions: MessageReader<ExtinctionEvent>,
+    mut summaries: MessageReader<TickSummary>,
+) {
+    let hooks = &mut *hooks;
+    for e in births.read() { for f in hooks.birth.iter_mut() { f(e); } }
+    for e in deaths.read() { for f in hooks.death.iter_mut() { f(e); } }
+    for e in eats.read() { for f in hooks.eat.iter_mut() { f(e); } }
+    for e in kills.read() { for f in hooks.kill.iter_mut() { f(e); } }
+    for e in weather.read() { for f in hooks.weather.iter_mut() { f(e); } }
+    for e in extinctions.read() { for f in hooks.extinction.iter_mut() { f(e); } }
+    for e in summaries.read() { for f in hooks.tick.iter_mut() { f(e); } }
+}
+
+// ========================
+// SIMULATION API
+// ========================
+/// The simulation without a window or a main loop: build it, step it, look at
+/// it. Each step is one 60 Hz frame of the regular game.
+///
+/// ```no_run
+/// use living_world_core::{Simulation, SimulationConfig};
+///
+/// let config = SimulationConfig::from_overrides("(map_size: 12, tree_count: 5)").unwrap();
+/// let mut sim = Simulation::new(config, 42);
+/// sim.run(600);
+/// println!("day {:.1}: {:?}", sim.day(), sim.population());
+/// ```
+pub struct Simulation {
+    pub app: App,
+}
+
+/// One living creature, as returned by [`Simulation::creatures`]
+#[derive(Clone, Debug)]
+pub struct CreatureState {
+    pub entity: Entity,
+    pub species_id: u32,
+    pub pos: (i32, i32),
+    pub hunger: f32,
+    pub age_seconds: f32,
+    pub is_adult: bool,
+    pub generation: u32,
+    pub name: String,
+    pub dna: Dna,
+}
+
+impl Simulation {
+    /// Generates a fresh world from `config` and `seed`. No config, preset or
+    /// save files are read (behavior files are, with built-in fallbacks).
+    pub fn new(config: SimulationConfig, seed: u64) -> Self {
+        let mut app = headless_app_with(SimulationPlugin { seed: Some(seed), config: Some(config), headless: true, ..default() });
+        // The first frame runs startup and generates the world
+        app.update();
+        Self { app }
+    }
+
+    /// Picks up the world in `snapshot`, e.g. one taken in the app, and runs it
+    /// on from there
+    pub fn from_snapshot(snapshot: WorldSnapshot, seed: u64) -> Self {
+        let mut sim = Self::new(snapshot.config.clone(), seed);
+        sim.load(snapshot);
+        sim
+    }
+
+    /// A second Simulation that runs on in lockstep with this one (given the
+    /// same config). Both carry on from one snapshot, so what snapshots don't
+    /// keep (insects, moods, memories) starts over here too.
+    pub fn fork(&mut self) -> Simulation {
+        let snapshot = self.snapshot();
+        let seed = self.app.world().resource::<SimRng>().seed;
+        self.load(snapshot.clone());
+        Self::from_snapshot(snapshot, seed)
+    }
+
+    /// Replaces the world with `snapshot`, the way loading a save does. Takes
+    /// one frame.
+    pub fn load(&mut self, snapshot: WorldSnapshot) {
+        self.app.world_mut().insert_resource(LoadRequest { snapshot, label: "snapshot".to_string() });
+        self.app.update();
+    }
+
+    /// Advances one frame (1/60 s of simulated time)
+    pub fn step(&mut self) {
+        self.app.update();
+    }
+
+    /// Advances `ticks` frames
+    pub fn run(&mut self, ticks: u64) {
+        for _ in 0..ticks {
+            self.app.update();
+        }
+    }
+
+    pub fn config(&self) -> &SimulationConfig {
+        self.app.world().resource::<SimulationConfig>()
+    }
+
+    /// Frames stepped so far, counting the one that built the world
+    pub fn tick(&self) -> u64 {
+        self.app.world().resource::<TickCounter>().0
+    }
+
+    pub fn day(&self) -> f32 {
+        self.app.world().resource::<GameStats>().days
+    }
+
+    pub fn weather(&self) -> WeatherKind {
+        self.app.world().resource::<Weather>().kind
+    }
+
+    /// Living creatures per species id
+    pub fn population(&mut self) -> HashMap<u32, u32> {
+        let mut counts = HashMap::new();
+        let world = self.app.world_mut();
+        for stats in world.query_filtered::<&CreatureStats, (With<Creature>, Without<Dead>)>().iter(world) {
+            *counts.entry(stats.species_id).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Living creatures, in no particular order
+    pub fn creatures(&mut self) -> Vec<CreatureState> {
+        let world = self.app.world_mut();
+        world
+            .query_filtered::<(Entity, &CreatureStats, &GridPosition, &Hunger, &Age, &Generation, &CreatureName, &Dna), (With<Creature>, Without<Dead>)>()
+            .iter(world)
+            .map(|(entity, stats, pos, hunger, age, generation, name, dna)| CreatureState {
+                entity,
+                species_id: stats.species_id,
+                pos: (pos.x, pos.y),
+                hunger: hunger.0,
+                age_seconds: age.seconds_alive,
+                is_adult: age.is_adult,
+                generation: generation.0,
+                name: name.0.clone(),
+                dna: *dna,
+            })
+            .collect()
+    }
+
+    /// Grid positions of edible plants (ripe crops included)
+    pub fn plants(&mut self) -> Vec<(i32, i32)> {
+        let world = self.app.world_mut();
+        world
+            .query_filtered::<&GridPosition, (With<Plant>, Without<Dead>)>()
+            .iter(world)
+            .map(|p| (p.x, p.y))
+            .collect()
+    }
+
+    /// Everything a save would hold, as of now
+    pub fn snapshot(&mut self) -> WorldSnapshot {
+        let world = self.app.world_mut();
+        let mut state = bevy::ecs::system::SystemState::<SnapshotQueries>::new(world);
+        state.get(world).capture()
+    }
+
+    /// See [`world_state_hash`]
+    pub fn state_hash(&mut self) -> u64 {
+        world_state_hash(self.app.world_mut())
+    }
+
+    /// The ECS world underneath, for anything the methods above don't cover
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+}
+
+// ========================
+// DETERMINISM
+// ========================
+/// A windowless app running the simulation on a fixed 60 Hz clock, for tests
+/// and tools. It reads the config files but writes nothing to disk.
+pub fn headless_app(seed: u64) -> App {
+    headless_app_with(SimulationPlugin { seed: Some(seed), headless: true, ..default() })
+}
+
+pub fn headless_app_with(plugin: SimulationPlugin) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, bevy::state::app::StatesPlugin))
+        .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(std::time::Duration::from_secs_f64(1.0 / TICKS_PER_SECOND as f64)))
+        .add_plugins(plugin);
+    // One system at a time, in a fixed order: the simulation's systems aren't all
+    // ordered against each other, and threads racing would pick the order
+    app.edit_schedule(Update, |schedule| {
+        schedule.set_executor_kind(bevy::ecs::schedule::ExecutorKind::SingleThreaded);
+    });
+    app.finish();
+    app.cleanup();
+    app
+}
+
+/// Fingerprint of the simulation state: living creatures, plants, water, the
+/// clock, weather and population counters. Entity ids and query order don't
+/// count, so two worlds in the same state hash the same.
+pub fn world_state_hash(world: &mut World) -> u64 {
+    let mut creatures: Vec<_> = world
+        .query_filtered::<(&CreatureStats, &GridPosition, &Hunger, &Age, &Dna, &Generation, &CreatureName), (With<Creature>, Without<Dead>)>()
+        .iter(world)
+        .map(|(stats, pos, hunger, age, dna, generation, name)| {
+            (
+                stats.species_id,
+                (pos.x, pos.y),
+                hunger.0.to_bits(),
+                (age.seconds_alive.to_bits(), age.is_adult),
+                (dna.move_speed_seconds.to_bits(), dna.metabolism_rate.to_bits(), dna.sight_range),
+                generation.0,
+                name.0.clone(),
+            )
+        })
+        .collect();
+    creatures.sort();
+
+    let mut plants: Vec<(i32, i32)> = world
+        .query_filtered::<&GridPosition, (With<Plant>, Without<Dead>)>()
+        .iter(world)
+        .map(|p| (p.x, p.y))
+        .collect();
+    plants.sort();
+
+    let mut water: Vec<((i32, i32), u32)> = world
+        .query::<(&Tile, &Water)>()
+        .iter(world)
+        .map(|(t, w)| ((t.x, t.y), w.depth.to_bits()))
+        .collect();
+    water.sort();
+
+    let mut population: Vec<(u32, (u32, u32, u32))> = world
+        .resource::<PopulationStats>()
+        .species
+        .iter()
+        .map(|(&id, c)| (id, (c.born, c.total_ever, c.extinctions)))
+        .collect();
+    population.sort();
+
+    let mut hasher = std::hash::DefaultHasher::new();
+    creatures.hash(&mut hasher);
+    plants.hash(&mut hasher);
+    water.hash(&mut hasher);
+    population.hash(&mut hasher);
+    world.resource::<GameStats>().days.to_bits().hash(&mut hasher);
+    (world.resource::<Weather>().kind as u8).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Runs two headless worlds from the same `seed` in lockstep for `ticks`
+/// frames and compares [`world_state_hash`] every `every` frames. The error
+/// names the first check at which they differed.
+pub fn verify_determinism(seed: u64, ticks: u64, every: u64) -> Result<(), String> {
+    let mut a = headless_app(seed);
+    let mut b = headless_app(seed);
+    for tick in 1..=ticks {
+        a.update();
+        b.update();
+        if tick % every.max(1) != 0 && tick != ticks { continue; }
+
+        let (hash_a, hash_b) = (world_state_hash(a.world_mut()), world_state_hash(b.world_mut()));
+        if hash_a != hash_b {
+            return Err(format!("seed {}: worlds diverged by tick {} ({:016x} vs {:016x})", seed, tick, hash_a, hash_b));
+        }
+    }
+    Ok(())
+}
+
+// ========================
+// LOCKSTEP COMPARISON
+// ========================
+// Headless apps step 1/TICKS_PER_SECOND s per frame
+pub const TICKS_PER_SECOND: u32 = 60;
+pub const TICKS_PER_DAY: u64 = (SECONDS_PER_DAY * TICKS_PER_SECOND as f32) as u64;
+
+/// One world at a sim-day boundary. A recorded trace is a RON list of these,
+/// so runs of two different builds can be compared after the fact.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TraceRow {
+    pub tick: u64,
+    pub day: u32,
+    pub hash: u64,
+    pub population: Vec<(u32, u32)>, // (species_id, living), by species id
+    pub plants: u32,
+    pub weather: WeatherKind,
+}
+
+impl TraceRow {
+    pub fn capture(sim: &mut Simulation) -> Self {
+        let mut population: Vec<(u32, u32)> = sim.population().into_iter().collect();
+        population.sort_unstable();
+        Self {
+            tick: sim.tick(),
+            day: sim.day().round() as u32,
+            hash: sim.state_hash(),
+            population,
+            plants: sim.plants().len() as u32,
+            weather: sim.weather(),
+        }
+    }
+
+    pub fn living(&self, species_id: u32) -> u32 {
+        self.population.iter().find(|(id, _)| *id == species_id).map(|(_, n)| *n).unwrap_or(0)
+    }
+}
+
+/// Runs one world for `days` sim-days, one row per day
+pub fn record_trace(config: SimulationConfig, seed: u64, days: u32) -> Vec<TraceRow> {
+    let mut sim = Simulation::new(config, seed);
+    (0..days)
+        .map(|_| {
+            sim.run(TICKS_PER_DAY);
+            TraceRow::capture(&mut sim)
+        })
+        .collect()
+}
+
+/// Two worlds from the same seed, stepped frame by frame together so the only
+/// difference between them is their config
+pub struct Lockstep {
+    pub a: Simulation,
+    pub b: Simulation,
+    pub first_divergence: Option<u64>, // first tick their states differed
+}
+
+impl Lockstep {
+    pub fn new(a: SimulationConfig, b: SimulationConfig, seed: u64) -> Self {
+        let mut lockstep = Self { a: Simulation::new(a, seed), b: Simulation::new(b, seed), first_divergence: None };
+        lockstep.check();
+        lockstep
+    }
+
+    // Hashing every frame is slow, so it stops once they've split
+    fn check(&mut self) {
+        if self.first_divergence.is_none() && self.a.state_hash() != self.b.state_hash() {
+            self.first_divergence = Some(self.a.tick());
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.a.step();
+        self.b.step();
+        self.check();
+    }
+
+    /// Runs `days` sim-days; one row per world per day
+    pub fn run_days(&mut self, days: u32) -> (Vec<TraceRow>, Vec<TraceRow>) {
+        let mut rows = (Vec::new(), Vec::new());
+        for _ in 0..days {
+            for _ in 0..TICKS_PER_DAY {
+                self.step();
+            }
+            rows.0.push(TraceRow::capture(&mut self.a));
+            rows.1.push(TraceRow::capture(&mut self.b));
+        }
+        rows
+    }
+}
+
+/// Side-by-side table of two traces: per day, each species' count in A and B
+/// and the difference, the same for plants, and the weather
+pub fn diff_report(cfg: &SimulationConfig, a: &[TraceRow], b: &[TraceRow], first_divergence: Option<u64>) -> String {
+    let mut species: Vec<u32> = cfg.species.keys().copied().collect();
+    species.sort_unstable();
+
+    let mut out = format!("{:>5}", "Day");
+    for id in &species {
+        out.push_str(&format!(" | {:<8}{:>5}{:>5}{:>6}", cfg.s(*id).name, "A", "B", "diff"));
+    }
+    out.push_str(&format!(" | {:<8}{:>5}{:>5}{:>6} | Weather A / B\n", "Plants", "A", "B", "diff"));
+
+    let column = |x: u32, y: u32| format!("{:<8}{:>5}{:>5}{:>+6}", "", x, y, y as i64 - x as i64);
+    for (ra, rb) in a.iter().zip(b) {
+        out.push_str(&format!("{:>5}", ra.day));
+        for id in &species {
+            out.push_str(&format!(" | {}", column(ra.living(*id), rb.living(*id))));
+        }
+        out.push_str(&format!(" | {} | {:?} / {:?}\n", column(ra.plants, rb.plants), ra.weather, rb.weather));
+    }
+
+    // Recorded traces only know the state once a day
+    let split = match first_divergence {
+        Some(tick) => Some(format!("tick {}", tick)),
+        None => a.iter().zip(b).find(|(ra, rb)| ra.hash != rb.hash).map(|(ra, _)| format!("day {}", ra.day)),
+    };
+    match split {
+        Some(when) => out.push_str(&format!("Worlds diverged by {}\n", when)),
+        None => out.push_str("Worlds stayed identical\n"),
+    }
+    if a.len() != b.len() {
+        out.push_str(&format!("Traces differ in length: {} vs {} days\n", a.len(), b.len()));
+    }
+    out
+}
+
+pub fn spawn_map(
+    mut commands: Commands,
+    mut pop: ResMut<PopulationStats>,
+    cfg: Res<SimulationConfig>,
+    mut logger: ResMut<SimulationLogger>,
+    mut tile_index: ResMut<TileIndex>,
+    sim_rng: Res<SimRng>,
+    blueprint: Option<Res<MapBlueprint>>,
+) {
+    let mut rng = sim_rng.stream("map");
+    let map_size = cfg.map_size;
+
+    // Starting spots, decided up front so lakes can keep clear of them
+    let sheep_cfg = cfg.s(0);
+    let wolf_cfg = cfg.s(1);
+    let mut wolf_coords = vec![(-6, -6), (-4, -6), (4, -6), (6, -6), (0, -8), (0, -4)]; // Added spots for 6 wolves
+    // Bigger packs (presets) get extra random spots south of the flock
+    while wolf_coords.len() < wolf_cfg.starting_count as usize {
+        let wx = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+        let wy = -2 - (rng.random::<i32>().abs() % (map_size - 2).max(1));
+        wolf_coords.push((wx, wy));
+    }
+    // Small maps: fixed spots past the edge fold back onto it
+    let in_map = |(x, y): (i32, i32)| (x.clamp(-map_size, map_size - 1), y.clamp(-map_size, map_size - 1));
+    let on_land = |pos: (i32, i32)| blueprint.as_ref().map_or(pos, |b| b.dry_spot(pos, map_size));
+    let sheep_coords: Vec<(i32, i32)> = (0..sheep_cfg.starting_count as i32).map(|i| on_land(in_map((i, i)))).collect();
+    let wolf_coords: Vec<(i32, i32)> = wolf_coords.into_iter().map(|pos| on_land(in_map(pos))).collect();
+    // A map file's spawn regions scatter founders inside them instead
+    let mut spawn_in = |species_id: u32, usual: Vec<(i32, i32)>| -> Vec<(i32, i32)> {
+        let region = blueprint.as_ref().and_then(|b| b.spawns.iter().find(|r| r.species_id == species_id));
+        match region {
+            Some(region) => usual.iter().map(|_| on_land(in_map(region.pick(&mut rng)))).collect(),
+            None => usual,
+        }
+    };
+    let sheep_coords = spawn_in(0, sheep_coords);
+    let wolf_coords = spawn_in(1, wolf_coords);
+
+    let mut keep_dry: HashSet<(i32, i32)> = HashSet::new();
+    let starts = sheep_coords
+        .iter()
+        .copied()
+        .chain(wolf_coords.iter().copied().take(wolf_cfg.starting_count as usize));
+    for (sx, sy) in starts {
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                keep_dry.insert((sx + dx, sy + dy));
+            }
+        }
+    }
+
+    // Lakes: depth falls off from each centre (a map blueprint has its own)
+    let mut lake_depth: HashMap<(i32, i32), f32> =
+        blueprint.as_ref().map(|b| b.water.iter().copied().collect()).unwrap_or_default();
+    let dirt: HashSet<(i32, i32)> = blueprint.as_ref().map(|b| b.dirt.iter().copied().collect()).unwrap_or_default();
+    let bridges: HashSet<(i32, i32)> = blueprint.as_ref().map(|b| b.bridges.iter().copied().collect()).unwrap_or_default();
+    let r = cfg.lake_radius.max(1);
+    let lake_count = if blueprint.is_some() { 0 } else { cfg.lake_count };
+    for _ in 0..lake_count {
+        let cx = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+        let cy = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+        for dx in -r..=r {
+            for dy in -r..=r {
+                let d = ((dx * dx + dy * dy) as f32).sqrt();
+                if d > r as f32 || keep_dry.contains(&(cx + dx, cy + dy)) { continue; }
+                let depth = cfg.max_water_depth * (1.0 - d / (r as f32 + 1.0));
+                let entry = lake_depth.entry((cx + dx, cy + dy)).or_insert(0.0);
+                *entry = entry.max(depth);
+            }
+        }
+    }
+
+    // Ground
+    for x in -map_size..map_size {
+        for y in -map_size..map_size {
+            let tile = commands.spawn((
+                Tile { x, y },
+                Footfall::default(),
+                Fertility::default(),
+                Grass::default(),
+            )).id();
+            if let Some(&depth) = lake_depth.get(&(x, y)) {
+                commands.entity(tile).insert(Water { depth });
+                if bridges.contains(&(x, y)) { commands.entity(tile).insert(Bridge); }
+            }
+            // Blueprint dirt starts as worn as trampling would leave it
+            if dirt.contains(&(x, y)) {
+                commands.entity(tile).insert(Footfall { wear: cfg.dirt_wear_threshold, dirt: true });
+            }
+            tile_index.0.insert((x, y), tile);
+        }
+    }
+
+    // A blueprint's plants and fixtures (trees are spawn_trees' job)
+    if let Some(b) = &blueprint {
+        for &(x, y) in &b.plants {
+            commands.spawn((Plant, GridPosition { x, y }));
+        }
+        for &(x, y) in &b.fences {
+            commands.spawn((Fence, GridPosition { x, y }));
+        }
+        for &((x, y), allowed) in &b.gates {
+            commands.spawn((Gate { allowed }, GridPosition { x, y }));
+        }
+        for &(x, y) in &b.shelters {
+            commands.spawn((Shelter::default(), GridPosition { x, y }));
+        }
+    }
+
+    // Starting sheep and wolves (start as babies)
+    for &pos in &sheep_coords {
+        spawn_founder(&mut commands, &cfg, &mut pop, &mut logger, &mut rng, 0, pos, 0.0);
+    }
+
+    // --- UPDATED WOLF SPAWNING ---
+    for &pos in wolf_coords.iter().take(wolf_cfg.starting_count as usize) {
+        spawn_founder(&mut commands, &cfg, &mut pop, &mut logger, &mut rng, 1, pos, 0.0);
+    }
+}
+
+// Gen 0 creature with base genetics (counts towards born + total_ever)
+pub fn spawn_founder(
+    commands: &mut Commands,
+    cfg: &SimulationConfig,
+    pop: &mut PopulationStats,
+    logger: &mut SimulationLogger,
+    rng: &mut impl Rng,
+    species_id: u32,
+    (x, y): (i32, i32),
+    day: f32,
+) {
+    let entry = pop.species.entry(species_id).or_default();
+    entry.born += 1;
+    entry.total_ever += 1;
+    let nth = entry.born as usize;
+
+    let is_wolf = species_id == 1;
+    let mut dna = Dna {
+        move_speed_seconds: cfg.base_move_seconds,
+        // Start with baby burn
+        metabolism_rate: if is_wolf { cfg.wolf_hunger_burn_baby } else { cfg.sheep_hunger_burn_baby },
+        sight_range: cfg.s(species_id).sight_range,
+        size_multiplier: if is_wolf { 1.1 } else { 1.0 }, // Wolves slightly bigger
+    };
+    // Genomes carried over from an earlier experiment, in turn
+    let seeds: Vec<&SeedGenome> = cfg.seed_genomes.iter().filter(|g| g.species_id == species_id).collect();
+    if !seeds.is_empty() {
+        dna = seeds[nth % seeds.len()].dna;
+    }
+    let name = generate_name(rng);
+
+    logger.log("Spawn", day, if is_wolf { "Wolf" } else { "Sheep" }, &name, 0, &dna);
+
+    commands.spawn((
+        Creature,
+        GridPosition { x, y },
+        NextMove::default(),
+        Hunger(0.0),
+        CreatureStats { sight_range: dna.sight_range, species_id },
+        CreatureBehavior { scared_of_water: true, altruistic: !is_wolf },
+        Age { seconds_alive: 0.0, is_adult: false, is_elder: false },
+        History::new(x, y),
+        dna,
+        Generation(0),
+        Founder(name.clone()),
+        StreamKey::new(&name, (x, y)),
+        CreatureName(name),
+    ));
+}
+
+// Everything that makes up one world, so new games and loads can start clean
+#[derive(SystemParam)]
+pub struct WorldReset<'w, 's> {
+    pub pop: ResMut<'w, PopulationStats>,
+    pub stats: ResMut<'w, GameStats>,
+    pub tile_index: ResMut<'w, TileIndex>,
+    pub water_field: ResMut<'w, WaterDistanceField>,
+    pub weather: ResMut<'w, Weather>,
+    pub extinction: ResMut<'w, ExtinctionWatch>,
+    pub move_clock: ResMut<'w, MoveClock>,
+    pub resources: ResMut<'w, PlayerResources>,
+    pub pheromones: ResMut<'w, PheromoneField>,
+    pub species_registry: ResMut<'w, SpeciesRegistry>,
+    pub ledgers: WorldLedgers<'w>,
+    pub messages: WorldMessages<'w>,
+    pub cleared: MessageWriter<'w, WorldCleared>,
+    pub q_world: Query<'w, 's, Entity, Or<(
+        With<Tile>,
+        With<Creature>,
+        With<Plant>,
+        With<Crop>,
+        With<Tree>,
+        With<Fence>,
+        With<Gate>,
+        With<Shelter>,
+        With<ExhaustedSoil>,
+        With<Shepherd>,
+        With<Bird>,
+        With<Insect>,
+        With<Corpse>,
+    )>>,
+}
+
+impl WorldReset<'_, '_> {
+    pub fn clear(&mut self, commands: &mut Commands) {
+        for entity in self.q_world.iter() {
+            commands.entity(entity).despawn();
+        }
+        *self.pop = PopulationStats::default();
+        self.stats.days = 0.0;
+        self.tile_index.0.clear();
+        *self.water_field = WaterDistanceField::default();
+        *self.weather = Weather::default();
+        *self.extinction = ExtinctionWatch::default();
+        *self.move_clock = MoveClock::default();
+        *self.resources = PlayerResources::default();
+        self.pheromones.0.clear();
+        *self.species_registry = SpeciesRegistry::default();
+        self.ledgers.clear();
+        self.messages.clear();
+        self.cleared.write(WorldCleared);
+    }
+}
+
+// Whatever happened to the old world's creatures this frame, unread (part of
+// WorldReset): listeners would count it against the new world
+#[derive(SystemParam)]
+pub struct WorldMessages<'w> {
+    pub births: ResMut<'w, Messages<BirthEvent>>,
+    pub deaths: ResMut<'w, Messages<DeathEvent>>,
+    pub kills: ResMut<'w, Messages<KillEvent>>,
+    pub eats: ResMut<'w, Messages<EatEvent>>,
+    pub adoptions: ResMut<'w, Messages<AdoptionEvent>>,
+    pub speciations: ResMut<'w, Messages<SpeciationEvent>>,
+    pub extinctions: ResMut<'w, Messages<ExtinctionEvent>>,
+}
+
+impl WorldMessages<'_> {
+    pub fn clear(&mut self) {
+        self.births.clear();
+        self.deaths.clear();
+        self.kills.clear();
+        self.eats.clear();
+        self.adoptions.clear();
+        self.speciations.clear();
+        self.extinctions.clear();
+    }
+}
+
+// What the world remembers about its creatures' pasts (part of WorldReset)
+#[derive(SystemParam)]
+pub struct WorldLedgers<'w> {
+    pub lineage: ResMut<'w, Lineage>,
+    pub genetics: ResMut<'w, GeneticsHistory>,
+    pub fitness: ResMut<'w, FitnessLedger>,
+    pub biographies: ResMut<'w, Biographies>,
+    pub records: ResMut<'w, WorldRecords>,
+    pub timeline: ResMut<'w, WorldTimeline>,
+    pub day_ledger: ResMut<'w, DayLedger>,
+    pub seasonal: ResMut<'w, SeasonalStats>,
+    pub genealogy: ResMut<'w, Genealogy>,
+}
+
+impl WorldLedgers<'_> {
+    pub fn clear(&mut self) {
+        self.lineage.0.clear();
+        *self.genetics = GeneticsHistory::default();
+        *self.fitness = FitnessLedger::default();
+        self.biographies.0.clear();
+        *self.records = WorldRecords::default();
+        *self.timeline = WorldTimeline::default();
+        *self.day_ledger = DayLedger::default();
+        *self.seasonal = SeasonalStats::default();
+        self.genealogy.0.clear();
+    }
+}
+
+// Tear the current world down and reset everything that describes it
+pub fn start_new_game(
+    mut commands: Commands,
+    request: Res<NewGameRequest>,
+    mut cfg: ResMut<SimulationConfig>,
+    mut active: ResMut<ActivePreset>,
+    mut world: WorldReset,
+    mut scenario: ResMut<ActiveScenario>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mode: Res<State<GameMode>>,
+    mut next_mode: ResMut<NextState<GameMode>>,
+    mut rng: ResMut<SimRng>,
+    fixed: Option<Res<FixedConfig>>,
+) {
+    world.clear(&mut commands);
+    rng.reseed();
+    // A world code's seed holds for this game only
+    if let Some(code) = &request.code {
+        rng.seed = code.seed;
+    }
+
+    // A scenario brings its own preset and overrides
+    let def = request.scenario.as_deref().and_then(|id| match ScenarioDef::load(id) {
+        Ok(def) => Some(def),
+        Err(err) => {
+            println!("Scenario unusable ({}), starting a sandbox game", err);
+            None
+        }
+    });
+    let preset = def.as_ref().and_then(|d| d.preset.clone()).or_else(|| request.preset.clone());
+
+    let debug_panel_enabled = cfg.debug_panel_enabled;
+    *cfg = match &fixed {
+        Some(fixed) => fixed.0.clone(),
+        None => SimulationConfig::load(preset.as_deref(), def.as_ref().and_then(|d| d.config.as_ref())),
+    };
+    cfg.debug_panel_enabled = debug_panel_enabled;
+    if let Some(code) = &request.code {
+        code.apply(&mut cfg);
+    }
+    commands.insert_resource(SpeciesBehaviors::load(&cfg));
+    world.resources.add(ResourceKind::Seeds, cfg.starting_seeds);
+
+    // A map file (bringing its own size) or a painted map_image replaces the
+    // generated lakes and trees for spawn_map/spawn_trees
+    let blueprint = match (&request.map, cfg.map_image.as_deref()) {
+        (Some(id), _) => Some(MapBlueprint::load(id)),
+        (None, Some(path)) => Some(MapBlueprint::load_image(path, &cfg)),
+        (None, None) => None,
+    };
+    match blueprint {
+        Some(Ok(blueprint)) => {
+            cfg.map_size = blueprint.map_size;
+            commands.insert_resource(blueprint);
+        }
+        Some(Err(err)) => println!("Map unusable ({}), generating one", err),
+        None => {}
+    }
+
+    // Results screens pause the clock
+    virtual_time.unpause();
+
+    if let Some(def) = &def {
+        println!("Scenario: {} - {}", def.name, def.description);
+    }
+    // Challenges run in their own mode; leaving one drops back to the sandbox
+    if def.is_some() {
+        next_mode.set(GameMode::Challenge);
+    } else if *mode.get() == GameMode::Challenge {
+        next_mode.set(GameMode::Sandbox);
+    } else if *mode.get() == GameMode::Observer {
+        cfg.governor_enabled = true; // the reload above reset it
+    }
+    scenario.0 = def.map(|def| ScenarioRun { def, outcome: None, peak: HashMap::new() });
+
+    active.0 = preset.clone();
+
+    match &preset {
+        Some(id) => println!("New game: {}", preset_display_name(id)),
+        None => println!("New game"),
+    }
+    println!("World code: {}", WorldCode::of(&cfg, rng.seed, preset.as_deref()).encode());
+}
+
+pub fn finish_new_game(mut commands: Commands) {
+    commands.remove_resource::<NewGameRequest>();
+    commands.remove_resource::<MapBlueprint>();
+}
+
+// --- DATA DIRECTORIES ---
+// Everything the game writes goes under one directory: `--data-dir <path>` if
+// given, else the platform's per-user data directory (~/.local/share,
+// %APPDATA%, ~/Library/Application Support) plus "living_world", else the
+// working directory. Shipped assets are still read from `assets/`.
+pub const LOG_DIR: &str = "logs";
+pub const EXPORT_DIR: &str = "exports";
+
+pub fn data_dir() -> &'static std::path::Path {
+    static DIR: std::sync::OnceLock<std::path::PathBuf> = std::sync::OnceLock::new();
+    DIR.get_or_init(|| {
+        cli_value("--data-dir")
+            .map(std::path::PathBuf::from)
+            .or_else(|| dirs::data_dir().map(|d| d.join("living_world")))
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+    })
+}
+
+pub fn data_path(relative: &str) -> std::path::PathBuf {
+    data_dir().join(relative)
+}
+
+pub fn ensure_parent(path: &std::path::Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(parent) => std::fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
+// Writes `relative` under data_dir(), creating directories on the way
+pub fn write_data_file(relative: &str, contents: impl AsRef<[u8]>) -> std::io::Result<std::path::PathBuf> {
+    let path = data_path(relative);
+    ensure_parent(&path)?;
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+// --- SAVE / LOAD ---
+// Quicksaves and autosaves live in SAVE_DIR under data_dir(). RON is there for reading and
+// hand-editing; the compressed (bincode + zstd) encoding is the compact default.
+pub const SAVE_DIR: &str = "saves";
+
+// Bump whenever a saved struct changes shape, and teach WorldSnapshot::decode
+// how to upgrade the previous one.
+// 1: unversioned saves, config stored field by field
+// 2: version header; config stored as RON text layered onto the defaults
+// 3: shelters
+// 4: parasites
+// 5: grass
+// 6: wool and player resources
+// 7: water pollution
+// 8: founders
+// 9: world records
+// 10: timeline
+// 11: gene-edited creatures
+// 12: creatures as CreatureRecords
+// 13: species registry (and each creature's clade)
+// 14: mothers and foster parents
+// 15: SimRng tick
+// 16: birds
+// 17: corpses
+pub const SAVE_VERSION: u32 = 17;
+pub const SAVE_MAGIC: &[u8; 3] = b"LWS";
+
+// (version, how many lists it appended to WorldSnapshot), for decoding older compressed saves.
+// An Option counts as one too: None is also a single 0 byte.
+pub const SAVE_LISTS_ADDED: &[(u32, usize)] = &[(3, 1), (4, 1), (5, 1), (6, 2), (7, 1), (8, 1), (9, 1), (10, 1), (11, 1), (12, 1), (13, 2), (15, 1), (16, 1), (17, 1)];
+
+// What a RON save file holds
+#[derive(Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub game_version: String, // build that wrote it, for error messages
+    pub world: WorldSnapshot,
+}
+
+// Enough of a RON save to tell its version
+#[derive(Deserialize)]
+struct SaveHeader {
+    #[serde(default = "unversioned")]
+    version: u32,
+}
+
+// Version 1 files are the bare world
+fn unversioned() -> u32 {
+    1
+}
+
+pub fn save_config_text<S: serde::Serializer>(cfg: &SimulationConfig, s: S) -> Result<S::Ok, S::Error> {
+    let text = ron::to_string(cfg).map_err(serde::ser::Error::custom)?;
+    s.serialize_str(&text)
+}
+
+// Version 1 RON saves hold the config field by field; those fields are laid
+// over the defaults the same way
+pub fn load_config_text<'de, D: serde::Deserializer<'de>>(d: D) -> Result<SimulationConfig, D::Error> {
+    let text = if d.is_human_readable() {
+        match ron::Value::deserialize(d)? {
+            ron::Value::String(text) => text,
+            fields => ron::to_string(&fields).map_err(serde::de::Error::custom)?,
+        }
+    } else {
+        String::deserialize(d)?
+    };
+    SimulationConfig::from_overrides(&text).map_err(serde::de::Error::custom)
+}
+
+pub fn save_records_text<S: serde::Serializer>(records: &[CreatureRecord], s: S) -> Result<S::Ok, S::Error> {
+    let text = ron::to_string(records).map_err(serde::ser::Error::custom)?;
+    s.serialize_str(&text)
+}
+
+// An empty string is what an older compressed save's padding decodes to
+pub fn load_records_text<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Vec<CreatureRecord>, D::Error> {
+    let text = String::deserialize(d)?;
+    if text.is_empty() { return Ok(Vec::new()); }
+    ron::from_str(&text).map_err(serde::de::Error::custom)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum SaveFormat {
+    Ron,
+    Compressed,
+}
+
+string_enum!(SaveFormat { Ron, Compressed });
+
+impl SaveFormat {
+    pub const ALL: [SaveFormat; 2] = [SaveFormat::Ron, SaveFormat::Compressed];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SaveFormat::Ron => "ron",
+            SaveFormat::Compressed => "lws",
+        }
+    }
+
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+        Self::ALL.into_iter().find(|f| f.extension() == ext)
+    }
+}
+
+pub fn save_path(slot: &str, format: SaveFormat) -> std::path::PathBuf {
+    data_path(SAVE_DIR).join(format!("{}.{}", slot, format.extension()))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedTile {
+    pub pos: (i32, i32),
+    pub water: Option<f32>, // depth
+    pub bridge: bool,
+    pub shaded: bool,
+    pub wear: f32,
+    pub dirt: bool,
+    pub fertility: (f32, f32, f32, f32), // level, recovered_from, recovery_seconds, graze_pressure
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedBird {
+    pub pos: (i32, i32),
+    pub bird: Bird,
+    pub stream_key: u64, // kept so two birds saved on one tile don't fly as one after a load
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedCorpse {
+    pub pos: (i32, i32),
+    pub species_id: u32,
+    pub cause: DeathCause,
+    pub seconds_left: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedCreature {
+    pub species_id: u32,
+    pub pos: (i32, i32),
+    pub last_pos: (i32, i32),
+    pub hunger: f32,
+    pub age_seconds: f32,
+    pub is_adult: bool,
+    pub dna: Dna,
+    pub generation: u32,
+    pub name: String,
+    pub scared_of_water: bool,
+    pub altruistic: bool,
+    pub cooldown_left: Option<f32>,
+    pub digesting: bool,
+    pub overfed_left: Option<f32>,
+}
+
+// Whole-world state: enough to carry on exactly where the save was made
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    // As text, so saves survive config fields being added or removed
+    #[serde(serialize_with = "save_config_text", deserialize_with = "load_config_text")]
+    pub config: SimulationConfig,
+    pub preset: Option<String>,
+    pub days: f32,
+    pub weather: WeatherKind,
+    pub weather_seconds_left: f32,
+    pub population: HashMap<u32, SpeciesCounters>,
+    pub tiles: Vec<SavedTile>,
+    pub plants: Vec<((i32, i32), bool)>, // pos, shade tolerant
+    pub crops: Vec<((i32, i32), CropStage, f32)>,
+    pub trees: Vec<(i32, i32)>,
+    pub fences: Vec<(i32, i32)>,
+    pub gates: Vec<((i32, i32), Option<u32>)>,
+    pub exhausted: Vec<(i32, i32)>,
+    pub creatures: Vec<SavedCreature>, // older saves only, see creature_records
+    pub shepherd: Option<(i32, i32)>,
+    // Lists added since version 2 go last, so older compressed saves decode with
+    // empty ones appended (see decode)
+    #[serde(default)]
+    pub shelters: Vec<(i32, i32)>,
+    #[serde(default)]
+    pub creature_parasites: Vec<u32>, // older saves only, same order as `creatures`
+    #[serde(default)]
+    pub tile_grass: Vec<f32>, // same order as `tiles`
+    #[serde(default)]
+    pub creature_wool: Vec<f32>, // older saves only, same order as `creatures`
+    #[serde(default)]
+    pub resources: Vec<(ResourceKind, u32)>,
+    #[serde(default)]
+    pub tile_pollution: Vec<f32>, // same order as `tiles`
+    #[serde(default)]
+    pub creature_founders: Vec<String>, // older saves only, same order as `creatures`
+    #[serde(default)]
+    pub records: Vec<WorldRecord>,
+    #[serde(default)]
+    pub timeline: Vec<TimelineEntry>,
+    #[serde(default)]
+    pub creature_edited: Vec<bool>, // older saves only, same order as `creatures`
+    // Every creature with all its state. As text, like the config, so new
+    // CreatureRecord fields need a serde default rather than another list;
+    // decode folds the older per-creature lists in here.
+    #[serde(default, serialize_with = "save_records_text", deserialize_with = "load_records_text")]
+    pub creature_records: Vec<CreatureRecord>,
+    #[serde(default)]
+    pub species_registry: SpeciesRegistry, // derived species and the last speciation check
+    #[serde(default)]
+    pub rng_tick: u64, // SimRng streams are keyed on it, so the world draws on as it would have
+    #[serde(default)]
+    pub birds: Vec<SavedBird>,
+    #[serde(default)]
+    pub corpses: Vec<SavedCorpse>,
+}
+
+impl WorldSnapshot {
+    // RON: a SaveFile. Compressed: SAVE_MAGIC, the version (u32 LE), then
+    // zstd(bincode((game_version, snapshot))).
+    pub fn encode(self, format: SaveFormat, level: i32) -> Result<Vec<u8>, String> {
+        let game_version = env!("CARGO_PKG_VERSION").to_string();
+        match format {
+            SaveFormat::Ron => {
+                let file = SaveFile { version: SAVE_VERSION, game_version, world: self };
+                ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default())
+                    .map(String::into_bytes)
+                    .map_err(|e| e.to_string())
+            }
+            SaveFormat::Compressed => {
+                let raw = bincode::serde::encode_to_vec((game_version, self), bincode::config::standard())
+                    .map_err(|e| e.to_string())?;
+                let mut bytes = SAVE_MAGIC.to_vec();
+                bytes.extend_from_slice(&SAVE_VERSION.to_le_bytes());
+                bytes.extend(zstd::encode_all(&raw[..], level).map_err(|e| e.to_string())?);
+                Ok(bytes)
+            }
+        }
+    }
+
+    pub fn decode(bytes: &[u8], format: SaveFormat) -> Result<Self, String> {
+        let too_new = |version: u32| {
+            format!("save version {} is newer than this build reads ({}), update the game", version, SAVE_VERSION)
+        };
+        match format {
+            SaveFormat::Ron => {
+                let text = std::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+                let version = ron::from_str::<SaveHeader>(text).map_err(|e| e.to_string())?.version;
+                if version > SAVE_VERSION {
+                    return Err(too_new(version));
+                }
+                // Straight into the types: a ron::Value can't hold enum variants
+                // (WeatherKind, RecordKind...), and RON names every field, so
+                // fields added since the save get their serde default
+                let world = if version == 1 {
+                    ron::from_str::<Self>(text)
+                } else {
+                    ron::from_str::<SaveFile>(text).map(|file| file.world)
+                };
+                world.map(Self::fold_creature_lists).map_err(|e| e.to_string())
+            }
+            SaveFormat::Compressed => {
+                let Some(rest) = bytes.strip_prefix(&SAVE_MAGIC[..]) else {
+                    // Version 1 had no header, and bincode can't be migrated blind
+                    return Err("compressed save predates save versioning; load it in the build that wrote it and save again".to_string());
+                };
+                let (version, body) = rest.split_at_checked(4).ok_or("save header is truncated")?;
+                let version = u32::from_le_bytes(version.try_into().unwrap());
+                if version > SAVE_VERSION {
+                    return Err(too_new(version));
+                }
+                // bincode isn't self-describing: each version decodes with its own
+                // layout. Versions since 2 only added lists at the end, so older saves
+                // get an empty one appended per missing list (a zero length is a
+                // single 0 byte).
+                let mut raw = zstd::decode_all(body).map_err(|e| e.to_string())?;
+                for &(added_in, lists) in SAVE_LISTS_ADDED {
+                    if version < added_in {
+                        raw.extend(std::iter::repeat_n(0, lists));
+                    }
+                }
+                bincode::serde::decode_from_slice::<(String, Self), _>(&raw, bincode::config::standard())
+                    .map(|((_, snapshot), _)| snapshot.fold_creature_lists())
+                    .map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+// Read-only view of everything WorldSnapshot records
+#[derive(SystemParam)]
+pub struct SnapshotQueries<'w, 's> {
+    pub cfg: Res<'w, SimulationConfig>,
+    pub preset: Res<'w, ActivePreset>,
+    pub totals: SnapshotTotals<'w>,
+    pub tiles: Query<'w, 's, (&'static Tile, &'static Footfall, &'static Fertility, Option<&'static Water>, Has<Bridge>, Has<Shaded>, &'static Grass, Option<&'static Pollution>)>,
+    pub plants: Query<'w, 's, (&'static GridPosition, Has<ShadeTolerant>), (With<Plant>, Without<Crop>, Without<Dead>)>,
+    pub crops: Query<'w, 's, (&'static GridPosition, &'static Crop), Without<Dead>>,
+    pub trees: Query<'w, 's, &'static GridPosition, With<Tree>>,
+    pub fences: Query<'w, 's, &'static GridPosition, With<Fence>>,
+    pub gates: Query<'w, 's, (&'static GridPosition, &'static Gate)>,
+    pub shelters: Query<'w, 's, &'static GridPosition, With<Shelter>>,
+    pub exhausted: Query<'w, 's, &'static GridPosition, (With<ExhaustedSoil>, Without<Dead>)>,
+    pub creatures: CreatureRecords<'w, 's>,
+    pub shepherd: Query<'w, 's, &'static GridPosition, With<Shepherd>>,
+    pub birds: Query<'w, 's, (&'static GridPosition, &'static Bird, &'static StreamKey)>,
+    pub corpses: Query<'w, 's, (&'static GridPosition, &'static Corpse), Without<Dead>>,
+}
+
+// The world-wide numbers a snapshot keeps (part of SnapshotQueries)
+#[derive(SystemParam)]
+pub struct SnapshotTotals<'w> {
+    pub stats: Res<'w, GameStats>,
+    pub weather: Res<'w, Weather>,
+    pub pop: Res<'w, PopulationStats>,
+    pub resources: Res<'w, PlayerResources>,
+    pub records: Res<'w, WorldRecords>,
+    pub timeline: Res<'w, WorldTimeline>,
+    pub species_registry: Res<'w, SpeciesRegistry>,
+    pub rng: Res<'w, SimRng>,
+}
+
+impl SnapshotQueries<'_, '_> {
+    pub fn capture(&self) -> WorldSnapshot {
+        let timer_left = |t: &Timer| t.remaining_secs();
+        let (tiles, (tile_grass, tile_pollution)): (Vec<_>, (Vec<_>, Vec<_>)) = self
+            .tiles
+            .iter()
+            .map(|(tile, footfall, fertility, water, bridge, shaded, grass, pollution)| {
+                let saved = SavedTile {
+                    pos: (tile.x, tile.y),
+                    water: water.map(|w| w.depth),
+                    bridge,
+                    shaded,
+                    wear: footfall.wear,
+                    dirt: footfall.dirt,
+                    fertility: (fertility.level, fertility.recovered_from, fertility.recovery_seconds, fertility.graze_pressure),
+                };
+                (saved, (grass.0, pollution.map(|p| p.0).unwrap_or(0.0)))
+            })
+            .unzip();
+        WorldSnapshot {
+            config: self.cfg.clone(),
+            preset: self.preset.0.clone(),
+            days: self.totals.stats.days,
+            weather: self.totals.weather.kind,
+            weather_seconds_left: timer_left(&self.totals.weather.timer),
+            population: self.totals.pop.species.clone(),
+            tiles,
+            plants: self.plants.iter().map(|(p, tolerant)| ((p.x, p.y), tolerant)).collect(),
+            crops: self.crops.iter().map(|(p, c)| ((p.x, p.y), c.stage, c.growth)).collect(),
+            trees: self.trees.iter().map(|p| (p.x, p.y)).collect(),
+            fences: self.fences.iter().map(|p| (p.x, p.y)).collect(),
+            gates: self.gates.iter().map(|(p, g)| ((p.x, p.y), g.allowed)).collect(),
+            exhausted: self.exhausted.iter().map(|p| (p.x, p.y)).collect(),
+            creatures: Vec::new(),
+            shepherd: self.shepherd.iter().next().map(|p| (p.x, p.y)),
+            shelters: self.shelters.iter().map(|p| (p.x, p.y)).collect(),
+            creature_parasites: Vec::new(),
+            tile_grass,
+            tile_pollution,
+            creature_wool: Vec::new(),
+            creature_founders: Vec::new(),
+            records: self.totals.records.best.clone(),
+            timeline: self.totals.timeline.entries.clone(),
+            creature_edited: Vec::new(),
+            creature_records: self.creatures.all(),
+            species_registry: self.totals.species_registry.clone(),
+            rng_tick: self.totals.rng.tick,
+            birds: {
+                let mut birds: Vec<SavedBird> = self
+                    .birds
+                    .iter()
+                    .map(|(p, bird, key)| SavedBird { pos: (p.x, p.y), bird: *bird, stream_key: key.0 })
+                    .collect();
+                birds.sort_by_key(|b| b.stream_key);
+                birds
+            },
+            corpses: self
+                .corpses
+                .iter()
+                .map(|(p, c)| SavedCorpse { pos: (p.x, p.y), species_id: c.species_id, cause: c.cause, seconds_left: c.seconds_left })
+                .collect(),
+            resources: {
+                let mut resources: Vec<(ResourceKind, u32)> = self.totals.resources.0.iter().map(|(&k, &v)| (k, v)).collect();
+                resources.sort();
+                resources
+            },
+        }
+    }
+}
+
+// Put a snapshot's world in place of the current one (already cleared)
+pub fn spawn_snapshot(commands: &mut Commands, snapshot: &WorldSnapshot, world: &mut WorldReset) {
+    // Own copy: the live config is replaced by the caller once this returns
+    let cfg = &snapshot.config;
+
+    world.stats.days = snapshot.days;
+    world.weather.kind = snapshot.weather;
+    world.weather.timer = Timer::from_seconds(snapshot.weather_seconds_left.max(0.1), TimerMode::Once);
+    world.pop.species = snapshot.population.clone();
+    world.resources.0 = snapshot.resources.iter().copied().collect();
+    world.ledgers.records.best = snapshot.records.clone();
+    world.ledgers.timeline.entries = snapshot.timeline.clone();
+    *world.species_registry = snapshot.species_registry.clone();
+
+    for (i, t) in snapshot.tiles.iter().enumerate() {
+        let (level, recovered_from, recovery_seconds, graze_pressure) = t.fertility;
+        // Saves from before grass start lush
+        let grass = snapshot.tile_grass.get(i).copied().map(Grass).unwrap_or_default();
+        let mut tile = commands.spawn((
+            Tile { x: t.pos.0, y: t.pos.1 },
+            Footfall { wear: t.wear, dirt: t.dirt },
+            Fertility { level, recovered_from, recovery_seconds, graze_pressure },
+            grass,
+        ));
+        if let Some(depth) = t.water { tile.insert(Water { depth }); }
+        if let Some(&level) = snapshot.tile_pollution.get(i).filter(|&&l| l > 0.0) {
+            tile.insert(Pollution(level));
+        }
+        if t.bridge { tile.insert(Bridge); }
+        if t.shaded { tile.insert(Shaded); }
+        world.tile_index.0.insert(t.pos, tile.id());
+    }
+
+    for &(pos, tolerant) in &snapshot.plants {
+        let mut plant = commands.spawn((Plant, GridPosition { x: pos.0, y: pos.1 }));
+        if tolerant { plant.insert(ShadeTolerant); }
+    }
+
+    for &(pos, stage, growth) in &snapshot.crops {
+        let mut crop = commands.spawn((Crop { stage, growth }, GridPosition { x: pos.0, y: pos.1 }));
+        if stage == CropStage::Ripe { crop.insert(Plant); }
+    }
+
+    for &pos in &snapshot.trees {
+        commands.spawn((Tree, GridPosition { x: pos.0, y: pos.1 }));
+    }
+
+    for &pos in &snapshot.fences {
+        commands.spawn((Fence, GridPosition { x: pos.0, y: pos.1 }));
+    }
+
+    for &(pos, allowed) in &snapshot.gates {
+        commands.spawn((Gate { allowed }, GridPosition { x: pos.0, y: pos.1 }));
+    }
+
+    // Occupants move back in on the first frame
+    for &pos in &snapshot.shelters {
+        commands.spawn((Shelter::default(), GridPosition { x: pos.0, y: pos.1 }));
+    }
+
+    for &pos in &snapshot.exhausted {
+        commands.spawn((ExhaustedSoil, GridPosition { x: pos.0, y: pos.1 }));
+    }
+
+    for b in &snapshot.birds {
+        commands.spawn((b.bird, GridPosition { x: b.pos.0, y: b.pos.1 }, NextMove::default(), StreamKey(b.stream_key)));
+    }
+
+    for c in &snapshot.corpses {
+        commands.spawn((
+            Corpse { species_id: c.species_id, cause: c.cause, seconds_left: c.seconds_left },
+            GridPosition { x: c.pos.0, y: c.pos.1 },
+        ));
+    }
+
+    let spawned: Vec<Entity> = snapshot.creature_records.iter().map(|r| r.spawn(commands, cfg)).collect();
+    for (record, &entity) in snapshot.creature_records.iter().zip(&spawned) {
+        // A mother that isn't there, so it's still up for adoption
+        if record.orphaned {
+            commands.entity(entity).insert(Mother(Entity::PLACEHOLDER));
+            continue;
+        }
+        let Some(&mother) = record.mother.and_then(|i| spawned.get(i)) else { continue; };
+        commands.entity(entity).insert(Mother(mother));
+        if record.fostered {
+            commands.entity(entity).insert(Fostered);
+        }
+    }
+
+    if let Some(pos) = snapshot.shepherd {
+        commands.spawn((
+            Shepherd,
+            GridPosition { x: pos.0, y: pos.1 },
+            NextMove::default(),
+        ));
+    }
+}
+
+// Encode on this frame, write the file off-thread so autosaves don't hitch
+pub fn write_save(snapshot: WorldSnapshot, slot: &str, format: SaveFormat, level: i32) {
+    let path = save_path(slot, format);
+    let bytes = match snapshot.encode(format, level) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            println!("Save failed: {}", err);
+            return;
+        }
+    };
+    std::thread::spawn(move || {
+        let result = ensure_parent(&path).and_then(|_| std::fs::write(&path, &bytes));
+        match result {
+            Ok(()) => println!("Saved {} ({} KB)", path.display(), bytes.len() / 1024),
+            Err(err) => println!("Save to {} failed: {}", path.display(), err),
+        }
+    });
+}
+
+// Newest file for `slot` in any format
+pub fn find_save(slot: &str) -> Option<(std::path::PathBuf, SaveFormat)> {
+    SaveFormat::ALL
+        .into_iter()
+        .map(|f| (save_path(slot, f), f))
+        .filter_map(|(path, f)| modified_time(path.to_str()?).map(|t| (t, path, f)))
+        .max_by_key(|(t, _, _)| *t)
+        .map(|(_, path, f)| (path, f))
+}
+
+// Present for one frame when a loaded snapshot should replace the world
+#[derive(Resource)]
+pub struct LoadRequest {
+    pub snapshot: WorldSnapshot,
+    pub label: String, // where it came from, for the notification
+}
+
+pub fn apply_load_request(
+    mut commands: Commands,
+    request: Res<LoadRequest>,
+    mut cfg: ResMut<SimulationConfig>,
+    mut active: ResMut<ActivePreset>,
+    mut world: WorldReset,
+    mut rng: ResMut<SimRng>,
+    mut scenario: ResMut<ActiveScenario>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mode: Res<State<GameMode>>,
+    mut next_mode: ResMut<NextState<GameMode>>,
+    mut notifications: ResMut<Notifications>,
+) {
+    world.clear(&mut commands);
+    spawn_snapshot(&mut commands, &request.snapshot, &mut world);
+    rng.tick = request.snapshot.rng_tick;
+
+    let debug_panel_enabled = cfg.debug_panel_enabled;
+    *cfg = request.snapshot.config.clone();
+    cfg.debug_panel_enabled = debug_panel_enabled;
+    commands.insert_resource(SpeciesBehaviors::load(&cfg));
+    active.0 = request.snapshot.preset.clone();
+
+    // Saves are sandbox worlds; a running challenge ends here
+    scenario.0 = None;
+    virtual_time.unpause();
+    if *mode.get() == GameMode::Challenge {
+        next_mode.set(GameMode::Sandbox);
+    }
+
+    commands.remove_resource::<LoadRequest>();
+    notifications.push(format!("Loaded {} (day {:.1})", request.label, request.snapshot.days), &cfg);
+}
+
+// --- MAP IMAGE ---
+// Top-down picture of a snapshot for docs, sharing and minimaps: `cell` pixels
+// per tile, north (high y) up. Colours follow the app's tiles; plants, crops
+// and structures are drawn as a smaller square in the middle of their tile.
+pub struct MapImage {
+    pub width: u32,
+    pub height: u32,
+    pub rgb: Vec<u8>, // row-major, top row first
+}
+
+impl MapImage {
+    pub fn render(snapshot: &WorldSnapshot, cell: u32) -> Self {
+        let cfg = &snapshot.config;
+        let cell = cell.max(1);
+        let side = (cfg.map_size * 2).max(0) as u32;
+        let (width, height) = (side * cell, side * cell);
+        let mut image = MapImage { width, height, rgb: vec![0; (width * height * 3) as usize] };
+
+        for (i, t) in snapshot.tiles.iter().enumerate() {
+            let grass = snapshot.tile_grass.get(i).copied().unwrap_or(1.0).clamp(0.0, 1.0);
+            let mut color = match (t.water, t.bridge) {
+                (Some(_), true) => [0.55, 0.4, 0.2],
+                (Some(depth), false) => {
+                    let d = (depth / cfg.max_water_depth).clamp(0.0, 1.0);
+                    let water = [0.35 - 0.25 * d, 0.55 - 0.4 * d, 0.9 - 0.2 * d];
+                    let foul = snapshot.tile_pollution.get(i).copied().unwrap_or(0.0).clamp(0.0, 1.0) * 0.7;
+                    mix_rgb(water, [0.35, 0.4, 0.2], foul)
+                }
+                (None, _) if t.dirt => [0.45, 0.35, 0.2],
+                (None, _) => [0.42 - 0.14 * grass, 0.4 + 0.14 * grass, 0.25 + 0.05 * grass],
+            };
+            if t.shaded && t.water.is_none() {
+                color = color.map(|c| c * 0.92);
+            }
+            image.fill(cfg.map_size, t.pos, cell, 0, color);
+        }
+
+        let inset = cell / 4;
+        let mut mark = |pos: (i32, i32), color: [f32; 3]| image.fill(cfg.map_size, pos, cell, inset, color);
+        for &(pos, _) in &snapshot.plants {
+            mark(pos, [0.2, 0.75, 0.2]);
+        }
+        for &(pos, stage, _) in &snapshot.crops {
+            mark(pos, match stage {
+                CropStage::Seed => [0.4, 0.3, 0.15],
+                CropStage::Sprout => [0.4, 0.7, 0.2],
+                CropStage::Ripe => [0.9, 0.75, 0.2],
+            });
+        }
+        for &pos in &snapshot.trees {
+            mark(pos, [0.1, 0.35, 0.1]);
+        }
+        for &pos in &snapshot.fences {
+            mark(pos, [0.45, 0.3, 0.15]);
+        }
+        for &(pos, _) in &snapshot.gates {
+            mark(pos, [0.75, 0.6, 0.35]);
+        }
+        for &pos in &snapshot.shelters {
+            mark(pos, [0.6, 0.45, 0.3]);
+        }
+        image
+    }
+
+    // Creatures as a dot in their species colour on top of a render, for live maps
+    pub fn mark_creatures(&mut self, snapshot: &WorldSnapshot, cell: u32) {
+        let cfg = &snapshot.config;
+        let cell = cell.max(1);
+        for c in snapshot.creature_records.iter().map(|r| &r.creature) {
+            self.fill(cfg.map_size, c.pos, cell, cell / 3, cfg.s(c.species_id).appearance.color);
+        }
+    }
+
+    // Paints one tile's square, `inset` pixels in from each edge; off-map tiles are skipped
+    fn fill(&mut self, map_size: i32, (x, y): (i32, i32), cell: u32, inset: u32, color: [f32; 3]) {
+        let (col, row) = (x + map_size, map_size - 1 - y);
+        let side = (map_size * 2) as u32;
+        if col < 0 || row < 0 || col as u32 >= side || row as u32 >= side { return; }
+        let px = color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        for dy in inset..cell.saturating_sub(inset).max(inset + 1) {
+            for dx in inset..cell.saturating_sub(inset).max(inset + 1) {
+                let (ix, iy) = (col as u32 * cell + dx, row as u32 * cell + dy);
+                let at = ((iy * self.width + ix) * 3) as usize;
+                self.rgb[at..at + 3].copy_from_slice(&px);
+            }
+        }
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(&self.rgb).map_err(|e| e.to_string())?;
+        writer.finish().map_err(|e| e.to_string())?;
+        Ok(bytes)
+    }
+
+    // Any 8- or 16-bit PNG; grey and palette images are expanded, alpha is ignored
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).map_err(|e| e.to_string())?;
+        let channels = info.color_type.samples();
+        let mut rgb = Vec::with_capacity((info.width * info.height * 3) as usize);
+        for row in buf.chunks(info.line_size).take(info.height as usize) {
+            for px in row.chunks(channels).take(info.width as usize) {
+                match channels {
+                    1 | 2 => rgb.extend_from_slice(&[px[0]; 3]),
+                    _ => rgb.extend_from_slice(&px[..3]),
+                }
+            }
+        }
+        Ok(MapImage { width: info.width, height: info.height, rgb })
+    }
+
+    pub fn pixel(&self, col: u32, row: u32) -> [u8; 3] {
+        let at = ((row.min(self.height - 1) * self.width + col.min(self.width - 1)) * 3) as usize;
+        [self.rgb[at], self.rgb[at + 1], self.rgb[at + 2]]
+    }
+}
+
+// --- MAP BLUEPRINTS ---
+// A world's ground and fixtures without anything alive: what spawn_map builds
+// instead of generating lakes and trees. Two sources:
+// - map files (MAP_DIR/<id>.ron), saved from a running world and offered by
+//   the new-game menu, so maps can be shared apart from saves;
+// - a painted PNG (cfg.map_image), stretched over the map with one sample at
+//   each tile's centre. Blue-ish pixels are water, darker blue deeper; the rest
+//   go to the nearest of the land colours below, which are also what MapImage
+//   paints, so an exported map imports as itself.
+pub const MAP_DIR: &str = "maps";
+
+// Bump when MapBlueprint changes shape in a way serde defaults can't cover
+pub const MAP_VERSION: u32 = 1;
+
+const BLUEPRINT_LAND: [([u8; 3], Terrain); 4] = [
+    ([89, 120, 70], Terrain::Grass),
+    ([26, 89, 26], Terrain::Forest),
+    ([51, 191, 51], Terrain::Plant),
+    ([115, 89, 51], Terrain::Dirt),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Terrain {
+    Grass,
+    Water,
+    Forest, // a tree on grass
+    Plant,
+    Dirt,
+}
+
+impl Terrain {
+    pub fn of_pixel([r, g, b]: [u8; 3]) -> Self {
+        if b > r.max(g) {
+            return Terrain::Water;
+        }
+        let distance = |c: [u8; 3]| (0..3).map(|i| (c[i] as i32 - [r, g, b][i] as i32).pow(2)).sum::<i32>();
+        BLUEPRINT_LAND.iter().min_by_key(|(c, _)| distance(*c)).map_or(Terrain::Grass, |&(_, t)| t)
+    }
+}
+
+// Founders of `species_id` start on random tiles in this box (corners included)
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct SpawnRegion {
+    pub species_id: u32,
+    pub min: (i32, i32),
+    pub max: (i32, i32),
+}
+
+impl SpawnRegion {
+    pub fn pick(&self, rng: &mut impl Rng) -> (i32, i32) {
+        let mut span = |lo: i32, hi: i32| lo + rng.random::<i32>().abs() % (hi - lo + 1).max(1);
+        (span(self.min.0, self.max.0), span(self.min.1, self.max.1))
+    }
+}
+
+// Every list is sorted by position, so a map saves to the same file each time
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapBlueprint {
+    pub version: u32,
+    pub map_size: i32,
+    pub water: Vec<((i32, i32), f32)>, // tile, depth
+    pub bridges: Vec<(i32, i32)>,
+    pub dirt: Vec<(i32, i32)>,
+    pub trees: Vec<(i32, i32)>,
+    pub plants: Vec<(i32, i32)>,
+    pub fences: Vec<(i32, i32)>,
+    pub gates: Vec<((i32, i32), Option<u32>)>, // who may pass, as on Gate
+    pub shelters: Vec<(i32, i32)>,
+    pub spawns: Vec<SpawnRegion>, // species without one use the usual starting spots
+}
+
+impl MapBlueprint {
+    pub fn load_image(path: &str, cfg: &SimulationConfig) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+        let image = MapImage::decode(&bytes).map_err(|e| format!("{}: {}", path, e))?;
+        Ok(Self::from_image(&image, cfg))
+    }
+
+    pub fn from_image(image: &MapImage, cfg: &SimulationConfig) -> Self {
+        let mut blueprint = MapBlueprint { version: MAP_VERSION, map_size: cfg.map_size, ..default() };
+        let side = (cfg.map_size * 2).max(1) as u64;
+        for x in -cfg.map_size..cfg.map_size {
+            for y in -cfg.map_size..cfg.map_size {
+                let (col, row) = ((x + cfg.map_size) as u64, (cfg.map_size - 1 - y) as u64);
+                let px = image.pixel(
+                    ((2 * col + 1) * image.width as u64 / (2 * side)) as u32,
+                    ((2 * row + 1) * image.height as u64 / (2 * side)) as u32,
+                );
+                match Terrain::of_pixel(px) {
+                    Terrain::Water => {
+                        // Exported water runs r+g from 0.9 (shallow) down to 0.25 (deepest)
+                        let d = 1.0 - (px[0] as f32 + px[1] as f32) / (0.9 * 255.0);
+                        blueprint.water.push(((x, y), cfg.max_water_depth * d.clamp(0.2, 1.0)));
+                    }
+                    Terrain::Forest => blueprint.trees.push((x, y)),
+                    Terrain::Plant => blueprint.plants.push((x, y)),
+                    Terrain::Dirt => blueprint.dirt.push((x, y)),
+                    Terrain::Grass => {}
+                }
+            }
+        }
+        blueprint
+    }
+
+    // The ground and fixtures of a world as it is now. Crops are left out (they
+    // belong to a game, not a map); each species' spawn region is the box
+    // around where its creatures are.
+    pub fn from_snapshot(snapshot: &WorldSnapshot) -> Self {
+        let mut blueprint = MapBlueprint {
+            version: MAP_VERSION,
+            map_size: snapshot.config.map_size,
+            water: snapshot.tiles.iter().filter_map(|t| t.water.map(|depth| (t.pos, depth))).collect(),
+            bridges: snapshot.tiles.iter().filter(|t| t.bridge).map(|t| t.pos).collect(),
+            dirt: snapshot.tiles.iter().filter(|t| t.dirt).map(|t| t.pos).collect(),
+            trees: snapshot.trees.clone(),
+            plants: snapshot.plants.iter().map(|&(pos, _)| pos).collect(),
+            fences: snapshot.fences.clone(),
+            gates: snapshot.gates.clone(),
+            shelters: snapshot.shelters.clone(),
+            spawns: Vec::new(),
+        };
+        for creature in snapshot.creature_records.iter().map(|r| &r.creature) {
+            let (x, y) = creature.pos;
+            match blueprint.spawns.iter_mut().find(|r| r.species_id == creature.species_id) {
+                Some(r) => {
+                    r.min = (r.min.0.min(x), r.min.1.min(y));
+                    r.max = (r.max.0.max(x), r.max.1.max(y));
+                }
+                None => blueprint.spawns.push(SpawnRegion { species_id: creature.species_id, min: (x, y), max: (x, y) }),
+            }
+        }
+        blueprint.sort();
+        blueprint
+    }
+
+    fn sort(&mut self) {
+        self.water.sort_by_key(|&(pos, _)| pos);
+        self.water.dedup_by_key(|&mut (pos, _)| pos);
+        for list in [&mut self.bridges, &mut self.dirt, &mut self.trees, &mut self.plants, &mut self.fences, &mut self.shelters] {
+            list.sort_unstable();
+            list.dedup();
+        }
+        self.gates.sort_by_key(|&(pos, _)| pos);
+        self.spawns.sort_by_key(|r| r.species_id);
+    }
+
+    pub fn save(&self, id: &str) -> Result<std::path::PathBuf, String> {
+        let text = ron::to_string(self).map_err(|e| e.to_string())?;
+        write_data_file(&format!("{}/{}.ron", MAP_DIR, id), text).map_err(|e| e.to_string())
+    }
+
+    pub fn load(id: &str) -> Result<Self, String> {
+        let path = data_path(MAP_DIR).join(format!("{}.ron", id));
+        let text = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut blueprint: Self = ron::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if blueprint.version > MAP_VERSION {
+            return Err(format!("{}: made by a newer build (map version {})", path.display(), blueprint.version));
+        }
+        if blueprint.map_size <= 0 {
+            return Err(format!("{}: no map_size", path.display()));
+        }
+        blueprint.sort();
+        Ok(blueprint)
+    }
+
+    // Ids of the maps in MAP_DIR, alphabetically
+    pub fn list() -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(data_path(MAP_DIR)) else { return Vec::new(); };
+        let mut ids: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "ron"))
+            .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    // Closest tile that isn't water (ties go to the lowest position), so
+    // founders whose usual spot was painted over still start on land
+    pub fn dry_spot(&self, pos: (i32, i32), map_size: i32) -> (i32, i32) {
+        let water: HashSet<(i32, i32)> = self.water.iter().map(|&(p, _)| p).collect();
+        if !water.contains(&pos) { return pos; }
+        let mut best: Option<(i32, (i32, i32))> = None;
+        for x in -map_size..map_size {
+            for y in -map_size..map_size {
+                if water.contains(&(x, y)) { continue; }
+                let d = (x - pos.0).abs() + (y - pos.1).abs();
+                if best.is_none_or(|(bd, _)| d < bd) {
+                    best = Some((d, (x, y)));
+                }
+            }
+        }
+        best.map_or(pos, |(_, p)| p)
+    }
+}
+
+fn mix_rgb(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, a[2] + (b[2] - a[2]) * t]
+}
+
+// --- CREATURE TRANSFERS ---
+// One creature with everything a save keeps about it: what spawn_snapshot
+// brings back, what the clone tool copies, and what creature files
+// (CREATURE_DIR/<id>.ron) carry between worlds. A spawned record is a new
+// entity, so its biography and genealogy start over; only spawn_snapshot,
+// which has the whole list, gives it its mother back.
+pub const CREATURE_DIR: &str = "creatures";
+
+// Bump when CreatureRecord changes shape in a way serde defaults can't cover
+pub const CREATURE_VERSION: u32 = 1;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CreatureRecord {
+    #[serde(default)]
+    pub version: u32,
+    pub creature: SavedCreature,
+    #[serde(default)]
+    pub parasites: u32,
+    #[serde(default)]
+    pub wool: Option<f32>, // sheep only
+    #[serde(default)]
+    pub founder: String, // empty: its own line
+    #[serde(default)]
+    pub edited: bool,
+    #[serde(default)]
+    pub clade: Option<u32>, // derived species only, see Clade
+    #[serde(default)]
+    pub mother: Option<usize>, // index in the same snapshot; single records have none
+    #[serde(default)]
+    pub orphaned: bool, // mother died and nobody has adopted it yet
+    #[serde(default)]
+    pub fostered: bool,
+    #[serde(default)]
+    pub infected_left: Option<f32>, // seconds, see Infected
+}
+
+// Living creatures as CreatureRecords (part of SnapshotQueries)
+#[derive(SystemParam)]
+pub struct CreatureRecords<'w, 's> {
+    pub creatures: Query<'w, 's, (
+        &'static GridPosition,
+        &'static History,
+        &'static CreatureStats,
+        &'static Hunger,
+        &'static Age,
+        &'static Dna,
+        &'static Generation,
+        &'static CreatureName,
+        &'static CreatureBehavior,
+        Option<&'static ReproductionCooldown>,
+        Has<Digesting>,
+        Option<&'static Overfed>,
+        Option<&'static Parasites>,
+        Option<&'static Wool>,
+        (Option<&'static Founder>, Has<GeneEdited>, Option<&'static Clade>, Has<Fostered>, Option<&'static Mother>, Entity, Option<&'static Infected>),
+    ), (With<Creature>, Without<Dead>)>,
+}
+
+impl CreatureRecords<'_, '_> {
+    pub fn get(&self, entity: Entity) -> Option<CreatureRecord> {
+        self.creatures.get(entity).ok().map(CreatureRecord::capture)
+    }
+
+    // Mothers become places in the list, so they survive a save
+    pub fn all(&self) -> Vec<CreatureRecord> {
+        let index: HashMap<Entity, usize> = self.creatures.iter().enumerate().map(|(i, item)| (item.14.5, i)).collect();
+        self.creatures
+            .iter()
+            .map(|item| {
+                let mother = item.14.4.and_then(|m| index.get(&m.0).copied());
+                let orphaned = item.14.4.is_some() && mother.is_none();
+                CreatureRecord { mother, orphaned, ..CreatureRecord::capture(item) }
+            })
+            .collect()
+    }
+}
+
+impl CreatureRecord {
+    fn capture(
+        (pos, history, stats, hunger, age, dna, generation, name, behavior, cooldown, digesting, overfed, parasites, wool, (founder, edited, clade, fostered, _, _, infected)): (
+            &GridPosition,
+            &History,
+            &CreatureStats,
+            &Hunger,
+            &Age,
+            &Dna,
+            &Generation,
+            &CreatureName,
+            &CreatureBehavior,
+            Option<&ReproductionCooldown>,
+            bool,
+            Option<&Overfed>,
+            Option<&Parasites>,
+            Option<&Wool>,
+            (Option<&Founder>, bool, Option<&Clade>, bool, Option<&Mother>, Entity, Option<&Infected>),
+        ),
+    ) -> Self {
+        let timer_left = |t: &Timer| t.remaining_secs();
+        CreatureRecord {
+            version: CREATURE_VERSION,
+            creature: SavedCreature {
+                species_id: stats.species_id,
+                pos: (pos.x, pos.y),
+                last_pos: (history.last_x, history.last_y),
+                hunger: hunger.0,
+                age_seconds: age.seconds_alive,
+                is_adult: age.is_adult,
+                dna: *dna,
+                generation: generation.0,
+                name: name.0.clone(),
+                scared_of_water: behavior.scared_of_water,
+                altruistic: behavior.altruistic,
+                cooldown_left: cooldown.map(|c| timer_left(&c.0)),
+                digesting,
+                overfed_left: overfed.map(|o| timer_left(&o.0)),
+            },
+            parasites: parasites.map(|p| p.count).unwrap_or(0),
+            wool: wool.map(|w| w.0),
+            founder: founder.map(|f| f.0.clone()).unwrap_or_else(|| name.0.clone()),
+            edited,
+            clade: clade.map(|c| c.0),
+            mother: None,
+            orphaned: false,
+            fostered,
+            infected_left: infected.map(|i| i.0),
+        }
+    }
+
+    // The same creature standing on `pos` (with no step behind it)
+    pub fn at(mut self, pos: (i32, i32)) -> Self {
+        self.creature.pos = pos;
+        self.creature.last_pos = pos;
+        self
+    }
+
+    pub fn spawn(&self, commands: &mut Commands, cfg: &SimulationConfig) -> Entity {
+        let c = &self.creature;
+        let founder = if self.founder.is_empty() { &c.name } else { &self.founder };
+        let mut creature = commands.spawn((
+            Creature,
+            GridPosition { x: c.pos.0, y: c.pos.1 },
+            NextMove::default(),
+            Hunger(c.hunger),
+            CreatureStats { sight_range: c.dna.sight_range, species_id: c.species_id },
+            CreatureBehavior { scared_of_water: c.scared_of_water, altruistic: c.altruistic },
+            Age { seconds_alive: c.age_seconds, is_adult: c.is_adult, is_elder: c.is_adult && c.age_seconds > cfg.s(c.species_id).elder_seconds },
+            History::new(c.last_pos.0, c.last_pos.1),
+            c.dna,
+            Generation(c.generation),
+            Founder(founder.clone()),
+            StreamKey::new(&c.name, c.pos),
+            CreatureName(c.name.clone()),
+        ));
+        if let Some(left) = c.cooldown_left {
+            creature.insert(ReproductionCooldown(Timer::from_seconds(left.max(0.01), TimerMode::Once)));
+        }
+        if c.digesting { creature.insert(Digesting); }
+        if let Some(left) = c.overfed_left {
+            creature.insert(Overfed(Timer::from_seconds(left.max(0.01), TimerMode::Once)));
+        }
+        if self.parasites > 0 {
+            creature.insert(Parasites { count: self.parasites, groomed: 0.0 });
+        }
+        // Older saves leave sheep without wool; wool_growth_system gives them some
+        if let Some(wool) = self.wool.filter(|_| c.species_id == 0) {
+            creature.insert(Wool(wool));
+        }
+        if self.edited {
+            creature.insert(GeneEdited);
+        }
+        if let Some(clade) = self.clade {
+            creature.insert(Clade(clade));
+        }
+        if let Some(left) = self.infected_left {
+            creature.insert(Infected(left));
+        }
+        creature.id()
+    }
+
+    pub fn save(&self, id: &str) -> Result<std::path::PathBuf, String> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).map_err(|e| e.to_string())?;
+        write_data_file(&format!("{}/{}.ron", CREATURE_DIR, id), text).map_err(|e| e.to_string())
+    }
+
+    pub fn load(id: &str) -> Result<Self, String> {
+        let path = data_path(CREATURE_DIR).join(format!("{}.ron", id));
+        let text = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let record: Self = ron::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if record.version > CREATURE_VERSION {
+            return Err(format!("{}: made by a newer build (creature version {})", path.display(), record.version));
+        }
+        Ok(record)
+    }
+
+    // Ids of the creature files in CREATURE_DIR, alphabetically
+    pub fn list() -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(data_path(CREATURE_DIR)) else { return Vec::new(); };
+        let mut ids: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "ron"))
+            .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .collect();
+        ids.sort();
+        ids
+    }
+}
+
+impl WorldSnapshot {
+    // Saves from before version 12 keep creatures as SavedCreatures plus one
+    // list per later field (each may be missing); turn those into records
+    fn fold_creature_lists(mut self) -> Self {
+        let creatures = std::mem::take(&mut self.creatures);
+        for (i, creature) in creatures.into_iter().enumerate() {
+            let founder = self.creature_founders.get(i).cloned().unwrap_or_else(|| creature.name.clone());
+            self.creature_records.push(CreatureRecord {
+                version: CREATURE_VERSION,
+                parasites: self.creature_parasites.get(i).copied().unwrap_or(0),
+                wool: self.creature_wool.get(i).copied(),
+                founder,
+                edited: self.creature_edited.get(i).copied().unwrap_or(false),
+                clade: None,
+                mother: None,
+                orphaned: false,
+                fostered: false,
+                infected_left: None,
+                creature,
+            });
+        }
+        self.creature_parasites.clear();
+        self.creature_wool.clear();
+        self.creature_founders.clear();
+        self.creature_edited.clear();
+        self
+    }
+}
+
+// --- GHOST RUNS ---
+// Where every creature stood, a frame every step_seconds of sim time, so a run
+// can be replayed as ghosts over a later one from the same seed. One file per
+// seed in GHOST_DIR: the last run that seed had.
+pub const GHOST_DIR: &str = "ghosts";
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GhostRun {
+    pub seed: u64,
+    pub step_seconds: f32,
+    pub frames: Vec<Vec<(u32, (i32, i32))>>, // frame i is at i * step_seconds: (species_id, tile)
+}
+
+impl GhostRun {
+    pub fn new(seed: u64, step_seconds: f32) -> Self {
+        Self { seed, step_seconds: step_seconds.max(0.05), frames: Vec::new() }
+    }
+
+    pub fn frame_index(&self, seconds: f32) -> usize {
+        (seconds.max(0.0) / self.step_seconds.max(0.05)) as usize
+    }
+
+    // Called every frame; only stores a frame when the clock reaches the next
+    // one. Frames the clock skipped over get the current positions, and a clock
+    // that went back (a rewind or load) drops everything after it.
+    pub fn record(&mut self, seconds: f32, creatures: impl FnOnce() -> Vec<(u32, (i32, i32))>) {
+        let index = self.frame_index(seconds);
+        if index + 1 == self.frames.len() { return; }
+        self.frames.truncate(index);
+        let frame = creatures();
+        self.frames.resize(index + 1, frame);
+    }
+
+    // The frame for `seconds`; None past the end of the run
+    pub fn at(&self, seconds: f32) -> Option<&[(u32, (i32, i32))]> {
+        self.frames.get(self.frame_index(seconds)).map(|f| f.as_slice())
+    }
+
+    pub fn seconds(&self) -> f32 {
+        self.frames.len() as f32 * self.step_seconds
+    }
+
+    pub fn save(&self) -> Result<std::path::PathBuf, String> {
+        let text = ron::to_string(self).map_err(|e| e.to_string())?;
+        write_data_file(&format!("{}/{}.ron", GHOST_DIR, self.seed), text).map_err(|e| e.to_string())
+    }
+
+    pub fn load(seed: u64) -> Result<Self, String> {
+        let path = data_path(GHOST_DIR).join(format!("{}.ron", seed));
+        let text = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        ron::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+// Checked once per frame: any lose condition ends the run, otherwise all win
+// conditions together win it. Either way the clock stops on the results screen.
+pub fn evaluate_scenario(
+    game_stats: Res<GameStats>,
+    mut scenario: ResMut<ActiveScenario>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    q_creatures: Query<&CreatureStats, (With<Creature>, Without<Dead>)>,
+) {
+    let Some(run) = scenario.0.as_mut() else { return; };
+    if run.outcome.is_some() { return; }
+
+    let mut counts: HashMap<u32, u32> = HashMap::new();
+    for stats in q_creatures.iter() {
+        *counts.entry(stats.species_id).or_default() += 1;
+    }
+    for (&id, &n) in counts.iter() {
+        let peak = run.peak.entry(id).or_default();
+        *peak = (*peak).max(n);
+    }
+
+    let day = game_stats.days;
+    if run.def.lose.iter().any(|c| c.met(day, &counts)) {
+        run.outcome = Some(false);
+    } else if !run.def.win.is_empty() && run.def.win.iter().all(|c| c.met(day, &counts)) {
+        run.outcome = Some(true);
+    }
+
+    if let Some(won) = run.outcome {
+        println!("Scenario {}: {}", run.def.name, if won { "victory" } else { "defeat" });
+        virtual_time.pause();
+    }
+}
+
+// Scatter trees; each shades the tiles around its trunk
+pub fn spawn_trees(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    tile_index: Res<TileIndex>,
+    sim_rng: Res<SimRng>,
+    blueprint: Option<Res<MapBlueprint>>,
+) {
+    let mut rng = sim_rng.stream("trees");
+    let map_size = cfg.map_size;
+    let mut shaded: HashSet<(i32, i32)> = HashSet::new();
+
+    // A painted map's forest, else tree_count scattered at random
+    let trees: Vec<(i32, i32)> = match &blueprint {
+        Some(blueprint) => blueprint.trees.clone(),
+        None => (0..cfg.tree_count)
+            .map(|_| {
+                let x = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+                let y = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+                (x, y)
+            })
+            .collect(),
+    };
+    for (x, y) in trees {
+        commands.spawn((Tree, GridPosition { x, y }));
+
+        let r = cfg.shade_radius;
+        for dx in -r..=r {
+            for dy in -r..=r {
+                if dx.abs() + dy.abs() <= r {
+                    shaded.insert((x + dx, y + dy));
+                }
+            }
+        }
+    }
+
+    // Sorted: the order tiles change archetype decides later query order
+    let mut shaded: Vec<(i32, i32)> = shaded.into_iter().collect();
+    shaded.sort();
+    for pos in shaded {
+        if let Some(&tile) = tile_index.0.get(&pos) {
+            commands.entity(tile).insert(Shaded);
+        }
+    }
+}
+
+// Decide every creature's next step in parallel. Planning only reads a snapshot
+// of the world (plus each mover's own schedule), so it scales across cores;
+// positions change afterwards in apply_creature_moves.
+pub fn plan_creature_moves(
+    time: Res<Time>,
+    clock: Res<MoveClock>,
+    sim_rng: Res<SimRng>,
+    cfg: Res<SimulationConfig>,
+    weather: Res<Weather>,
+    behaviors: Res<SpeciesBehaviors>,
+    q_targets: Query<(Entity, &GridPosition, &CreatureStats, &Age, &Dna, Option<&ReproductionCooldown>, Option<&Clade>, &StreamKey), (With<Creature>, Without<Dead>)>,
+    mut q_movers: Query<(
+        (Entity, &StreamKey),
+        &GridPosition,
+        &mut NextMove,
+        &CreatureBehavior,
+        &CreatureStats,
+        Option<&ReproductionCooldown>,
+        &History,
+        Option<&Digesting>,
+        Option<&Overfed>,
+        Option<&mut BerryStun>,
+        &Hunger,
+        &Age,
+        &Dna,
+        (Has<Swimming>, Has<Wading>, Has<Sheltered>, Has<Fishing>, Has<Satiated>, Has<Desperate>, Has<Injured>, Option<&Mood>, Option<&Alarmed>, Option<&Mother>, Option<&Clade>, Option<&Memory>, Option<&GrazingMemory>, Option<&mut DecisionTrace>),
+        &mut MoveDecision,
+    ), (With<Creature>, Without<Dead>)>,
+    q_plants: Query<&GridPosition, With<Plant>>,
+    // Bridged water is walkable, so it doesn't count as water for scoring
+    q_water: Query<(&Tile, &Water, Option<&Pollution>), Without<Bridge>>,
+    q_shepherd: Query<&GridPosition, With<Shepherd>>,
+    q_fences: Query<&GridPosition, With<Fence>>,
+    q_gates: Query<(&GridPosition, &Gate)>,
+    q_trees: Query<&GridPosition, With<Tree>>,
+    q_huts: Query<(&GridPosition, &Shelter)>,
+    pheromones: Res<PheromoneField>,
+) {
+    struct CreatureSnapshot {
+        entity: Entity,
+        x: i32,
+        y: i32,
+        species: u32,
+        clade: u32,
+        is_adult: bool,
+        is_elder: bool,
+        on_cooldown: bool,
+        risky: bool,
+    }
+
+    // In StreamKey order: the first of several equal targets wins
+    let mut targets: Vec<_> = q_targets.iter().collect();
+    targets.sort_by_key(|(.., key)| key.0);
+    let creature_targets: Vec<CreatureSnapshot> = targets
+        .into_iter()
+        .map(|(e, pos, stats, age, dna, cooldown, clade, _)| CreatureSnapshot {
+            entity: e,
+            x: pos.x,
+            y: pos.y,
+            species: stats.species_id,
+            clade: clade_of(stats, clade),
+            is_adult: age.is_adult,
+            is_elder: age.is_elder,
+            on_cooldown: cooldown.is_some(),
+            risky: cfg.is_risky_prey(age, dna),
+        })
+        .collect();
+
+    let mut occupancy: HashMap<(i32, i32), u32> = HashMap::new();
+    for o in &creature_targets {
+        *occupancy.entry((o.x, o.y)).or_default() += 1;
+    }
+
+    let mut plant_positions: Vec<(i32, i32)> = q_plants.iter().map(|p| (p.x, p.y)).collect();
+    plant_positions.sort();
+    // (x, y) -> is deep
+    let water_tiles: HashMap<(i32, i32), bool> = q_water
+        .iter()
+        .map(|(t, w, _)| ((t.x, t.y), w.is_deep(&cfg)))
+        .collect();
+    let shore = shore_tiles(&fish_water(&q_water, &cfg), cfg.map_size);
+    let shepherd_pos: Option<(i32, i32)> = q_shepherd.iter().next().map(|p| (p.x, p.y));
+    let fences: HashSet<(i32, i32)> = q_fences.iter().map(|p| (p.x, p.y)).collect();
+    let storm = weather.kind == WeatherKind::Storm;
+    let trees: Vec<(i32, i32)> = if storm { q_trees.iter().map(|p| (p.x, p.y)).collect() } else { Vec::new() };
+    // Full huts aren't worth walking to
+    let open_huts: Vec<(i32, i32)> = if storm {
+        q_huts
+            .iter()
+            .filter(|(_, hut)| hut.occupants.len() < cfg.shelter_capacity as usize)
+            .map(|(p, _)| (p.x, p.y))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Walls per species: every fence, plus gates that species can't fit through
+    let blocked_by_species: HashMap<u32, HashSet<(i32, i32)>> = {
+        let gates: Vec<((i32, i32), Gate)> = q_gates
+            .iter()
+            .map(|(p, g)| ((p.x, p.y), *g))
+            .collect();
+        cfg.species
+            .keys()
+            .map(|&id| {
+                let mut blocked = fences.clone();
+                blocked.extend(gates.iter().filter(|(_, g)| !g.allows(id)).map(|(pos, _)| *pos));
+                (id, blocked)
+            })
+            .collect()
+    };
+    let no_walls: HashSet<(i32, i32)> = HashSet::new();
+    let frame_delta = time.delta();
+
+    q_movers.par_iter_mut().for_each(|(
+        (my_entity, my_key),
+        my_pos,
+        mut next_move,
+        behavior,
+        my_stats,
+        cooldown,
+        history,
+        digesting,
+        overfed,
+        berry_stun,
+        my_hunger,
+        my_age,
+        dna,
+        (swimming, wading, in_hut, fishing, satiated, desperate, injured, mood, alarmed, mother, clade, memory, grazed, mut trace),
+        mut decision,
+    )| {
+        decision.0 = None;
+
+        // --- BERRY STUN --- (apply_creature_moves removes it once it's over)
+        if let Some(mut stun) = berry_stun {
+            stun.0.tick(frame_delta);
+            if !stun.0.just_finished() {
+                trace_state(&mut trace, "Stunned by berries");
+                return;
+            }
+        }
+
+        if digesting.is_some() || fishing {
+            trace_state(&mut trace, if fishing { "Fishing" } else { "Digesting" });
+            return;
+        }
+
+        let mut move_seconds = dna.move_speed_seconds;
+        if cooldown.is_some() {
+            move_seconds = dna.move_speed_seconds * (cfg.reproduction_move_seconds / cfg.base_move_seconds);
+        }
+        if overfed.is_some() {
+            move_seconds = dna.move_speed_seconds * cfg.overfed_move_multiplier;
+        }
+        if swimming {
+            move_seconds *= cfg.swim_move_multiplier;
+        } else if wading {
+            move_seconds *= cfg.wading_move_multiplier;
+        }
+        if my_age.is_elder {
+            move_seconds *= cfg.elder_move_multiplier;
+        }
+        if injured {
+            move_seconds *= cfg.injured_move_multiplier;
+        }
+        move_seconds /= cfg.s(my_stats.species_id).time_scale.max(0.01);
+
+        // Only written when booking a step, so waiting movers stay unchanged
+        let interval = MoveClock::ticks_for(&cfg, move_seconds);
+        if !clock.is_due(&next_move) {
+            // First slot at a random phase within one interval, so creatures spawned
+            // together (founders, snapshot loads) don't all step on the same frames
+            if next_move.0.is_none() {
+                let phase = sim_rng.stream_for("move_phase", my_key.0).random::<u64>() % interval;
+                *next_move = clock.after(1 + phase);
+            }
+            return;
+        }
+        *next_move = clock.after(interval);
+
+        // === TARGET SELECTION ===
+        // Interpret this species' behavior rules (first match wins)
+        let mut target_pos: Option<(i32, i32)> = None;
+        let mut target_goal: Option<Goal> = None;
+        let mut target_weight: i32 = 20;
+        let mut chosen_rule: Option<&BehaviorRule> = None;
+
+        let is_sheep = my_stats.species_id == 0;
+        let is_wolf = my_stats.species_id == 1;
+
+        let hunger_level = my_hunger.0;
+        // Starving hunters have no thought of mates
+        let can_breed = my_age.is_adult && !my_age.is_elder && cooldown.is_none() && overfed.is_none() && !desperate;
+        let sight_range = cfg.sight_for(my_stats, my_age);
+
+        // Nearest candidate strictly further than `min_dist` and closer than `range`
+        let nearest_within = |candidates: &mut dyn Iterator<Item = (i32, i32)>, min_dist: i32, range: i32| -> Option<(i32, i32)> {
+            let mut best: Option<((i32, i32), i32)> = None;
+            for (x, y) in candidates {
+                let dist = (my_pos.x - x).abs() + (my_pos.y - y).abs();
+                if dist > min_dist && dist < range && best.map(|(_, d)| dist < d).unwrap_or(true) {
+                    best = Some(((x, y), dist));
+                }
+            }
+            best.map(|(pos, _)| pos)
+        };
+        let nearest = |candidates: &mut dyn Iterator<Item = (i32, i32)>, min_dist: i32| nearest_within(candidates, min_dist, sight_range);
+
+        // Memory brains head back to where they last ate, unless they're stood on it
+        let intelligence = cfg.s(my_stats.species_id).intelligence;
+        let recall_food = || memory.and_then(|m| m.food).map(|(spot, _)| spot).filter(|&spot| spot != (my_pos.x, my_pos.y));
+
+        for rule in &behaviors.get(my_stats.species_id).rules {
+            if !rule.applies(hunger_level, my_age.is_adult) { continue; }
+
+            let found = match rule.goal {
+                Goal::Food => nearest(&mut plant_positions.iter().copied(), 0).or_else(&recall_food),
+                Goal::Mate => {
+                    if !can_breed { continue; }
+                    nearest(
+                        &mut creature_targets
+                            .iter()
+                            .filter(|o| o.entity != my_entity && o.species == my_stats.species_id && o.clade == clade_of(my_stats, clade))
+                            .filter(|o| !o.on_cooldown && !o.is_elder && (o.is_adult || !rule.target_adults_only))
+                            .map(|o| (o.x, o.y)),
+                        1,
+                    )
+                }
+                Goal::Shelter => {
+                    if !storm { continue; }
+                    // Already under cover: sit the storm out
+                    if in_hut || is_sheltered((my_pos.x, my_pos.y), &trees, &cfg) {
+                        trace_state(&mut trace, "Sheltering from the storm");
+                        return;
+                    }
+                    nearest(&mut trees.iter().chain(open_huts.iter()).copied(), 0)
+                }
+                Goal::Fish => {
+                    // On the shore already: wait there for fishing_system
+                    if shore.contains(&(my_pos.x, my_pos.y)) {
+                        trace_state(&mut trace, "Waiting to fish");
+                        return;
+                    }
+                    nearest(&mut shore.iter().copied(), 0)
+                }
+                Goal::Mother => {
+                    if my_age.is_adult { continue; }
+                    // Orphans stick to the nearest adult of their kind instead
+                    let mother_pos = mother.and_then(|m| creature_targets.iter().find(|o| o.entity == m.0)).map(|o| (o.x, o.y));
+                    let guardian = mother_pos.or_else(|| nearest(
+                        &mut creature_targets
+                            .iter()
+                            .filter(|o| o.entity != my_entity && o.species == my_stats.species_id && o.is_adult)
+                            .map(|o| (o.x, o.y)),
+                        0,
+                    ));
+                    match guardian {
+                        // Close enough: the other rules decide
+                        Some((x, y)) if (my_pos.x - x).abs() + (my_pos.y - y).abs() <= cfg.baby_follow_distance => continue,
+                        found => found,
+                    }
+                }
+                Goal::Prey if !cfg.wants_prey(hunger_level, satiated) => continue,
+                // Desperate hunters pick up prey by scent beyond sight, and go for risky prey too
+                Goal::Prey | Goal::Flee => nearest_within(
+                    &mut creature_targets
+                        .iter()
+                        .filter(|o| o.entity != my_entity && rule.species.contains(&o.species))
+                        .filter(|o| o.is_adult || !rule.target_adults_only)
+                        .filter(|o| rule.goal == Goal::Flee || desperate || !o.risky)
+                        .map(|o| (o.x, o.y)),
+                    -1,
+                    if rule.goal == Goal::Prey && desperate { sight_range + cfg.desperate_scent_range } else { sight_range },
+                )
+                .or_else(|| {
+                    // Nothing in sight, but someone called out about it
+                    alarmed
+                        .filter(|a| rule.goal == Goal::Flee && rule.species.contains(&a.threat_species))
+                        .map(|a| a.threat)
+                })
+                .or_else(|| match rule.goal {
+                    Goal::Prey => recall_food(),
+                    // Keep clear of where it saw a kill, while that's in sight
+                    _ => memory
+                        .and_then(|m| m.danger)
+                        .map(|(spot, _)| spot)
+                        .filter(|&(x, y)| (my_pos.x - x).abs() + (my_pos.y - y).abs() < sight_range),
+                }),
+            };
+
+            if let Some(pos) = found {
+                target_pos = Some(pos);
+                target_goal = Some(rule.goal);
+                target_weight = rule.weight;
+                chosen_rule = Some(rule);
+                break;
+            }
+        }
+
+        // Shepherd pull/push on top of the usual target
+        // (sheep only follow when not already fleeing a wolf)
+        let shepherd_dist = shepherd_pos.map(|(sx, sy)| (my_pos.x - sx).abs() + (my_pos.y - sy).abs());
+        let follow_shepherd = is_sheep
+            && target_goal != Some(Goal::Flee)
+            && shepherd_dist.map(|d| d > 1 && d <= cfg.shepherd_follow_radius).unwrap_or(false);
+        let avoid_shepherd = is_wolf
+            && shepherd_dist.map(|d| d <= cfg.shepherd_scare_radius).unwrap_or(false);
+
+        // With fences around, straight-line distance lies; measure the walk instead
+        // (greedy movers don't, and get stuck behind them)
+        let blocked = blocked_by_species.get(&my_stats.species_id).unwrap_or(&no_walls);
+        let path_field = match target_pos {
+            Some(goal) if !blocked.is_empty() && intelligence != Intelligence::Greedy => {
+                Some(path_distance_field(goal, blocked, cfg.map_size, sight_range * 2))
+            }
+            _ => None,
+        };
+        let target_dist = |x: i32, y: i32, (tx, ty): (i32, i32)| -> i32 {
+            match &path_field {
+                Some(field) => field.get(&(x, y)).copied().unwrap_or(sight_range * 2 + 1),
+                None => (x - tx).abs() + (y - ty).abs(),
+            }
+        };
+
+        // Fleeing weighs every threat in sight, not just the nearest (an alarm
+        // or a remembered kill is the only threat when none is in sight)
+        let mut threats: Vec<((i32, i32), i32)> = Vec::new();
+        if let Some(rule) = chosen_rule.filter(|r| r.goal == Goal::Flee) {
+            for o in creature_targets.iter().filter(|o| o.entity != my_entity && rule.species.contains(&o.species)) {
+                if !o.is_adult && rule.target_adults_only { continue; }
+                let dist = (my_pos.x - o.x).abs() + (my_pos.y - o.y).abs();
+                if dist < sight_range {
+                    threats.push(((o.x, o.y), dist));
+                }
+            }
+            if threats.is_empty() {
+                if let Some((tx, ty)) = target_pos {
+                    threats.push(((tx, ty), (my_pos.x - tx).abs() + (my_pos.y - ty).abs()));
+                }
+            }
+        }
+        let closest_threat = threats.iter().map(|&(_, d)| d.max(1)).min().unwrap_or(1);
+
+        // ...and steers clear of dead ends: water it can't swim, fences and corners
+        // (greedy movers just run)
+        let flee_walls: Option<HashSet<(i32, i32)>> = (!threats.is_empty() && intelligence != Intelligence::Greedy).then(|| {
+            let can_swim = cfg.s(my_stats.species_id).can_swim;
+            blocked
+                .iter()
+                .copied()
+                .chain(water_tiles.iter().filter(|&(_, &deep)| deep && !can_swim).map(|(&pos, _)| pos))
+                .collect()
+        });
+
+        // === MOVE EVALUATION ===
+        // Own stream per creature: threads finish in any order
+        let mut rng = sim_rng.stream_for("moves", my_key.0);
+        let noise = cfg.mood_move_noise(mood.map(|m| m.kind).unwrap_or_default());
+        let mut best_move = (0, 0);
+        let mut best_score = -9999_i32;
+        let mut scores = [None; 4];
+
+        for (i, (dx, dy)) in MOVE_DIRECTIONS.into_iter().enumerate() {
+            let nx = my_pos.x + dx;
+            let ny = my_pos.y + dy;
+
+            if nx < -cfg.map_size || nx >= cfg.map_size || ny < -cfg.map_size || ny >= cfg.map_size {
+                continue;
+            }
+
+            // Fences (and gates for the wrong species) are walls
+            if blocked.contains(&(nx, ny)) {
+                continue;
+            }
+
+            let mut score = rng.random::<i32>() % noise;
+
+            if behavior.scared_of_water {
+                match water_tiles.get(&(nx, ny)) {
+                    // Swimmers only mildly avoid deep water; everyone else treats it as death
+                    Some(true) => {
+                        score -= if cfg.s(my_stats.species_id).can_swim { cfg.swim_score_penalty } else { 1000 };
+                    }
+                    Some(false) => score -= cfg.wading_score_penalty,
+                    None => {}
+                }
+            }
+
+            if nx == history.last_x && ny == history.last_y {
+                score -= 30;
+            }
+
+            // Spread the grazing: bare patches wait until they've grown back
+            if grazed.is_some_and(|g| g.0.contains(&(nx, ny))) {
+                score -= cfg.grazed_tile_penalty;
+            }
+
+            // Full tiles only for a hunter closing in on its prey
+            let onto_prey = target_goal == Some(Goal::Prey) && target_pos == Some((nx, ny));
+            if !onto_prey && cfg.tile_full(occupancy.get(&(nx, ny)).copied().unwrap_or(0)) {
+                score -= cfg.crowded_tile_penalty;
+            }
+
+            for scent in behaviors.get(my_stats.species_id).scents.iter().filter(|s| s.weight != 0.0 && s.applies(hunger_level)) {
+                score += (pheromones.get((nx, ny), scent.channel) * scent.weight) as i32;
+            }
+
+            if let Some(target) = target_pos {
+                let dist_now = target_dist(my_pos.x, my_pos.y, target);
+                let dist_after = target_dist(nx, ny, target);
+                let delta = dist_after - dist_now;
+
+                match target_goal {
+                    // Closer threats push harder: the nearest at full weight
+                    Some(Goal::Flee) => {
+                        for &((tx, ty), dist) in &threats {
+                            let threat_delta = ((nx - tx).abs() + (ny - ty).abs()) - dist;
+                            score += threat_delta * target_weight * closest_threat / dist.max(1);
+                        }
+                    }
+                    Some(_) => score -= delta * target_weight,
+                    None => {}
+                }
+            }
+
+            if let Some(walls) = &flee_walls {
+                let room = path_distance_field((nx, ny), walls, cfg.map_size, cfg.flee_lookahead).len() as i32;
+                score += room * cfg.flee_room_weight;
+            }
+
+            if let Some((sx, sy)) = shepherd_pos {
+                let dist_now = (my_pos.x - sx).abs() + (my_pos.y - sy).abs();
+                let dist_after = (nx - sx).abs() + (ny - sy).abs();
+                let delta = dist_after - dist_now;
+
+                if follow_shepherd {
+                    score -= delta * cfg.shepherd_follow_weight;
+                }
+                if avoid_shepherd {
+                    score += delta * cfg.shepherd_scare_weight;
+                }
+            }
+
+            scores[i] = Some(score);
+            if score > best_score {
+                best_score = score;
+                best_move = (dx, dy);
+            }
+        }
+
+        if let Some(trace) = trace.as_mut() {
+            let path = target_pos
+                .filter(|_| target_goal != Some(Goal::Flee))
+                .map(|goal| planned_path((my_pos.x, my_pos.y), goal, path_field.as_ref(), sight_range * 2))
+                .unwrap_or_default();
+            // Having taken a step along the old path, the new one should be the rest of it
+            let carried_on = trace.path.iter().skip_while(|&&p| p != (my_pos.x, my_pos.y)).skip(1).eq(path.iter());
+            let replanned = !trace.path.is_empty() && !path.is_empty() && !carried_on;
+            **trace = DecisionTrace {
+                state: if target_goal.is_some() { "Heading for its target" } else { "Wandering" },
+                goal: target_goal,
+                target: target_pos,
+                scores,
+                chosen: Some(best_move),
+                path,
+                replans: trace.replans + replanned as u32,
+            };
+        }
+        decision.0 = Some(best_move);
+    });
+}
+
+pub fn apply_creature_moves(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    mut q_movers: Query<(&mut GridPosition, &mut History, &mut MoveDecision), (With<Creature>, Without<Dead>)>,
+    q_stunned: Query<(Entity, &BerryStun)>,
+) {
+    // Here rather than in the parallel planner, so removals happen in a fixed order
+    for (entity, stun) in q_stunned.iter() {
+        if stun.0.is_finished() {
+            commands.entity(entity).remove::<BerryStun>();
+        }
+    }
+
+    for (mut pos, mut history, mut decision) in q_movers.iter_mut() {
+        let Some((dx, dy)) = decision.0.take() else { continue; };
+
+        history.record(pos.x, pos.y, cfg.trail_length);
+        pos.x += dx;
+        pos.y += dy;
+    }
+}
+
+// --- TELEPORTS ---
+// Debug moves that skip the walk (the app's drag and scatter): the creature
+// stands on `to` with no step pending and no trail behind it, so nothing
+// downstream mistakes the jump for a step.
+pub fn teleport(pos: &mut GridPosition, history: &mut History, decision: &mut MoveDecision, (x, y): (i32, i32)) {
+    pos.x = x;
+    pos.y = y;
+    *history = History::new(x, y);
+    decision.0 = None;
+}
+
+// A random tile from `land` for each of `count` creatures: all different while
+// there's room, then the land is dealt out again
+pub fn scatter_spots(rng: &mut impl Rng, land: &[(i32, i32)], count: usize) -> Vec<(i32, i32)> {
+    if land.is_empty() { return Vec::new(); }
+    let mut deck: Vec<(i32, i32)> = Vec::new();
+    let mut spots = Vec::with_capacity(count);
+    while spots.len() < count {
+        if deck.is_empty() {
+            deck = land.to_vec();
+        }
+        let i = (rng.random::<u32>() as usize) % deck.len();
+        spots.push(deck.swap_remove(i));
+    }
+    spots
+}
+
+// --- PHEROMONES ---
+// Every step lays the mover's scents on the tile it stepped onto
+pub fn pheromone_deposit_system(
+    cfg: Res<SimulationConfig>,
+    behaviors: Res<SpeciesBehaviors>,
+    mut pheromones: ResMut<PheromoneField>,
+    q_movers: Query<(&GridPosition, &CreatureStats, &Hunger), (With<Creature>, Without<Dead>, Changed<GridPosition>)>,
+) {
+    for (pos, stats, hunger) in q_movers.iter() {
+        for scent in behaviors.get(stats.species_id).scents.iter().filter(|s| s.deposit > 0.0 && s.applies(hunger.0)) {
+            pheromones.deposit((pos.x, pos.y), scent.channel, scent.deposit, cfg.pheromone_max);
+        }
+    }
+}
+
+pub fn pheromone_decay_system(time: Res<Time>, cfg: Res<SimulationConfig>, mut pheromones: ResMut<PheromoneField>) {
+    let keep = (1.0 - cfg.pheromone_decay_per_second * time.delta_secs()).max(0.0);
+    pheromones.0.retain(|_, levels| {
+        for level in levels.iter_mut() {
+            *level *= keep;
+        }
+        levels.iter().any(|&level| level > 0.01)
+    });
+}
+
+// --- PATHFINDING ---
+
+// BFS walking distance from `goal` to every tile within `max_dist` steps,
+// treating `blocked` tiles as walls.
+pub fn path_distance_field(
+    goal: (i32, i32),
+    blocked: &HashSet<(i32, i32)>,
+    map_size: i32,
+    max_dist: i32,
+) -> HashMap<(i32, i32), i32> {
+    let mut dist: HashMap<(i32, i32), i32> = HashMap::new();
+    let mut frontier: VecDeque<(i32, i32)> = VecDeque::new();
+    dist.insert(goal, 0);
+    frontier.push_back(goal);
+
+    while let Some((x, y)) = frontier.pop_front() {
+        let d = dist[&(x, y)];
+        if d >= max_dist { continue; }
+
+        for (dx, dy) in [(0, 1), (0, -1), (-1, 0), (1, 0)] {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx < -map_size || nx >= map_size || ny < -map_size || ny >= map_size {
+                continue;
+            }
+            if blocked.contains(&(nx, ny)) || dist.contains_key(&(nx, ny)) { continue; }
+            dist.insert((nx, ny), d + 1);
+            frontier.push_back((nx, ny));
+        }
+    }
+
+    dist
+}
+
+// The walk from `from` to `goal`, next step first. Follows a distance field
+// downhill when there is one (first direction wins ties, so it's stable);
+// across open ground it's a straight staircase. Empty if the field doesn't
+// reach `from`; never longer than `max_steps`.
+pub fn planned_path(
+    from: (i32, i32),
+    goal: (i32, i32),
+    field: Option<&HashMap<(i32, i32), i32>>,
+    max_steps: i32,
+) -> Vec<(i32, i32)> {
+    let mut path = Vec::new();
+    let mut at = from;
+    while at != goal && (path.len() as i32) < max_steps {
+        let next = match field {
+            Some(field) => {
+                let Some(&d) = field.get(&at) else { break; };
+                match MOVE_DIRECTIONS
+                    .iter()
+                    .map(|&(dx, dy)| (at.0 + dx, at.1 + dy))
+                    .find(|p| field.get(p) == Some(&(d - 1)))
+                {
+                    Some(p) => p,
+                    None => break,
+                }
+            }
+            None => {
+                let (dx, dy) = (goal.0 - at.0, goal.1 - at.1);
+                if dx.abs() >= dy.abs() { (at.0 + dx.signum(), at.1) } else { (at.0, at.1 + dy.signum()) }
+            }
+        };
+        path.push(next);
+        at = next;
+    }
+    path
+}
+
+// Every unbridged water tile, and whether it holds fish (deep and not too foul)
+pub fn fish_water(
+    q_water: &Query<(&Tile, &Water, Option<&Pollution>), Without<Bridge>>,
+    cfg: &SimulationConfig,
+) -> HashMap<(i32, i32), bool> {
+    q_water
+        .iter()
+        .map(|(t, w, pollution)| ((t.x, t.y), w.is_deep(cfg) && !pollution.is_some_and(|p| p.is_toxic(cfg))))
+        .collect()
+}
+
+// Dry, unbridged land next to deep water, in a fixed order
+pub fn shore_tiles(water_tiles: &HashMap<(i32, i32), bool>, map_size: i32) -> Vec<(i32, i32)> {
+    let mut shore: Vec<(i32, i32)> = water_tiles
+        .iter()
+        .filter(|&(_, &deep)| deep)
+        .flat_map(|(&(x, y), _)| [(x, y + 1), (x, y - 1), (x - 1, y), (x + 1, y)])
+        .filter(|&(x, y)| x >= -map_size && x < map_size && y >= -map_size && y < map_size)
+        .filter(|pos| !water_tiles.contains_key(pos))
+        .collect();
+    shore.sort();
+    shore.dedup();
+    shore
+}
+
+// Creatures on the shore whose Fish rule applies, with none of their prey in sight,
+// settle down to fish; each attempt takes `fishing_seconds` and lands a meal with
+// `fishing_success_chance`
+pub fn fishing_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    behaviors: Res<SpeciesBehaviors>,
+    sim_rng: Res<SimRng>,
+    q_water: Query<(&Tile, &Water, Option<&Pollution>), Without<Bridge>>,
+    mut q_fishers: Query<(Entity, &StreamKey, &GridPosition, &mut Hunger, &CreatureStats, &Age, Option<&mut Fishing>), (With<Creature>, Without<Dead>, Without<Digesting>)>,
+    q_creatures: Query<(&GridPosition, &CreatureStats), (With<Creature>, Without<Dead>)>,
+) {
+    let shore: HashSet<(i32, i32)> = shore_tiles(&fish_water(&q_water, &cfg), cfg.map_size).into_iter().collect();
+    let others: Vec<((i32, i32), u32)> = q_creatures.iter().map(|(p, s)| ((p.x, p.y), s.species_id)).collect();
+
+    for (entity, key, pos, mut hunger, stats, age, fishing) in q_fishers.iter_mut() {
+        let Some(mut fishing) = fishing else {
+            if !behaviors.fishes(stats.species_id, hunger.0, age.is_adult) { continue; }
+            if !shore.contains(&(pos.x, pos.y)) { continue; }
+            let prey_in_sight = others.iter().any(|&((x, y), species)| {
+                behaviors.hunts(stats.species_id, species) && (pos.x - x).abs() + (pos.y - y).abs() < stats.sight_range
+            });
+            if !prey_in_sight {
+                commands.entity(entity).insert(Fishing(Timer::from_seconds(cfg.fishing_seconds, TimerMode::Once)));
+            }
+            continue;
+        };
+
+        fishing.0.tick(time.delta());
+        if !fishing.0.is_finished() { continue; }
+
+        commands.entity(entity).remove::<Fishing>();
+        if sim_rng.stream_for("fishing", key.0).random::<f32>() < cfg.fishing_success_chance {
+            hunger.0 = (hunger.0 - cfg.fishing_meal).max(0.0);
+        }
+    }
+}
+
+// Close enough to a tree to be out of the worst of a storm
+pub fn is_sheltered((x, y): (i32, i32), shelters: &[(i32, i32)], cfg: &SimulationConfig) -> bool {
+    shelters.iter().any(|&(sx, sy)| (x - sx).abs() + (y - sy).abs() <= cfg.shelter_radius)
+}
+
+// Storms now and then strike a creature caught out in the open
+pub fn lightning_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    weather: Res<Weather>,
+    sim_rng: Res<SimRng>,
+    q_creatures: Query<(Entity, &StreamKey, &GridPosition, &CreatureStats, &CreatureName, &Generation, &Dna, Has<Sheltered>), (With<Creature>, Without<Dead>)>,
+    q_trees: Query<&GridPosition, With<Tree>>,
+    mut deaths: MessageWriter<DeathEvent>,
+) {
+    if weather.kind != WeatherKind::Storm { return; }
+
+    let chance = cfg.lightning_kill_chance * time.delta_secs();
+    let shelters: Vec<(i32, i32)> = q_trees.iter().map(|p| (p.x, p.y)).collect();
+
+    for (entity, key, pos, stats, name, generation, dna, in_hut) in q_creatures.iter() {
+        if in_hut || is_sheltered((pos.x, pos.y), &shelters, &cfg) { continue; }
+        if sim_rng.stream_for("lightning", key.0).random::<f32>() >= chance { continue; }
+
+        commands.entity(entity).insert(Dead { cause: DeathCause::Lightning });
+        deaths.write(DeathEvent {
+            entity,
+            species_id: stats.species_id,
+            name: name.0.clone(),
+            generation: generation.0,
+            dna: *dna,
+            pos: (pos.x, pos.y),
+            cause: DeathCause::Lightning,
+        });
+    }
+}
+
+// Huts fill first come, first served: occupants keep their spot while they stay
+// next to it, and free spots go to the nearest newcomers
+pub fn shelter_occupancy_system(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    mut q_huts: Query<(&GridPosition, &mut Shelter)>,
+    q_creatures: Query<(Entity, &GridPosition, Has<Sheltered>), (With<Creature>, Without<Dead>)>,
+) {
+    let dist = |a: &GridPosition, b: &GridPosition| (a.x - b.x).abs() + (a.y - b.y).abs();
+    let capacity = cfg.shelter_capacity as usize;
+
+    // In a fixed order, so huts next to each other share creatures out the same way every run
+    let mut huts: Vec<_> = q_huts.iter_mut().collect();
+    huts.sort_by_key(|(pos, _)| (pos.x, pos.y));
+
+    let mut inside: HashSet<Entity> = HashSet::new();
+    for (hut_pos, mut hut) in huts {
+        hut.occupants.retain(|&e| {
+            !inside.contains(&e) && q_creatures.get(e).is_ok_and(|(_, pos, _)| dist(hut_pos, pos) <= cfg.shelter_radius)
+        });
+        hut.occupants.truncate(capacity);
+
+        let free = capacity - hut.occupants.len();
+        if free > 0 {
+            let mut newcomers: Vec<(i32, (i32, i32), Entity)> = q_creatures
+                .iter()
+                .filter(|(e, pos, _)| dist(hut_pos, pos) <= cfg.shelter_radius && !inside.contains(e) && !hut.occupants.contains(e))
+                .map(|(e, pos, _)| (dist(hut_pos, pos), (pos.x, pos.y), e))
+                .collect();
+            newcomers.sort_by_key(|&(d, pos, _)| (d, pos));
+            hut.occupants.extend(newcomers.into_iter().take(free).map(|(_, _, e)| e));
+        }
+        inside.extend(hut.occupants.iter().copied());
+    }
+
+    for (entity, _, sheltered) in q_creatures.iter() {
+        match (inside.contains(&entity), sheltered) {
+            (true, false) => { commands.entity(entity).insert(Sheltered); }
+            (false, true) => { commands.entity(entity).remove::<Sheltered>(); }
+            _ => {}
+        }
+    }
+}
+
+// --- MOOD ---
+// Moods wear off, then this frame's events set new ones. Grief outranks stress.
+pub fn mood_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    mut lineage: ResMut<Lineage>,
+    mut births: MessageReader<BirthEvent>,
+    mut deaths: MessageReader<DeathEvent>,
+    mut eats: MessageReader<EatEvent>,
+    mut q_creatures: Query<(Entity, &GridPosition, &CreatureStats, &Hunger, Option<&mut Mood>), (With<Creature>, Without<Dead>)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, _, _, _, mood) in q_creatures.iter_mut() {
+        match mood {
+            Some(mut mood) => {
+                mood.left -= dt;
+                if mood.left <= 0.0 {
+                    *mood = Mood::default();
+                }
+            }
+            None => { commands.entity(entity).insert(Mood::default()); }
+        }
+    }
+
+    let feel = |mood: &mut Mood, kind: MoodKind, seconds: f32| {
+        if mood.kind == MoodKind::Grieving && kind == MoodKind::Stressed { return; }
+        mood.kind = kind;
+        mood.left = mood.left.max(seconds);
+    };
+
+    for e in births.read() {
+        lineage.0.insert(e.entity, e.parents);
+    }
+
+    for e in deaths.read() {
+        // Lost a child
+        if let Some((a, b)) = lineage.0.remove(&e.entity) {
+            for parent in [a, b] {
+                if let Ok((_, _, _, _, Some(mut mood))) = q_creatures.get_mut(parent) {
+                    feel(&mut mood, MoodKind::Grieving, cfg.mood_grief_seconds);
+                }
+            }
+        }
+
+        // Saw one of its own get eaten
+        if e.cause != DeathCause::Predation { continue; }
+        for (_, pos, stats, _, mood) in q_creatures.iter_mut() {
+            let Some(mut mood) = mood else { continue; };
+            let dist = (pos.x - e.pos.0).abs() + (pos.y - e.pos.1).abs();
+            if stats.species_id == e.species_id && dist <= stats.sight_range {
+                feel(&mut mood, MoodKind::Stressed, cfg.mood_stress_seconds);
+            }
+        }
+    }
+
+    // A good meal calms a stressed creature down
+    for e in eats.read() {
+        if let Ok((_, _, _, hunger, Some(mut mood))) = q_creatures.get_mut(e.entity) {
+            if mood.kind == MoodKind::Stressed && hunger.0 <= cfg.mood_well_fed_hunger {
+                *mood = Mood::default();
+            }
+        }
+    }
+}
+
+// --- ADOPTION ---
+// Babies whose mother has died get taken in by the nearest altruistic adult of
+// their kind. Founders never had a mother, so they aren't orphans.
+pub fn adoption_system(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    mut adoptions: MessageWriter<AdoptionEvent>,
+    q_creatures: Query<(Entity, &GridPosition, &CreatureStats, &Age, &CreatureBehavior, &CreatureName, &Generation, &Dna, Option<&Mother>), (With<Creature>, Without<Dead>)>,
+) {
+    for (baby, pos, stats, age, _, name, generation, dna, mother) in q_creatures.iter() {
+        if age.is_adult { continue; }
+        let Some(mother) = mother else { continue; };
+        if q_creatures.contains(mother.0) { continue; }
+
+        let foster = q_creatures
+            .iter()
+            .filter(|(_, _, s, a, behavior, ..)| s.species_id == stats.species_id && a.is_adult && behavior.altruistic)
+            .map(|(entity, p, _, _, _, foster_name, ..)| ((p.x - pos.x).abs() + (p.y - pos.y).abs(), (p.x, p.y), entity, foster_name))
+            .filter(|(dist, ..)| *dist <= cfg.adoption_radius)
+            .min_by_key(|&(dist, p, ..)| (dist, p));
+
+        if let Some((_, _, foster, foster_name)) = foster {
+            commands.entity(baby).insert((Mother(foster), Fostered));
+            adoptions.write(AdoptionEvent {
+                baby,
+                foster,
+                species_id: stats.species_id,
+                name: name.0.clone(),
+                foster_name: foster_name.0.clone(),
+                generation: generation.0,
+                dna: *dna,
+            });
+        }
+    }
+}
+
+// Foster parents share every meal (plants or prey) with the babies next to them
+pub fn foster_feeding_system(
+    cfg: Res<SimulationConfig>,
+    mut eats: MessageReader<EatEvent>,
+    mut kills: MessageReader<KillEvent>,
+    q_parents: Query<&GridPosition, (With<Creature>, Without<Dead>)>,
+    mut q_babies: Query<(&GridPosition, &Mother, &mut Hunger), (With<Fostered>, Without<Dead>)>,
+) {
+    let eaters: Vec<Entity> = eats.read().map(|e| e.entity).chain(kills.read().map(|k| k.predator)).collect();
+    if eaters.is_empty() { return; }
+
+    for (pos, mother, mut hunger) in q_babies.iter_mut() {
+        if !eaters.contains(&mother.0) { continue; }
+        let Ok(parent) = q_parents.get(mother.0) else { continue; };
+        if (pos.x - parent.x).abs() + (pos.y - parent.y).abs() <= cfg.baby_follow_distance {
+            hunger.0 = (hunger.0 - cfg.foster_meal).max(0.0);
+        }
+    }
+}
+
+// --- MEMORY BRAIN ---
+// Only species with Intelligence::Memory carry a Memory: it's added and taken
+// away here as the config says. Eating (or killing) marks a food spot; a kill
+// within sight marks a danger spot for everyone not of the killer's kind.
+pub fn memory_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    mut eats: MessageReader<EatEvent>,
+    mut kills: MessageReader<KillEvent>,
+    mut q_creatures: Query<(Entity, &GridPosition, &CreatureStats, &Age, Option<&mut Memory>), (With<Creature>, Without<Dead>)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, _, stats, _, memory) in q_creatures.iter_mut() {
+        let wants = cfg.s(stats.species_id).intelligence == Intelligence::Memory;
+        match memory {
+            Some(_) if !wants => { commands.entity(entity).remove::<Memory>(); }
+            None if wants => { commands.entity(entity).insert(Memory::default()); }
+            Some(mut memory) => {
+                let memory = &mut *memory;
+                for slot in [&mut memory.food, &mut memory.danger] {
+                    if let Some((_, left)) = slot {
+                        *left -= dt;
+                        if *left <= 0.0 { *slot = None; }
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    let remember = cfg.memory_seconds;
+    for e in eats.read() {
+        if let Ok((.., Some(mut memory))) = q_creatures.get_mut(e.entity) {
+            memory.food = Some((e.pos, remember));
+        }
+    }
+    for e in kills.read() {
+        let Ok((_, _, killer, ..)) = q_creatures.get(e.predator) else { continue; };
+        let killer_species = killer.species_id;
+        if let Ok((.., Some(mut memory))) = q_creatures.get_mut(e.predator) {
+            memory.food = Some((e.pos, remember));
+        }
+        for (_, pos, stats, age, memory) in q_creatures.iter_mut() {
+            let Some(mut memory) = memory else { continue; };
+            if stats.species_id == killer_species { continue; }
+            if (pos.x - e.pos.0).abs() + (pos.y - e.pos.1).abs() < cfg.sight_for(stats, age) {
+                memory.danger = Some((e.pos, remember));
+            }
+        }
+    }
+}
+
+// Every plant a grazer eats leaves exhausted soil: it remembers the tile until
+// the soil has recovered
+pub fn grazing_memory_system(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    tile_index: Res<TileIndex>,
+    q_fertility: Query<&Fertility>,
+    mut eats: MessageReader<EatEvent>,
+    mut q_grazers: Query<Option<&mut GrazingMemory>, (With<Creature>, Without<Dead>)>,
+) {
+    let recovered = |pos: &(i32, i32)| {
+        tile_index.0.get(pos).and_then(|&tile| q_fertility.get(tile).ok()).map(|f| f.level >= cfg.grazing_memory_recovered).unwrap_or(true)
+    };
+
+    for memory in q_grazers.iter_mut() {
+        let Some(mut memory) = memory else { continue; };
+        if memory.0.iter().any(&recovered) {
+            memory.0.retain(|pos| !recovered(pos));
+        }
+    }
+
+    for e in eats.read() {
+        if !cfg.s(e.species_id).grazer { continue; }
+        match q_grazers.get_mut(e.entity) {
+            Ok(Some(mut memory)) => {
+                memory.0.retain(|&pos| pos != e.pos);
+                memory.0.push(e.pos);
+                let excess = memory.0.len().saturating_sub(cfg.grazing_memory_tiles);
+                memory.0.drain(..excess);
+            }
+            Ok(None) => { commands.entity(e.entity).insert(GrazingMemory(vec![e.pos])); }
+            Err(_) => {}
+        }
+    }
+}
+
+// --- ALARM CALLS ---
+// Creatures that see something their Flee rules run from call out, unless
+// they're already running from an alarm
+pub fn alarm_call_system(
+    cfg: Res<SimulationConfig>,
+    behaviors: Res<SpeciesBehaviors>,
+    q_creatures: Query<(Entity, &GridPosition, &CreatureStats, &Age, Has<Alarmed>, &StreamKey), (With<Creature>, Without<Dead>)>,
+    mut alarms: MessageWriter<AlarmEvent>,
+) {
+    if cfg.alarm_hearing_range <= 0 { return; }
+
+    // Callers in StreamKey order: where alarms overlap, the last one heard wins
+    let mut callers: Vec<_> = q_creatures.iter().collect();
+    callers.sort_by_key(|(.., key)| key.0);
+
+    for (entity, pos, stats, my_age, alarmed, _) in callers {
+        if alarmed { continue; }
+        let sight_range = cfg.sight_for(stats, my_age);
+
+        let spotted = behaviors.get(stats.species_id).rules.iter().filter(|rule| rule.goal == Goal::Flee).find_map(|rule| {
+            q_creatures
+                .iter()
+                .filter(|(other, _, s, age, ..)| *other != entity && rule.species.contains(&s.species_id) && (age.is_adult || !rule.target_adults_only))
+                .map(|(_, p, s, ..)| ((p.x - pos.x).abs() + (p.y - pos.y).abs(), (p.x, p.y), s.species_id))
+                .filter(|(dist, ..)| *dist < sight_range)
+                .min()
+        });
+
+        if let Some((_, threat, threat_species)) = spotted {
+            alarms.write(AlarmEvent {
+                caller: entity,
+                species_id: stats.species_id,
+                pos: (pos.x, pos.y),
+                threat,
+                threat_species,
+            });
+        }
+    }
+}
+
+// Everyone of the caller's kind within earshot (the caller too) runs from the reported spot
+pub fn alarm_response_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    mut alarms: MessageReader<AlarmEvent>,
+    mut q_creatures: Query<(Entity, &GridPosition, &CreatureStats, Option<&mut Alarmed>), (With<Creature>, Without<Dead>)>,
+) {
+    for (entity, _, _, alarmed) in q_creatures.iter_mut() {
+        let Some(mut alarmed) = alarmed else { continue; };
+        alarmed.timer.tick(time.delta());
+        if alarmed.timer.is_finished() {
+            commands.entity(entity).remove::<Alarmed>();
+        }
+    }
+
+    // Inserting replaces an older alarm (and outlives a removal queued above)
+    for alarm in alarms.read() {
+        for (entity, pos, stats, _) in q_creatures.iter() {
+            let dist = (pos.x - alarm.pos.0).abs() + (pos.y - alarm.pos.1).abs();
+            if stats.species_id != alarm.species_id || dist > cfg.alarm_hearing_range { continue; }
+
+            commands.entity(entity).insert(Alarmed {
+                threat: alarm.threat,
+                threat_species: alarm.threat_species,
+                timer: Timer::from_seconds(cfg.alarm_seconds, TimerMode::Once),
+            });
+        }
+    }
+}
+
+// --- WOOL ---
+// Fleeces grow back after shearing. Sheep get theirs on their first frame:
+// a full one for adults, none yet for lambs.
+pub fn wool_growth_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    mut q_sheep: Query<(Entity, &CreatureStats, &Age, Option<&mut Wool>), (With<Creature>, Without<Dead>)>,
+) {
+    let growth = cfg.wool_regrow_per_second * time.delta_secs();
+    for (entity, stats, age, wool) in q_sheep.iter_mut() {
+        if stats.species_id != 0 { continue; }
+        match wool {
+            Some(mut wool) if wool.0 < 1.0 => wool.0 = (wool.0 + growth).min(1.0),
+            Some(_) => {}
+            None => { commands.entity(entity).insert(Wool(if age.is_adult { 1.0 } else { 0.0 })); }
+        }
+    }
+}
+
+/// Shears a fleece into `resources` if there's enough on it
+pub fn shear(wool: &mut Wool, cfg: &SimulationConfig, resources: &mut PlayerResources) -> bool {
+    if wool.0 < cfg.wool_shear_min { return false; }
+    resources.add(ResourceKind::Wool, (wool.0 * cfg.wool_per_fleece).round() as u32);
+    wool.0 = 0.0;
+    true
+}
+
+// The shepherd shears full fleeces it passes
+pub fn shepherd_shearing_system(
+    cfg: Res<SimulationConfig>,
+    mut resources: ResMut<PlayerResources>,
+    q_shepherd: Query<&GridPosition, With<Shepherd>>,
+    mut q_sheep: Query<(&GridPosition, &mut Wool), (With<Creature>, Without<Dead>)>,
+) {
+    if !cfg.shepherd_shears { return; }
+    let Ok(shepherd) = q_shepherd.single() else { return; };
+
+    for (pos, mut wool) in q_sheep.iter_mut() {
+        let next_to = (pos.x - shepherd.x).abs() + (pos.y - shepherd.y).abs() <= 1;
+        if next_to && wool.0 >= 1.0 {
+            shear(&mut wool, &cfg, &mut resources);
+        }
+    }
+}
+
+// --- PARASITES & BIRDS ---
+// Ticks climb onto creatures stepping through plants and drop off while they rest
+pub fn parasite_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    sim_rng: Res<SimRng>,
+    q_plants: Query<&GridPosition, (With<Plant>, Without<Crop>, Without<Dead>)>,
+    mut q_creatures: Query<(Entity, &StreamKey, Ref<GridPosition>, Option<&mut Parasites>, Has<Digesting>, Has<Sheltered>), (With<Creature>, Without<Dead>)>,
+) {
+    let dt = time.delta().as_secs_f32();
+    let plants: HashSet<(i32, i32)> = q_plants.iter().map(|p| (p.x, p.y)).collect();
+
+    for (entity, key, pos, parasites, digesting, sheltered) in q_creatures.iter_mut() {
+        let picked_up = pos.is_changed()
+            && plants.contains(&(pos.x, pos.y))
+            && sim_rng.stream_for("parasites", key.0).random::<f32>() < cfg.parasite_attach_chance;
+
+        let Some(mut parasites) = parasites else {
+            if picked_up { commands.entity(entity).insert(Parasites { count: 1, groomed: 0.0 }); }
+            continue;
+        };
+        if picked_up {
+            parasites.count = (parasites.count + 1).min(cfg.parasite_max);
+        }
+
+        if digesting || sheltered {
+            parasites.groomed += dt;
+            if parasites.groomed >= cfg.parasite_groom_seconds {
+                parasites.groomed = 0.0;
+                parasites.count = parasites.count.saturating_sub(1);
+            }
+        }
+        if parasites.count == 0 {
+            commands.entity(entity).remove::<Parasites>();
+        }
+    }
+}
+
+// Keep `bird_count` birds around, landing one a frame at a random spot
+pub fn bird_flock_system(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    sim_rng: Res<SimRng>,
+    q_birds: Query<(Entity, &Bird, &StreamKey)>,
+    q_hosts: Query<&Parasites, (With<Creature>, Without<Dead>)>,
+    q_insects: Query<(), With<Insect>>,
+) {
+    // Starving birds leave, and the flock shrinks one at a time to a lower
+    // bird_count (lowest StreamKey first, so it's the same bird every run)
+    let mut birds: Vec<(u64, Entity, f32)> = q_birds.iter().map(|(e, bird, key)| (key.0, e, bird.hunger)).collect();
+    birds.sort_by_key(|&(key, ..)| key);
+    let (starving, fed): (Vec<_>, Vec<_>) = birds.into_iter().partition(|&(.., hunger)| hunger > cfg.bird_starve_seconds);
+    for (_, bird, _) in starving {
+        commands.entity(bird).despawn();
+    }
+    let count = fed.len() as u32;
+    if count > cfg.bird_count {
+        commands.entity(fed[0].1).despawn();
+        return;
+    }
+    if count == cfg.bird_count { return; }
+
+    // Newcomers only turn up while there's something to eat
+    if q_insects.is_empty() && !q_hosts.iter().any(|p| p.count > 0) { return; }
+
+    let mut rng = sim_rng.stream("birds");
+    let map_size = cfg.map_size;
+    let x = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+    let y = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+    commands.spawn((Bird::default(), GridPosition { x, y }, NextMove::default(), StreamKey::new(&format!("bird {}", sim_rng.tick), (x, y))));
+}
+
+// Birds fly (over water and fences) to the nearest creature carrying ticks and
+// eat one each turn they share its tile. With no host in sight they go after
+// insects, and failing that flit about. Every tick or insect is a meal.
+pub fn bird_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    clock: Res<MoveClock>,
+    cfg: Res<SimulationConfig>,
+    sim_rng: Res<SimRng>,
+    mut q_birds: Query<(&StreamKey, &mut Bird, &mut GridPosition, &mut NextMove), (Without<Creature>, Without<Insect>)>,
+    mut q_hosts: Query<(Entity, &GridPosition, &mut Parasites), (With<Creature>, Without<Dead>)>,
+    q_insects: Query<(Entity, &GridPosition), With<Insect>>,
+) {
+    let interval = MoveClock::ticks_for(&cfg, cfg.bird_move_seconds);
+    let mut hosts: Vec<(Entity, (i32, i32))> = q_hosts
+        .iter()
+        .filter(|(_, _, p)| p.count > 0)
+        .map(|(e, pos, _)| (e, (pos.x, pos.y)))
+        .collect();
+    let mut insects: Vec<(Entity, (i32, i32))> = q_insects.iter().map(|(e, pos)| (e, (pos.x, pos.y))).collect();
+    // Query order isn't stable; ties have to break the same way every run
+    hosts.sort_by_key(|&(_, pos)| pos);
+    insects.sort_by_key(|&(_, pos)| pos);
+    let mut eaten: HashSet<Entity> = HashSet::new();
+    // In StreamKey order: two birds after one host don't depend on the query
+    let mut birds: Vec<_> = q_birds.iter_mut().collect();
+    birds.sort_by_key(|(key, ..)| key.0);
+
+    for (bird_key, mut bird, mut pos, mut next_move) in birds {
+        bird.hunger += time.delta_secs();
+        if !clock.is_due(&next_move) {
+            if next_move.0.is_none() { *next_move = clock.after(interval); }
+            continue;
+        }
+        *next_move = clock.after(interval);
+
+        let dist = |(x, y): (i32, i32)| (pos.x - x).abs() + (pos.y - y).abs();
+        let nearest = |list: &[(Entity, (i32, i32))]| {
+            list.iter()
+                .filter(|&&(e, at)| dist(at) <= cfg.bird_sight && !eaten.contains(&e))
+                .min_by_key(|&&(_, at)| dist(at))
+                .copied()
+        };
+        let host = nearest(&hosts);
+        let insect = if host.is_none() { nearest(&insects) } else { None };
+
+        let (dx, dy) = match host.or(insect) {
+            Some((prey, at)) if dist(at) == 0 => {
+                if host.is_some() {
+                    if let Ok((_, _, mut parasites)) = q_hosts.get_mut(prey) {
+                        if parasites.count > 0 {
+                            parasites.count -= 1;
+                            bird.hunger = 0.0;
+                        }
+                    }
+                } else {
+                    bird.hunger = 0.0;
+                    commands.entity(prey).despawn();
+                    eaten.insert(prey);
+                }
+                continue;
+            }
+            Some((_, (x, y))) => ((x - pos.x).signum(), (y - pos.y).signum()),
+            None => {
+                let mut rng = sim_rng.stream_for("bird_moves", bird_key.0);
+                (rng.random::<i32>().rem_euclid(3) - 1, rng.random::<i32>().rem_euclid(3) - 1)
+            }
+        };
+        pos.x = (pos.x + dx).clamp(-cfg.map_size, cfg.map_size - 1);
+        pos.y = (pos.y + dy).clamp(-cfg.map_size, cfg.map_size - 1);
+    }
+}
+
+// Keep `insect_count` insects around: a missing one hatches on a random sun plant
+// every `insect_hatch_seconds`
+pub fn insect_hatch_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    sim_rng: Res<SimRng>,
+    mut since_hatch: Local<f32>,
+    mut cleared: MessageReader<WorldCleared>,
+    q_insects: Query<Entity, With<Insect>>,
+    q_plants: Query<&GridPosition, (With<Plant>, Without<Crop>, Without<ShadeTolerant>, Without<Dead>)>,
+) {
+    if cleared.read().count() > 0 { *since_hatch = 0.0; }
+    if q_insects.iter().count() as u32 >= cfg.insect_count {
+        *since_hatch = 0.0;
+        return;
+    }
+    *since_hatch += time.delta_secs();
+    if *since_hatch < cfg.insect_hatch_seconds { return; }
+
+    let mut plants: Vec<(i32, i32)> = q_plants.iter().map(|p| (p.x, p.y)).collect();
+    if plants.is_empty() { return; }
+    plants.sort();
+    *since_hatch = 0.0;
+
+    let mut rng = sim_rng.stream("insects");
+    let (x, y) = plants[(rng.random::<u32>() as usize) % plants.len()];
+    commands.spawn((Insect::default(), GridPosition { x, y }, NextMove::default(), StreamKey::new(&format!("insect {}", sim_rng.tick), (x, y))));
+}
+
+// Insects fly between sun plants in daylight, marking a visit at each one
+pub fn insect_system(
+    mut commands: Commands,
+    clock: Res<MoveClock>,
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    sim_rng: Res<SimRng>,
+    mut q_insects: Query<(&StreamKey, &mut GridPosition, &mut NextMove, &mut Insect), Without<Plant>>,
+    mut q_plants: Query<(Entity, &GridPosition, Option<&mut Pollination>), (With<Plant>, Without<Crop>, Without<ShadeTolerant>, Without<Dead>)>,
+) {
+    if stats.is_night(&cfg) { return; }
+
+    let interval = MoveClock::ticks_for(&cfg, cfg.insect_move_seconds);
+    let mut plants: Vec<(Entity, (i32, i32))> = q_plants.iter().map(|(e, pos, _)| (e, (pos.x, pos.y))).collect();
+    plants.sort_by_key(|&(_, pos)| pos);
+
+    for (insect_key, mut pos, mut next_move, mut insect) in q_insects.iter_mut() {
+        if !clock.is_due(&next_move) {
+            if next_move.0.is_none() { *next_move = clock.after(interval); }
+            continue;
+        }
+        *next_move = clock.after(interval);
+
+        let here = (pos.x, pos.y);
+        let target = insect.target.and_then(|t| plants.iter().find(|(e, _)| *e == t)).copied();
+
+        match target {
+            Some((plant, at)) if at == here => {
+                match q_plants.get_mut(plant) {
+                    Ok((_, _, Some(mut pollination))) => {
+                        pollination.0 = (pollination.0 + 1.0).min(cfg.pollination_max_visits);
+                    }
+                    _ => { commands.entity(plant).insert(Pollination(1.0)); }
+                }
+                insect.target = None;
+            }
+            Some((_, (x, y))) => {
+                pos.x += (x - pos.x).signum();
+                pos.y += (y - pos.y).signum();
+            }
+            None => {
+                // Another plant within range, or whichever one is nearest
+                let dist = |(x, y): (i32, i32)| (here.0 - x).abs() + (here.1 - y).abs();
+                let nearby: Vec<Entity> = plants
+                    .iter()
+                    .filter(|&&(_, at)| at != here && dist(at) <= cfg.insect_range)
+                    .map(|&(e, _)| e)
+                    .collect();
+                let mut rng = sim_rng.stream_for("insect_moves", insect_key.0);
+                insect.target = if nearby.is_empty() {
+                    plants.iter().filter(|&&(_, at)| at != here).min_by_key(|&&(_, at)| dist(at)).map(|&(e, _)| e)
+                } else {
+                    Some(nearby[(rng.random::<u32>() as usize) % nearby.len()])
+                };
+            }
+        }
+    }
+}
+
+// Pollinated sun plants drop seeds onto free dry land next to them
+pub fn plant_seeding_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    game_stats: Res<GameStats>,
+    sim_rng: Res<SimRng>,
+    tile_index: Res<TileIndex>,
+    mut q_pollinated: Query<(Entity, &GridPosition, &mut Pollination), Without<Dead>>,
+    q_ground: Query<(Has<Water>, Has<Shaded>), With<Tile>>,
+    q_occupied: Query<&GridPosition, Or<(With<Plant>, With<Crop>, With<Tree>, With<Fence>, With<Shelter>)>>,
+) {
+    let dt = time.delta_secs();
+    let sunlight = game_stats.sunlight(&cfg);
+    if q_pollinated.is_empty() { return; }
+    let mut occupied: HashSet<(i32, i32)> = q_occupied.iter().map(|p| (p.x, p.y)).collect();
+
+    for (plant, pos, mut pollination) in q_pollinated.iter_mut() {
+        pollination.0 -= cfg.pollination_decay_per_second * dt;
+        if pollination.0 <= 0.0 {
+            commands.entity(plant).remove::<Pollination>();
+            continue;
+        }
+
+        let mut rng = sim_rng.stream_for("seeding", StreamKey::new("plant", (pos.x, pos.y)).0);
+        if rng.random::<f32>() >= cfg.pollinated_seed_chance * pollination.0 * sunlight * dt { continue; }
+
+        let (dx, dy) = [(0, 1), (0, -1), (-1, 0), (1, 0)][(rng.random::<u32>() % 4) as usize];
+        let (x, y) = (pos.x + dx, pos.y + dy);
+        let sunny_land = tile_index.0.get(&(x, y))
+            .and_then(|&tile| q_ground.get(tile).ok())
+            .is_some_and(|(water, shaded)| !water && !shaded);
+        if sunny_land && occupied.insert((x, y)) {
+            commands.spawn((Plant, GridPosition { x, y }));
+        }
+    }
+}
+
+pub fn handle_drowning(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    // FIX: Add Without<Dead> so we don't try to kill ghosts
+    q_creatures: Query<(
+        Entity,
+        &GridPosition,
+        &CreatureStats,
+        Has<Swimming>,
+        Has<Wading>,
+        &CreatureName,
+        &Generation,
+        &Dna,
+    ), (With<Creature>, Without<Dead>)>,
+    // Creatures standing on a bridge stay dry
+    q_water: Query<(&Tile, &Water), Without<Bridge>>,
+    mut deaths: MessageWriter<DeathEvent>,
+) {
+    for (creature_entity, creature_pos, stats, swimming, wading, name, generation, dna) in q_creatures.iter() {
+        let water = q_water
+            .iter()
+            .find(|(tile, _)| creature_pos.x == tile.x && creature_pos.y == tile.y)
+            .map(|(_, water)| water);
+
+        let mut entity = commands.entity(creature_entity);
+        match water {
+            None => {
+                if swimming { entity.remove::<Swimming>(); }
+                if wading { entity.remove::<Wading>(); }
+            }
+            Some(w) if !w.is_deep(&cfg) => {
+                if swimming { entity.remove::<Swimming>(); }
+                if !wading { entity.insert(Wading); }
+            }
+            Some(_) => {
+                if wading { entity.remove::<Wading>(); }
+                if cfg.s(stats.species_id).can_swim {
+                    if !swimming { entity.insert(Swimming); }
+                } else {
+                    entity.insert(Dead { cause: DeathCause::Drowning });
+                    deaths.write(DeathEvent {
+                        entity: creature_entity,
+                        species_id: stats.species_id,
+                        name: name.0.clone(),
+                        generation: generation.0,
+                        dna: *dna,
+                        pos: (creature_pos.x, creature_pos.y),
+                        cause: DeathCause::Drowning,
+                    });
+                }
+            }
+        }
+    }
+}
+
+// Ten real seconds make a day
+pub const SECONDS_PER_DAY: f32 = 10.0;
+
+pub fn advance_clock(time: Res<Time>, mut stats: ResMut<GameStats>) {
+    stats.days += time.delta().as_secs_f32() / SECONDS_PER_DAY;
+}
+
+pub fn weather_system(
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    mut weather: ResMut<Weather>,
+    mut changes: MessageWriter<WeatherChanged>,
+    sim_rng: Res<SimRng>,
+) {
+    weather.timer.tick(time.delta());
+    if !weather.timer.is_finished() {
+        return;
+    }
+
+    let mut rng = sim_rng.stream("weather");
+    let roll = rng.random::<f32>();
+    weather.kind = if roll < cfg.weather_rain_chance {
+        WeatherKind::Rain
+    } else if roll < cfg.weather_rain_chance + cfg.weather_drought_chance {
+        WeatherKind::Drought
+    } else if roll < cfg.weather_rain_chance + cfg.weather_drought_chance + cfg.weather_storm_chance {
+        WeatherKind::Storm
+    } else {
+        WeatherKind::Clear
+    };
+
+    let span = (cfg.weather_max_seconds - cfg.weather_min_seconds).max(0.0);
+    let seconds = cfg.weather_min_seconds + rng.random::<f32>() * span;
+    weather.timer = Timer::from_seconds(seconds.max(1.0), TimerMode::Once);
+
+    changes.write(WeatherChanged { kind: weather.kind });
+}
+
+// Rain deepens water and floods land next to it; sun and drought evaporate it.
+// Runs on a coarse tick since it touches every tile.
+pub fn hydrology_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    weather: Res<Weather>,
+    mut elapsed: Local<f32>,
+    mut cleared: MessageReader<WorldCleared>,
+    mut q_tiles: Query<(Entity, &Tile, Option<&mut Water>)>,
+    q_plants: Query<(Entity, &GridPosition), Or<(With<Plant>, With<Crop>)>>,
+    q_exhausted: Query<(Entity, &GridPosition), With<ExhaustedSoil>>,
+    sim_rng: Res<SimRng>,
+) {
+    // A new or loaded world starts its own tick
+    if cleared.read().count() > 0 { *elapsed = 0.0; }
+    *elapsed += time.delta().as_secs_f32();
+    if *elapsed < cfg.hydrology_tick_seconds {
+        return;
+    }
+    let step = *elapsed;
+    *elapsed = 0.0;
+    let mut rng = sim_rng.stream("hydrology");
+
+    let depth_delta = match weather.kind {
+        WeatherKind::Rain | WeatherKind::Storm => cfg.rain_fill_rate,
+        WeatherKind::Clear => -cfg.sun_evaporation_rate,
+        WeatherKind::Drought => -cfg.drought_evaporation_rate,
+    } * step;
+
+    let wet: HashSet<(i32, i32)> = q_tiles
+        .iter()
+        .filter(|(_, _, water)| water.is_some())
+        .map(|(_, tile, _)| (tile.x, tile.y))
+        .collect();
+
+    let mut flooded: HashSet<(i32, i32)> = HashSet::new();
+    let mut shore: Vec<((i32, i32), Entity)> = Vec::new();
+
+    for (entity, tile, water) in q_tiles.iter_mut() {
+        match water {
+            Some(mut water) => {
+                water.depth = (water.depth + depth_delta).min(cfg.max_water_depth);
+                if water.depth <= 0.0 {
+                    // Dried up: back to grass (a bridge over a dry bed is pointless)
+                    commands.entity(entity).remove::<(Water, Bridge, Pollution)>();
+                }
+            }
+            None => {
+                if !weather.kind.is_wet() { continue; }
+
+                let next_to_water = [(0, 1), (0, -1), (-1, 0), (1, 0)]
+                    .iter()
+                    .any(|(dx, dy)| wet.contains(&(tile.x + dx, tile.y + dy)));
+                if next_to_water { shore.push(((tile.x, tile.y), entity)); }
+            }
+        }
+    }
+
+    // Rolled in tile order: query order follows archetypes, which differ between worlds
+    shore.sort_by_key(|&(pos, _)| pos);
+    for (pos, entity) in shore {
+        if rng.random::<f32>() < cfg.rain_spread_chance * step {
+            commands.entity(entity).insert(Water { depth: cfg.rain_spread_depth });
+            flooded.insert(pos);
+        }
+    }
+
+    if flooded.is_empty() {
+        return;
+    }
+
+    // Rising water kills what grew on the new shoreline, same as painting it
+    for (entity, pos) in q_plants.iter() {
+        if flooded.contains(&(pos.x, pos.y)) {
+            commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+        }
+    }
+    for (entity, pos) in q_exhausted.iter() {
+        if flooded.contains(&(pos.x, pos.y)) {
+            commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+        }
+    }
+}
+
+// Every body of water (connected water tiles) shares one pollution level. Visitors
+// and corpses raise it, time and rain bring it back down, and past the threshold
+// it kills the plants along its shore.
+pub fn water_pollution_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    weather: Res<Weather>,
+    sim_rng: Res<SimRng>,
+    mut elapsed: Local<f32>,
+    mut cleared: MessageReader<WorldCleared>,
+    q_water: Query<(Entity, &Tile, Option<&Pollution>), With<Water>>,
+    q_creatures: Query<&GridPosition, (With<Creature>, Without<Dead>)>,
+    q_corpses: Query<&GridPosition, (With<Corpse>, Without<Dead>)>,
+    q_plants: Query<(Entity, &GridPosition), (With<Plant>, Without<Dead>)>,
+) {
+    if cleared.read().count() > 0 { *elapsed = 0.0; }
+    *elapsed += time.delta().as_secs_f32();
+    if *elapsed < cfg.hydrology_tick_seconds {
+        return;
+    }
+    let step = *elapsed;
+    *elapsed = 0.0;
+
+    let neighbours = |(x, y): (i32, i32)| [(x, y + 1), (x, y - 1), (x - 1, y), (x + 1, y)];
+    let water: HashMap<(i32, i32), (Entity, f32)> = q_water
+        .iter()
+        .map(|(entity, tile, pollution)| ((tile.x, tile.y), (entity, pollution.map(|p| p.0).unwrap_or(0.0))))
+        .collect();
+
+    // Label the bodies of water
+    let mut body_of: HashMap<(i32, i32), usize> = HashMap::new();
+    let mut bodies: Vec<Vec<(i32, i32)>> = Vec::new();
+    let mut starts: Vec<(i32, i32)> = water.keys().copied().collect();
+    starts.sort();
+    for start in starts {
+        if body_of.contains_key(&start) { continue; }
+        let id = bodies.len();
+        let mut tiles = vec![start];
+        body_of.insert(start, id);
+        let mut i = 0;
+        while i < tiles.len() {
+            for next in neighbours(tiles[i]) {
+                if water.contains_key(&next) && !body_of.contains_key(&next) {
+                    body_of.insert(next, id);
+                    tiles.push(next);
+                }
+            }
+            i += 1;
+        }
+        bodies.push(tiles);
+    }
+
+    // Creatures and corpses in the water or on its bank
+    let body_at = |pos: &GridPosition| {
+        let here = (pos.x, pos.y);
+        std::iter::once(here).chain(neighbours(here)).find_map(|p| body_of.get(&p).copied())
+    };
+    let mut fouling = vec![0.0f32; bodies.len()];
+    for id in q_creatures.iter().filter_map(body_at) {
+        fouling[id] += cfg.pollution_per_visitor;
+    }
+    for id in q_corpses.iter().filter_map(body_at) {
+        fouling[id] += cfg.pollution_per_corpse;
+    }
+
+    let recovery = cfg.pollution_recovery_per_second + if weather.kind.is_wet() { cfg.pollution_rain_dilution } else { 0.0 };
+    let mut toxic: HashSet<(i32, i32)> = HashSet::new();
+    for (id, tiles) in bodies.iter().enumerate() {
+        let size = tiles.len() as f32;
+        let mean = tiles.iter().map(|t| water[t].1).sum::<f32>() / size;
+        let level = (mean + (fouling[id] / size - recovery) * step).clamp(0.0, 1.0);
+
+        for t in tiles {
+            let entity = water[t].0;
+            if level > 0.0 {
+                commands.entity(entity).insert(Pollution(level));
+            } else if water[t].1 > 0.0 {
+                commands.entity(entity).remove::<Pollution>();
+            }
+        }
+        if level > cfg.pollution_kill_threshold {
+            toxic.extend(tiles.iter().copied());
+        }
+    }
+
+    if toxic.is_empty() { return; }
+    let mut rng = sim_rng.stream("pollution");
+    let chance = cfg.pollution_plant_die_chance * step;
+    let mut plants: Vec<(Entity, (i32, i32))> = q_plants.iter().map(|(e, p)| (e, (p.x, p.y))).collect();
+    plants.sort_by_key(|&(_, pos)| pos);
+    for (entity, pos) in plants {
+        if neighbours(pos).iter().any(|p| toxic.contains(p)) && rng.random::<f32>() < chance {
+            commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+        }
+    }
+}
+
+// Creatures in or next to water drink from it every drink_interval_seconds. A
+// drink from a fouled body of water may infect them, more likely the fouler it
+// is; infections wear off after infection_seconds.
+pub fn drinking_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    sim_rng: Res<SimRng>,
+    tile_index: Res<TileIndex>,
+    mut elapsed: Local<f32>,
+    mut cleared: MessageReader<WorldCleared>,
+    q_water: Query<Option<&Pollution>, (With<Water>, Without<Bridge>)>,
+    mut q_creatures: Query<(Entity, &StreamKey, &GridPosition, Option<&mut Infected>), (With<Creature>, Without<Dead>)>,
+) {
+    let dt = time.delta().as_secs_f32();
+    if cleared.read().count() > 0 { *elapsed = 0.0; }
+    *elapsed += dt;
+    let drinking = *elapsed >= cfg.drink_interval_seconds;
+    if drinking { *elapsed = 0.0; }
+
+    for (entity, key, pos, infected) in q_creatures.iter_mut() {
+        if let Some(mut infected) = infected {
+            infected.0 -= dt;
+            if infected.0 <= 0.0 {
+                commands.entity(entity).remove::<Infected>();
+            }
+            continue;
+        }
+        if !drinking { continue; }
+
+        // The water here or the first next to it; a body shares one level
+        let (x, y) = (pos.x, pos.y);
+        let water = [(x, y), (x, y + 1), (x, y - 1), (x - 1, y), (x + 1, y)]
+            .iter()
+            .filter_map(|p| tile_index.0.get(p))
+            .find_map(|&tile| q_water.get(tile).ok());
+        let Some(Some(pollution)) = water else { continue; };
+        if sim_rng.stream_for("drinking", key.0).random::<f32>() < cfg.infection_chance * pollution.0 {
+            commands.entity(entity).insert(Infected(cfg.infection_seconds));
+        }
+    }
+}
+
+pub fn enter_observer_mode(
+    mut commands: Commands,
+    mut cfg: ResMut<SimulationConfig>,
+    q_shepherd: Query<Entity, With<Shepherd>>,
+) {
+    cfg.governor_enabled = true;
+    for entity in q_shepherd.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn enter_challenge_mode(mut cfg: ResMut<SimulationConfig>) {
+    cfg.governor_enabled = false;
+}
+
+// Keeps both species going by small steps rather than hard resets: a struggling
+// flock gets more grass and fewer wolf litters, a struggling pack more litters.
+pub fn balance_governor_system(
+    time: Res<Time>,
+    mut cfg: ResMut<SimulationConfig>,
+    mut governor: ResMut<BalanceGovernor>,
+    q_creatures: Query<&CreatureStats, (With<Creature>, Without<Dead>)>,
+) {
+    if !cfg.governor_enabled { return; }
+
+    governor.elapsed += time.delta().as_secs_f32();
+    if governor.elapsed < cfg.governor_interval_seconds { return; }
+    governor.elapsed = 0.0;
+
+    let sheep = q_creatures.iter().filter(|s| s.species_id == 0).count() as u32;
+    let wolves = q_creatures.iter().filter(|s| s.species_id == 1).count() as u32;
+    let (last_sheep, last_wolves) = governor.last.unwrap_or((sheep, wolves));
+    governor.last = Some((sheep, wolves));
+
+    let sheep_mid = (cfg.governor_sheep_low + cfg.governor_sheep_high) / 2;
+    let wolf_mid = (cfg.governor_wolf_low + cfg.governor_wolf_high) / 2;
+    let sheep_low = sheep < cfg.governor_sheep_low || (sheep < last_sheep && sheep < sheep_mid);
+    let sheep_high = sheep > cfg.governor_sheep_high;
+    let wolves_low = wolves < cfg.governor_wolf_low || (wolves < last_wolves && wolves < wolf_mid);
+    let wolves_high = wolves > cfg.governor_wolf_high;
+
+    let plant_dir = if sheep_low { 1.0 } else if sheep_high { -1.0 } else { 0.0 };
+    // Prey comes first: never push wolves while the flock is in trouble
+    let repro_dir = if sheep_low || wolves_high {
+        -1.0
+    } else if wolves_low || sheep_high {
+        1.0
+    } else {
+        0.0
+    };
+
+    let step = cfg.governor_step;
+    let (lo, hi) = cfg.governor_plant_chance_bounds;
+    cfg.plant_spawn_chance_per_tick = (cfg.plant_spawn_chance_per_tick + plant_dir * step * (hi - lo)).clamp(lo, hi);
+
+    let (lo, hi) = cfg.governor_wolf_reproduction_bounds;
+    let wolf = cfg.s_mut(1);
+    wolf.reproduction_chance = (wolf.reproduction_chance + repro_dir * step * (hi - lo)).clamp(lo, hi);
+}
+
+// Every step wears the tile it lands on; wear decays over time, with
+// hysteresis between turning to dirt and growing back.
+pub fn trampling_system(
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    tile_index: Res<TileIndex>,
+    q_moved: Query<&GridPosition, (With<Creature>, Without<Dead>, Changed<GridPosition>)>,
+    mut q_tiles: Query<&mut Footfall, With<Tile>>,
+) {
+    for pos in q_moved.iter() {
+        let Some(&tile) = tile_index.0.get(&(pos.x, pos.y)) else { continue; };
+        if let Ok(mut footfall) = q_tiles.get_mut(tile) {
+            footfall.wear += cfg.footfall_per_step;
+        }
+    }
+
+    let decay = cfg.footfall_decay_per_second * time.delta().as_secs_f32();
+    for mut footfall in q_tiles.iter_mut() {
+        footfall.wear = (footfall.wear - decay).max(0.0);
+
+        if !footfall.dirt && footfall.wear >= cfg.dirt_wear_threshold {
+            footfall.dirt = true;
+        } else if footfall.dirt && footfall.wear <= cfg.dirt_recover_threshold {
+            footfall.dirt = false;
+        }
+    }
+}
+
+pub fn water_distance_system(
+    cfg: Res<SimulationConfig>,
+    mut field: ResMut<WaterDistanceField>,
+    q_added: Query<(), Added<Water>>,
+    mut removed: RemovedComponents<Water>,
+    q_water: Query<&Tile, With<Water>>,
+) {
+    let any_removed = removed.read().count() > 0;
+    if q_added.is_empty() && !any_removed {
+        return;
+    }
+
+    let map_size = cfg.map_size;
+    let mut dist: HashMap<(i32, i32), i32> = HashMap::new();
+    let mut frontier: VecDeque<(i32, i32)> = VecDeque::new();
+
+    for tile in q_water.iter() {
+        dist.insert((tile.x, tile.y), 0);
+        frontier.push_back((tile.x, tile.y));
+    }
+
+    while let Some((x, y)) = frontier.pop_front() {
+        let d = dist[&(x, y)];
+        for (dx, dy) in [(0, 1), (0, -1), (-1, 0), (1, 0)] {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx < -map_size || nx >= map_size || ny < -map_size || ny >= map_size {
+                continue;
+            }
+            if dist.contains_key(&(nx, ny)) { continue; }
+            dist.insert((nx, ny), d + 1);
+            frontier.push_back((nx, ny));
+        }
+    }
+
+    field.dist = dist;
+}
+
+// Crops advance Seed -> Sprout -> Ripe with daylight; creatures walking over
+// unripe crops may trample them (fence them off to protect them).
+pub fn crop_growth_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    game_stats: Res<GameStats>,
+    mut q_crops: Query<(Entity, &mut Crop, &GridPosition), Without<Dead>>,
+    q_moved: Query<&GridPosition, (With<Creature>, Without<Dead>, Changed<GridPosition>)>,
+    sim_rng: Res<SimRng>,
+) {
+    let mut rng = sim_rng.stream("crops");
+    let grow = time.delta().as_secs_f32() * game_stats.sunlight(&cfg);
+    let trampled: HashSet<(i32, i32)> = q_moved.iter().map(|p| (p.x, p.y)).collect();
+    // In tile order, so the trample rolls don't depend on the query
+    let mut crops: Vec<_> = q_crops.iter_mut().collect();
+    crops.sort_by_key(|(_, _, pos)| (pos.x, pos.y));
+
+    for (entity, mut crop, pos) in crops {
+        if crop.stage == CropStage::Ripe { continue; }
+
+        if trampled.contains(&(pos.x, pos.y)) && rng.random::<f32>() < cfg.crop_trample_chance {
+            commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+            continue;
+        }
+
+        crop.growth += grow;
+        if crop.growth < cfg.crop_stage_seconds { continue; }
+        crop.growth = 0.0;
+
+        match crop.stage {
+            CropStage::Seed => {
+                crop.stage = CropStage::Sprout;
+            }
+            CropStage::Sprout => {
+                crop.stage = CropStage::Ripe;
+                commands.entity(entity).insert(Plant);
+            }
+            CropStage::Ripe => {}
+        }
+    }
+}
+
+// Grass regrows logistically: slowly when sparse, fastest at half cover, levelling
+// off near full. Drowned grass dies back.
+pub fn grass_growth_system(
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    game_stats: Res<GameStats>,
+    mut q_grass: Query<(&mut Grass, &Fertility, Has<Water>), With<Tile>>,
+) {
+    let dt = time.delta_secs();
+    let sunlight = game_stats.sunlight(&cfg);
+
+    for (mut grass, fertility, water) in q_grass.iter_mut() {
+        if water {
+            if grass.0 > 0.0 { grass.0 = 0.0; }
+            continue;
+        }
+        if sunlight <= 0.0 || grass.0 >= 1.0 { continue; }
+
+        let g = grass.0.max(cfg.grass_seed_level);
+        grass.0 = (g + cfg.grass_regrow_rate * sunlight * fertility.level * g * (1.0 - g) * dt).min(1.0);
+    }
+}
+
+pub fn plant_growth_system(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    water_field: Res<WaterDistanceField>,
+    game_stats: Res<GameStats>,
+    q_tiles: Query<(&Tile, &Footfall, &Fertility, Has<Shaded>), Without<Water>>,
+    q_plants: Query<&GridPosition, Or<(With<Plant>, With<Crop>)>>,
+    q_trees: Query<&GridPosition, With<Tree>>,
+    q_creatures: Query<&CreatureStats, (With<Creature>, Without<Dead>)>,
+    sim_rng: Res<SimRng>,
+) {
+    // No growth at night, slow in winter
+    let sunlight = game_stats.sunlight(&cfg);
+    if sunlight <= 0.0 {
+        return;
+    }
+
+    // Starve out a population explosion while any cap is hit
+    let mut counts: HashMap<u32, u32> = HashMap::new();
+    for stats in q_creatures.iter() {
+        *counts.entry(stats.species_id).or_default() += 1;
+    }
+    let total: u32 = counts.values().sum();
+    let over_cap = cfg.species.keys().any(|&id| !cfg.births_allowed(id, counts.get(&id).copied().unwrap_or(0), total));
+    let cap_multiplier = if over_cap { cfg.over_cap_plant_multiplier } else { 1.0 };
+
+    let mut rng = sim_rng.stream("plants");
+    let map_size = cfg.map_size;
+    let x = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+    let y = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+
+    // Lush shores, sparse interior
+    let chance = cfg.plant_spawn_chance_per_tick * water_field.plant_multiplier(x, y, &cfg) * sunlight * cap_multiplier;
+
+    if rng.random::<f32>() < chance {
+        let mut valid_ground = false;
+        let mut in_shade = false;
+        for (tile, footfall, fertility, shaded) in q_tiles.iter() {
+            if tile.x == x && tile.y == y {
+                // Trampled dirt only occasionally takes a seed
+                valid_ground = !footfall.dirt || rng.random::<f32>() < cfg.dirt_plant_spawn_multiplier;
+                // Grazed soil takes seeds in proportion to how far it has recovered
+                valid_ground = valid_ground && rng.random::<f32>() < fertility.level;
+                // Only shade-tolerant plants grow under canopy, and more slowly
+                in_shade = shaded;
+                if shaded {
+                    valid_ground = valid_ground && rng.random::<f32>() < cfg.shade_plant_multiplier;
+                }
+                break;
+            }
+        }
+
+        let mut occupied = false;
+        // Check Plants
+        for plant_pos in q_plants.iter() {
+            if plant_pos.x == x && plant_pos.y == y {
+                occupied = true;
+                break;
+            }
+        }
+        // Nothing grows on a trunk
+        if q_trees.iter().any(|t| t.x == x && t.y == y) {
+            occupied = true;
+        }
+
+        if valid_ground && !occupied {
+            if in_shade {
+                // Fern
+                commands.spawn((Plant, ShadeTolerant, GridPosition { x, y }));
+            } else {
+                commands.spawn((Plant, GridPosition { x, y }));
+            }
+        }
+    }
+}
+
+// SYSTEM 1: Updates internal state (Hunger, Age, Timers)
+pub fn creature_state_update(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    mut deaths: MessageWriter<DeathEvent>,
+    mut q_creatures: Query<(
+        Entity,
+        &mut Hunger,
+        &mut Age,
+        Option<&mut ReproductionCooldown>,
+        &CreatureStats,
+        Option<&Digesting>,
+        Option<&mut Overfed>,
+        &Dna,
+        &Generation,
+        &CreatureName,
+        Has<Swimming>,
+        &GridPosition,
+        Option<&Parasites>,
+        Option<&mut Satiated>,
+        Has<Infected>,
+    ), (With<Creature>, Without<Dead>)>,
+) {
+    let dt = time.delta().as_secs_f32();
+
+    for (entity, mut hunger, mut age, mut cooldown_opt, stats, digesting, mut overfed_opt, dna, generation_comp, name, swimming, pos, parasites, satiated, infected) in q_creatures.iter_mut() {
+
+        // 1. Growth
+        age.seconds_alive += dt;
+        let sc = cfg.s(stats.species_id);
+        if !age.is_adult && age.seconds_alive > sc.adult_seconds {
+            age.is_adult = true;
+        }
+        if age.is_adult && !age.is_elder && age.seconds_alive > sc.elder_seconds {
+            age.is_elder = true;
+        }
+
+        // 2. Hunger (METABOLISM GENE)
+        // We use dna.metabolism_rate.
+        // Babies burn 50% of their adult genetic rate.
+        let mut burn_mult = if age.is_adult { 1.0 } else { 0.5 };
+        if swimming {
+            burn_mult *= cfg.swim_hunger_multiplier;
+        }
+        if let Some(parasites) = parasites {
+            burn_mult *= 1.0 + cfg.parasite_hunger_per_tick * parasites.count as f32;
+        }
+        if infected {
+            burn_mult *= cfg.infection_hunger_multiplier;
+        }
+        hunger.0 += dna.metabolism_rate * burn_mult * sc.time_scale.max(0.0) * dt;
+
+        // 2. Digestion
+        if digesting.is_some() {
+            // Burn off the "Overheal" (Waiting for hunger to reach 0.0)
+            if hunger.0 >= 0.0 {
+                commands.entity(entity).remove::<Digesting>();
+                commands.entity(entity).insert(Overfed(Timer::from_seconds(5.0, TimerMode::Once)));
+                if cfg.satiation_seconds > 0.0 {
+                    commands.entity(entity).insert(Satiated(Timer::from_seconds(cfg.satiation_seconds, TimerMode::Once)));
+                }
+            }
+        }
+        else if let Some(ref mut overfed_timer) = overfed_opt {
+            overfed_timer.0.tick(time.delta());
+            if overfed_timer.0.is_finished() {
+                commands.entity(entity).remove::<Overfed>();
+            }
+        }
+
+        if let Some(mut satiated) = satiated {
+            satiated.0.tick(time.delta());
+            if satiated.0.is_finished() {
+                commands.entity(entity).remove::<Satiated>();
+            }
+        }
+
+        // 3. Cooldown Timer Tick
+        if let Some(ref mut timer) = cooldown_opt {
+            timer.0.tick(time.delta());
+            if timer.0.is_finished() {
+                commands.entity(entity).remove::<ReproductionCooldown>();
+            }
+        }
+
+        // 4. Starvation, or old age
+        let cause = if hunger.0 >= 100.0 {
+            DeathCause::Starvation
+        } else if age.is_elder && sc.lifespan_seconds > 0.0 && age.seconds_alive > sc.lifespan_seconds {
+            DeathCause::OldAge
+        } else {
+            continue;
+        };
+        commands.entity(entity).insert(Dead { cause });
+
+        deaths.write(DeathEvent {
+            entity,
+            species_id: stats.species_id,
+            name: name.0.clone(),
+            generation: generation_comp.0,
+            dna: *dna,
+            pos: (pos.x, pos.y),
+            cause,
+        });
+    }
+}
+
+// SYSTEM 2: Handling Eating (Interactions with Plants)
+// Hungry grazers nibble the grass under them: a little hunger back, a little biomass gone
+pub fn grass_grazing_system(
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    weather: Res<Weather>,
+    tile_index: Res<TileIndex>,
+    mut q_grazers: Query<(&GridPosition, &mut Hunger, &CreatureStats, &StreamKey), (With<Creature>, Without<Dead>, Without<Digesting>)>,
+    mut q_grass: Query<&mut Grass>,
+) {
+    let bite = cfg.grass_graze_rate * time.delta_secs();
+    // Storms keep all but the desperate from grazing (see creature_eating)
+    let min_hunger = if weather.kind == WeatherKind::Storm { cfg.storm_graze_hunger } else { cfg.grass_graze_hunger };
+
+    // In StreamKey order: the first to graze a thin patch gets the last of it
+    let mut grazers: Vec<_> = q_grazers.iter_mut().collect();
+    grazers.sort_by_key(|(.., key)| key.0);
+
+    for (pos, mut hunger, stats, _) in grazers {
+        if !cfg.s(stats.species_id).grazer || hunger.0 < min_hunger { continue; }
+        let Some(&tile) = tile_index.0.get(&(pos.x, pos.y)) else { continue; };
+        let Ok(mut grass) = q_grass.get_mut(tile) else { continue; };
+
+        let eaten = bite.min(grass.0);
+        if eaten <= 0.0 { continue; }
+        grass.0 -= eaten;
+        hunger.0 = (hunger.0 - eaten * cfg.grass_hunger_per_biomass).max(0.0);
+    }
+}
+
+pub fn creature_eating(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    weather: Res<Weather>,
+    // 1. We added Option<&ReproductionCooldown> to the query tuple below
+    mut q_creatures: Query<(
+        Entity,
+        &GridPosition,
+        &mut Hunger,
+        &CreatureStats,
+        &CreatureBehavior,
+        &Age,
+        Option<&Digesting>,
+        Option<&ReproductionCooldown>,
+        &StreamKey,
+    ), (With<Creature>, Without<Dead>)>,
+    q_plants: Query<(Entity, &GridPosition, Has<ShadeTolerant>), (With<Plant>, Without<Dead>)>,
+    q_all_creatures: Query<(Entity, &GridPosition, &CreatureStats), (With<Creature>, Without<Dead>)>,
+    tile_index: Res<TileIndex>,
+    mut q_fertility: Query<&mut Fertility>,
+    mut eats: MessageWriter<EatEvent>,
+) {
+    // In StreamKey order: the first creature on a plant's tile gets it
+    let mut eaters: Vec<_> = q_creatures.iter_mut().collect();
+    eaters.sort_by_key(|(.., key)| key.0);
+
+    for (plant_entity, plant_pos, fern) in q_plants.iter() {
+        // 2. Added 'cooldown' to the destructuring here
+        for (my_entity, my_pos, my_hunger, my_stats, my_behavior, my_age, digesting, cooldown, _) in eaters.iter_mut() {
+            let my_entity = *my_entity;
+            if digesting.is_some() { continue; }
+
+            if my_pos.x != plant_pos.x || my_pos.y != plant_pos.y {
+                continue;
+            }
+
+            let is_sheep = my_stats.species_id == 0;
+            let is_wolf = my_stats.species_id == 1;
+
+            // Sheep can always eat plants (existing behavior)
+            // Wolves can eat plants only if:
+            // - baby wolf
+            // - OR hunger <= 30
+            // - OR moderately hungry (hunger >= 50) <-- CHANGED from 70
+            // - OR currently on Reproduction Cooldown
+            let wolf_can_eat_plant = !my_age.is_adult
+                || my_hunger.0 <= 30.0
+                || my_hunger.0 >= 50.0
+                || cooldown.is_some();
+
+            if is_wolf && !wolf_can_eat_plant {
+                continue;
+            }
+
+            if my_pos.x == plant_pos.x && my_pos.y == plant_pos.y {
+                // Full check (keep it: no point eating if already essentially full)
+                if my_hunger.0 < cfg.eat_skip_if_hunger_below { continue; }
+
+                // Nobody grazes in a storm unless it's getting desperate
+                if weather.kind == WeatherKind::Storm && my_hunger.0 < cfg.storm_graze_hunger { continue; }
+
+                // Altruism only applies to sheep (wolves ignore altruism)
+                if is_sheep {
+                    let mut should_eat = true;
+                    if my_behavior.altruistic && my_hunger.0 < 20.0 {
+                        for (other_entity, other_pos, other_stats) in q_all_creatures.iter() {
+                            if my_entity == other_entity { continue; }
+                            let dist = (my_pos.x - other_pos.x).abs() + (my_pos.y - other_pos.y).abs();
+                            if other_stats.species_id == my_stats.species_id && dist <= my_stats.sight_range {
+                                should_eat = false;
+                                break;
+                            }
+                        }
+                    }
+                    if !should_eat { continue; }
+                }
+
+                // Eat plant
+                let nutrition = if fern { cfg.fern_nutrition } else { cfg.plant_nutrition };
+                my_hunger.0 = (my_hunger.0 - nutrition).max(0.0);
+                commands.entity(plant_entity).insert(Dead { cause: DeathCause::Removed });
+                eats.write(EatEvent { entity: my_entity, species_id: my_stats.species_id, pos: (my_pos.x, my_pos.y) });
+
+                // If wolf: apply 2-tick berry stun
+                if my_stats.species_id == 1 {
+                    let stun_seconds = cfg.base_move_seconds * (cfg.wolf_berry_stun_ticks as f32);
+                    commands.entity(my_entity).insert(BerryStun(Timer::from_seconds(stun_seconds, TimerMode::Once)));
+                }
+
+                // Knock the tile's fertility back to zero (longer recovery if overgrazed)
+                if let Some(&tile) = tile_index.0.get(&(my_pos.x, my_pos.y)) {
+                    if let Ok(mut fertility) = q_fertility.get_mut(tile) {
+                        fertility.graze(&cfg);
+                    }
+                }
+
+                // Spawn Exhausted Soil marker
+                commands.spawn((ExhaustedSoil, GridPosition { x: my_pos.x, y: my_pos.y }));
+
+                break; // plant eaten
+            }
+        }
+    }
+}
+
+// SYSTEM 3: Handling Reproduction (Interactions with other Creatures)
+pub fn creature_reproduction(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    mut births: MessageWriter<BirthEvent>,
+    sim_rng: Res<SimRng>,
+    // Fetch Dna, Generation, Name
+    q_creatures: Query<(
+        Entity,
+        &GridPosition,
+        &Age,
+        &CreatureStats,
+        &CreatureBehavior,
+        Option<&ReproductionCooldown>,
+        &Dna,            // <---
+        &Generation,     // <---
+        &CreatureName,   // <---
+        Option<&Mood>,
+        Option<&Clade>,
+        Option<&Founder>,
+        &StreamKey,
+    ), (With<Creature>, Without<Dead>)>,
+) {
+    // Living counts, kept up to date as babies arrive so caps hold within the frame
+    let mut counts: HashMap<u32, u32> = HashMap::new();
+    for (_, _, _, stats, ..) in q_creatures.iter() {
+        *counts.entry(stats.species_id).or_default() += 1;
+    }
+    let mut total: u32 = counts.values().sum();
+    let mut occupancy: HashMap<(i32, i32), u32> = HashMap::new();
+    for (_, pos, ..) in q_creatures.iter() {
+        *occupancy.entry((pos.x, pos.y)).or_default() += 1;
+    }
+    let mut rng = sim_rng.stream("births");
+
+    // Pairs in StreamKey order, so who mothers whom doesn't depend on the query
+    let mut creatures: Vec<_> = q_creatures.iter().collect();
+    creatures.sort_by_key(|(.., key)| key.0);
+    let pairs = creatures.iter().enumerate().flat_map(|(i, a)| creatures[i + 1..].iter().map(move |b| (*a, *b)));
+
+    for ((entity_a, pos_a, age_a, stats_a, behavior_a, cooldown_a, dna_a, gen_a, name_a, mood_a, clade_a, founder_a, _),
+    (entity_b, pos_b, age_b, stats_b, _,          cooldown_b, dna_b, gen_b, name_b, mood_b, clade_b, founder_b, _)) in pairs
+    {
+        if !age_a.is_adult || !age_b.is_adult || age_a.is_elder || age_b.is_elder { continue; }
+        if cooldown_a.is_some() || cooldown_b.is_some() { continue; }
+        if stats_a.species_id != stats_b.species_id { continue; }
+        // Split-off species don't interbreed with their parent any more
+        if clade_a != clade_b { continue; }
+
+        let dist = (pos_a.x - pos_b.x).abs() + (pos_a.y - pos_b.y).abs();
+        if dist > 1 { continue; }
+
+        let sid = stats_a.species_id;
+        let sc = cfg.s(sid);
+
+        // Over the cap: no births until numbers drop
+        let species_count = counts.get(&sid).copied().unwrap_or(0);
+        if !cfg.births_allowed(sid, species_count, total) { continue; }
+
+        // Born beside the mother, or the father if her tile is full
+        let Some((baby_x, baby_y)) = [(pos_a.x, pos_a.y), (pos_b.x, pos_b.y)]
+            .into_iter()
+            .find(|tile| !cfg.tile_full(occupancy.get(tile).copied().unwrap_or(0)))
+        else { continue };
+
+        // Mix Genes!
+        // Unhappy parents breed less
+        let mood = |m: Option<&Mood>| cfg.mood_breed_multiplier(m.map(|m| m.kind).unwrap_or_default());
+        if rng.random::<f32>() < sc.reproduction_chance * mood(mood_a) * mood(mood_b) {
+            *counts.entry(sid).or_default() += 1;
+            total += 1;
+            *occupancy.entry((baby_x, baby_y)).or_default() += 1;
+
+            // 1. Create Baby DNA
+            let baby_dna = mutate_dna(&mut rng, dna_a, dna_b);
+
+            // 2. Increment Generation
+            let baby_gen = Generation(gen_a.0.max(gen_b.0) + 1);
+            // ...down the deeper parent's line
+            let (deeper_founder, deeper_name) = if gen_b.0 > gen_a.0 { (founder_b, name_b) } else { (founder_a, name_a) };
+            let baby_founder = deeper_founder.cloned().unwrap_or_else(|| Founder(deeper_name.0.clone()));
+
+            // 3. New Name
+            let baby_name = generate_name(&mut rng);
+
+            // Spawn
+            let generation = baby_gen.0;
+            let baby = commands.spawn((
+                Creature,
+                GridPosition { x: baby_x, y: baby_y },
+                NextMove::default(),
+                Hunger(0.0),
+                // Use Baby DNA for stats
+                CreatureStats { sight_range: baby_dna.sight_range, species_id: sid },
+                CreatureBehavior { scared_of_water: behavior_a.scared_of_water, altruistic: behavior_a.altruistic },
+                Age { seconds_alive: 0.0, is_adult: false, is_elder: false },
+                History::new(baby_x, baby_y),
+                baby_dna,
+                baby_gen,
+                baby_founder,
+                StreamKey::new(&baby_name, (baby_x, baby_y)),
+                CreatureName(baby_name.clone()),
+                Mother(entity_a),
+            )).id();
+            if let Some(clade) = clade_a {
+                commands.entity(baby).insert(*clade);
+            }
+
+            births.write(BirthEvent {
+                entity: baby,
+                parents: (entity_a, entity_b),
+                species_id: sid,
+                name: baby_name,
+                generation,
+                dna: baby_dna,
+                pos: (baby_x, baby_y),
+            });
+
+            let cd = sc.reproduction_cooldown_seconds;
+            commands.entity(entity_a).insert(ReproductionCooldown(Timer::from_seconds(cd, TimerMode::Once)));
+            commands.entity(entity_b).insert(ReproductionCooldown(Timer::from_seconds(cd, TimerMode::Once)));
+        }
+    }
+}
+
+pub fn reaper_system(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    q_dead: Query<(Entity, &Dead, Option<&GridPosition>, Option<&CreatureStats>)>,
+) {
+    for (entity, dead, pos, stats) in q_dead.iter() {
+        // Despawn safely. If it's already gone, this won't crash
+        // because we are iterating existing entities.
+        commands.entity(entity).despawn();
+
+        if let (Some(pos), Some(stats)) = (pos, stats) {
+            if dead.cause.leaves_corpse() && cfg.corpse_seconds > 0.0 {
+                commands.spawn((
+                    Corpse { species_id: stats.species_id, cause: dead.cause, seconds_left: cfg.corpse_seconds },
+                    GridPosition { x: pos.x, y: pos.y },
+                ));
+            }
+        }
+    }
+}
+
+// Corpses rot away; beyond corpse_budget the oldest go first
+pub fn corpse_decay_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    mut q_corpses: Query<(Entity, &mut Corpse, &GridPosition), Without<Dead>>,
+) {
+    let mut remaining: Vec<(f32, (i32, i32), Entity)> = Vec::new();
+    for (entity, mut corpse, pos) in q_corpses.iter_mut() {
+        corpse.seconds_left -= time.delta_secs();
+        if corpse.seconds_left <= 0.0 {
+            commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+        } else {
+            remaining.push((corpse.seconds_left, (pos.x, pos.y), entity));
+        }
+    }
+
+    let excess = remaining.len().saturating_sub(cfg.corpse_budget as usize);
+    if excess == 0 { return; }
+    // Sorted by position on ties so runs stay deterministic
+    remaining.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    for (_, _, entity) in remaining.into_iter().take(excess) {
+        commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+    }
+}
+
+// --- EXTINCTION ---
+pub fn detect_extinctions(
+    mut watch: ResMut<ExtinctionWatch>,
+    stats: Res<GameStats>,
+    cfg: Res<SimulationConfig>,
+    q_creatures: Query<&CreatureStats, (With<Creature>, Without<Dead>)>,
+    mut extinctions: MessageWriter<ExtinctionEvent>,
+) {
+    let living: HashSet<u32> = q_creatures.iter().map(|s| s.species_id).collect();
+
+    for &species_id in cfg.species.keys() {
+        let was_alive = watch.alive.contains(&species_id);
+        let is_alive = living.contains(&species_id);
+        if was_alive && !is_alive {
+            extinctions.write(ExtinctionEvent { species_id, day: stats.days });
+        }
+    }
+
+    watch.alive = living;
+}
+
+pub fn record_extinctions(
+    mut events: MessageReader<ExtinctionEvent>,
+    cfg: Res<SimulationConfig>,
+    mut pop: ResMut<PopulationStats>,
+    mut logger: ResMut<SimulationLogger>,
+    mut notifications: ResMut<Notifications>,
+    mut watch: ResMut<ExtinctionWatch>,
+    mut timeline: ResMut<WorldTimeline>,
+) {
+    for event in events.read() {
+        let name = cfg.s(event.species_id).name.clone();
+        pop.species.entry(event.species_id).or_default().extinctions += 1;
+        timeline.push(event.day, event.species_id, TimelineKind::Extinct);
+        logger.log_species("Extinct", event.day, &name);
+
+        if cfg.extinction_restock_enabled {
+            watch.restock_in.insert(event.species_id, cfg.extinction_restock_delay_seconds);
+            notifications.push(
+                format!("{} went extinct! Reintroducing in {:.0}s", name, cfg.extinction_restock_delay_seconds),
+                &cfg,
+            );
+        } else {
+            notifications.push(format!("{} went extinct!", name), &cfg);
+        }
+    }
+}
+
+// Drop a small seed population of founders onto random dry land
+pub fn restock_extinct_species(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    tile_index: Res<TileIndex>,
+    q_water: Query<(), With<Water>>,
+    mut watch: ResMut<ExtinctionWatch>,
+    mut pop: ResMut<PopulationStats>,
+    mut logger: ResMut<SimulationLogger>,
+    mut notifications: ResMut<Notifications>,
+    mut timeline: ResMut<WorldTimeline>,
+    sim_rng: Res<SimRng>,
+) {
+    let mut rng = sim_rng.stream("restock");
+    let dt = time.delta().as_secs_f32();
+    let mut due = Vec::new();
+    for (&species_id, left) in watch.restock_in.iter_mut() {
+        *left -= dt;
+        if *left <= 0.0 {
+            due.push(species_id);
+        }
+    }
+
+    due.sort(); // spawn order must not depend on HashMap order
+    let map_size = cfg.map_size;
+    for species_id in due {
+        watch.restock_in.remove(&species_id);
+        // Someone may have come back some other way (config reload, new game)
+        if watch.alive.contains(&species_id) { continue; }
+
+        let mut placed = 0;
+        for _ in 0..(cfg.extinction_restock_count * 20) {
+            if placed >= cfg.extinction_restock_count { break; }
+            let x = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+            let y = (rng.random::<i32>().abs() % (map_size * 2)) - map_size;
+            let dry = tile_index.0.get(&(x, y)).map(|&t| q_water.get(t).is_err()).unwrap_or(false);
+            if !dry { continue; }
+
+            spawn_founder(&mut commands, &cfg, &mut pop, &mut logger, &mut rng, species_id, (x, y), stats.days);
+            placed += 1;
+        }
+
+        let name = &cfg.s(species_id).name;
+        logger.log_species("Restock", stats.days, name);
+        timeline.push(stats.days, species_id, TimelineKind::Reintroduced(placed));
+        notifications.push(format!("{} reintroduced ({})", name, placed), &cfg);
+    }
+}
+
+// --- EVENT LISTENERS ---
+// CSV log of births, deaths and adoptions (kills show up as Predation deaths,
+// weather in the day summaries)
+pub fn log_simulation_events(
+    game_stats: Res<GameStats>,
+    cfg: Res<SimulationConfig>,
+    mut logger: ResMut<SimulationLogger>,
+    mut births: MessageReader<BirthEvent>,
+    mut deaths: MessageReader<DeathEvent>,
+    mut adoptions: MessageReader<AdoptionEvent>,
+) {
+    for e in births.read() {
+        logger.log("Born", game_stats.days, &cfg.s(e.species_id).name, &e.name, e.generation, &e.dna);
+    }
+
+    for e in deaths.read() {
+        logger.log(e.cause.label(), game_stats.days, &cfg.s(e.species_id).name, &e.name, e.generation, &e.dna);
+    }
+
+    for e in adoptions.read() {
+        logger.log("Adopted", game_stats.days, &cfg.s(e.species_id).name, &e.name, e.generation, &e.dna);
+    }
+}
+
+// --- BIOGRAPHIES ---
+#[derive(Clone, Debug)]
+pub struct LifeEntry {
+    pub day: f32, // when it first happened, for a run
+    pub what: String,
+    pub times: u32,
+}
+
+impl LifeEntry {
+    pub fn describe(&self) -> String {
+        if self.times > 1 { format!("{} x{}", self.what, self.times) } else { self.what.clone() }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct Biography {
+    pub entries: VecDeque<LifeEntry>,
+    pub alarmed_by: Option<u32>, // species it's running from, so the escape can be told
+}
+
+impl Biography {
+    // The same thing twice in a row just counts up ("Ate x3"); beyond `keep`
+    // entries the oldest go
+    pub fn note(&mut self, day: f32, what: impl Into<String>, keep: usize) {
+        let what = what.into();
+        match self.entries.back_mut() {
+            Some(last) if last.what == what => last.times += 1,
+            _ => self.entries.push_back(LifeEntry { day, what, times: 1 }),
+        }
+        while self.entries.len() > keep.max(1) {
+            self.entries.pop_front();
+        }
+    }
+}
+
+// Living creatures' stories. Not saved: loaded creatures start a new one.
+#[derive(Resource, Default)]
+pub struct Biographies(pub HashMap<Entity, Biography>);
+
+// Notes births, meals, kills, escapes, ticks, adoptions and offspring; on death
+// the whole story goes to the console and the event log, then it's forgotten
+pub fn biography_system(
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    mut bios: ResMut<Biographies>,
+    mut logger: ResMut<SimulationLogger>,
+    mut births: MessageReader<BirthEvent>,
+    mut deaths: MessageReader<DeathEvent>,
+    mut eats: MessageReader<EatEvent>,
+    mut kills: MessageReader<KillEvent>,
+    mut adoptions: MessageReader<AdoptionEvent>,
+    q_names: Query<&CreatureName>,
+    q_creatures: Query<(Entity, Option<&Alarmed>, Option<Ref<Parasites>>), (With<Creature>, Without<Dead>)>,
+) {
+    let today = stats.days;
+    let keep = cfg.biography_length;
+    let name_of = |entity: Entity| q_names.get(entity).map(|n| n.0.clone()).unwrap_or_else(|_| "?".into());
+
+    for e in births.read() {
+        let (a, b) = e.parents;
+        bios.0.entry(e.entity).or_default().note(today, format!("Born to {} and {}", name_of(a), name_of(b)), keep);
+        for parent in [a, b] {
+            if let Some(bio) = bios.0.get_mut(&parent) {
+                bio.note(today, format!("Had {}", e.name), keep);
+            }
+        }
+    }
+
+    for (entity, alarmed, parasites) in q_creatures.iter() {
+        // Whoever's around without a story was there from the start (or loaded)
+        let bio = bios.0.entry(entity).or_insert_with(|| {
+            let mut bio = Biography::default();
+            bio.note(today, "Arrived", keep);
+            bio
+        });
+        // An alarm that ran out with the creature still alive was an escape
+        match (bio.alarmed_by, alarmed) {
+            (None, Some(alarmed)) => bio.alarmed_by = Some(alarmed.threat_species),
+            (Some(species), None) => {
+                bio.alarmed_by = None;
+                bio.note(today, format!("Escaped the {}", cfg.s(species).name), keep);
+            }
+            _ => {}
+        }
+        if parasites.is_some_and(|p| p.is_added()) {
+            bio.note(today, "Picked up ticks", keep);
+        }
+    }
+
+    for e in eats.read() {
+        if let Some(bio) = bios.0.get_mut(&e.entity) {
+            bio.note(today, "Ate", keep);
+        }
+    }
+
+    let deaths: Vec<DeathEvent> = deaths.read().cloned().collect();
+    for e in kills.read() {
+        let prey = deaths
+            .iter()
+            .find(|d| d.entity == e.prey)
+            .map(|d| format!("Killed {} ({})", d.name, cfg.s(d.species_id).name))
+            .unwrap_or_else(|| "Made a kill".into());
+        if let Some(bio) = bios.0.get_mut(&e.predator) {
+            bio.note(today, prey, keep);
+        }
+    }
+
+    for e in adoptions.read() {
+        if let Some(bio) = bios.0.get_mut(&e.baby) {
+            bio.note(today, format!("Adopted by {}", e.foster_name), keep);
+        }
+        if let Some(bio) = bios.0.get_mut(&e.foster) {
+            bio.note(today, format!("Adopted {}", e.name), keep);
+        }
+    }
+
+    for e in &deaths {
+        let Some(mut bio) = bios.0.remove(&e.entity) else { continue; };
+        bio.note(today, e.cause.label(), keep);
+        let species = &cfg.s(e.species_id).name;
+        for entry in &bio.entries {
+            logger.log_life(&entry.describe(), entry.day, species, &e.name, e.generation);
+        }
+    }
+}
+
+// --- WORLD RECORDS ---
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum RecordKind {
+    LongestLived,   // days
+    MostOffspring,
+    MostKills,
+    LargestHerd,    // creatures of one species linked within herd_link_distance
+    LongestDrought, // days, with someone alive at the end
+}
+
+impl RecordKind {
+    pub const ALL: [RecordKind; 5] = [
+        RecordKind::LongestLived,
+        RecordKind::MostOffspring,
+        RecordKind::MostKills,
+        RecordKind::LargestHerd,
+        RecordKind::LongestDrought,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecordKind::LongestLived => "Longest lived",
+            RecordKind::MostOffspring => "Most offspring",
+            RecordKind::MostKills => "Most kills",
+            RecordKind::LargestHerd => "Largest herd",
+            RecordKind::LongestDrought => "Longest drought survived",
+        }
+    }
+
+    pub fn format_value(&self, value: f32) -> String {
+        match self {
+            RecordKind::LongestLived | RecordKind::LongestDrought => format!("{:.1} days", value),
+            _ => format!("{:.0}", value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorldRecord {
+    pub kind: RecordKind,
+    pub value: f32,
+    pub holder: String, // "Name (Species)", or what the record was about
+    pub day: f32,       // when it was set
+}
+
+// Best ever per RecordKind, saved with the world. The tallies behind them are
+// not: creatures from a load start counting from zero.
+#[derive(Resource, Default)]
+pub struct WorldRecords {
+    pub best: Vec<WorldRecord>,
+    pub tallies: HashMap<Entity, (u32, u32)>, // living creature -> (offspring, kills)
+    pub drought_since: Option<f32>,
+    pub last_herd_day: Option<u32>,
+}
+
+impl WorldRecords {
+    pub fn get(&self, kind: RecordKind) -> Option<&WorldRecord> {
+        self.best.iter().find(|r| r.kind == kind)
+    }
+
+    // Keeps `value` if it beats the record (or there is none yet)
+    pub fn offer(&mut self, kind: RecordKind, value: f32, holder: impl Into<String>, day: f32) {
+        match self.best.iter_mut().find(|r| r.kind == kind) {
+            Some(record) if value <= record.value => {}
+            Some(record) => *record = WorldRecord { kind, value, holder: holder.into(), day },
+            None => self.best.push(WorldRecord { kind, value, holder: holder.into(), day }),
+        }
+    }
+}
+
+// Size of the biggest group in `positions` where each member is within `link`
+// tiles of another
+pub fn largest_group(positions: &[(i32, i32)], link: i32) -> usize {
+    let mut seen = vec![false; positions.len()];
+    let mut largest = 0;
+    for start in 0..positions.len() {
+        if seen[start] { continue; }
+        seen[start] = true;
+        let mut stack = vec![start];
+        let mut size = 0;
+        while let Some(i) = stack.pop() {
+            size += 1;
+            let (x, y) = positions[i];
+            for j in 0..positions.len() {
+                if seen[j] { continue; }
+                let (ox, oy) = positions[j];
+                if (x - ox).abs() + (y - oy).abs() <= link {
+                    seen[j] = true;
+                    stack.push(j);
+                }
+            }
+        }
+        largest = largest.max(size);
+    }
+    largest
+}
+
+pub fn world_records_system(
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    mut records: ResMut<WorldRecords>,
+    mut births: MessageReader<BirthEvent>,
+    mut kills: MessageReader<KillEvent>,
+    mut deaths: MessageReader<DeathEvent>,
+    mut weather: MessageReader<WeatherChanged>,
+    q_creatures: Query<(Entity, &GridPosition, &CreatureStats, &CreatureName, &Age), (With<Creature>, Without<Dead>)>,
+) {
+    let today = stats.days;
+    let holder = |entity: Entity| {
+        q_creatures
+            .get(entity)
+            .ok()
+            .map(|(_, _, creature_stats, name, _)| format!("{} ({})", name.0, cfg.s(creature_stats.species_id).name))
+    };
+
+    for e in births.read() {
+        for parent in [e.parents.0, e.parents.1] {
+            let tally = records.tallies.entry(parent).or_default();
+            tally.0 += 1;
+            let offspring = tally.0;
+            if let Some(holder) = holder(parent) {
+                records.offer(RecordKind::MostOffspring, offspring as f32, holder, today);
+            }
+        }
+    }
+    for e in kills.read() {
+        let tally = records.tallies.entry(e.predator).or_default();
+        tally.1 += 1;
+        let kills = tally.1;
+        if let Some(holder) = holder(e.predator) {
+            records.offer(RecordKind::MostKills, kills as f32, holder, today);
+        }
+    }
+    for e in deaths.read() {
+        records.tallies.remove(&e.entity);
+    }
+
+    // The oldest creature alive holds the age record while it keeps living
+    if let Some((entity, .., age)) = q_creatures
+        .iter()
+        .max_by(|(ea, .., a), (eb, .., b)| a.seconds_alive.total_cmp(&b.seconds_alive).then(eb.to_bits().cmp(&ea.to_bits())))
+    {
+        if let Some(holder) = holder(entity) {
+            records.offer(RecordKind::LongestLived, age.seconds_alive / SECONDS_PER_DAY, holder, today);
+        }
+    }
+
+    for e in weather.read() {
+        match (e.kind == WeatherKind::Drought, records.drought_since) {
+            (true, None) => records.drought_since = Some(today),
+            (false, Some(since)) => {
+                records.drought_since = None;
+                let survivors = q_creatures.iter().count();
+                if survivors > 0 {
+                    records.offer(RecordKind::LongestDrought, today - since, format!("{} survivors", survivors), today);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Herds once a sim-day: grouping is quadratic in a species' numbers
+    let day = today as u32;
+    if records.last_herd_day == Some(day) { return; }
+    records.last_herd_day = Some(day);
+    let mut by_species: HashMap<u32, Vec<(i32, i32)>> = HashMap::new();
+    for (_, pos, creature_stats, ..) in q_creatures.iter() {
+        by_species.entry(creature_stats.species_id).or_default().push((pos.x, pos.y));
+    }
+    let mut species: Vec<u32> = by_species.keys().copied().collect();
+    species.sort_unstable();
+    for species_id in species {
+        let mut positions = by_species.remove(&species_id).unwrap_or_default();
+        positions.sort_unstable();
+        let size = largest_group(&positions, cfg.herd_link_distance);
+        records.offer(RecordKind::LargestHerd, size as f32, cfg.s(species_id).name.clone(), today);
+    }
+}
+
+// --- TIMELINE ---
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum TimelineKind {
+    Extinct,
+    Reintroduced(u32), // founders placed
+    Peak(u32),         // living count at the turn
+    Trough(u32),
+}
+
+impl TimelineKind {
+    pub fn describe(&self) -> String {
+        match self {
+            TimelineKind::Extinct => "went extinct".to_string(),
+            TimelineKind::Reintroduced(n) => format!("reintroduced ({})", n),
+            TimelineKind::Peak(n) => format!("peaked at {}", n),
+            TimelineKind::Trough(n) => format!("bottomed out at {}", n),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub day: f32,
+    pub species_id: u32,
+    pub kind: TimelineKind,
+}
+
+// Where a species' numbers are heading since the last turn, and the furthest
+// they've got. Not saved: a load starts watching afresh.
+#[derive(Clone, Copy, Debug)]
+pub struct SwingWatch {
+    pub rising: bool,
+    pub extreme: u32,
+    pub extreme_day: f32,
+}
+
+// Species-level history of the world, oldest first. Saved with the world.
+#[derive(Resource, Default)]
+pub struct WorldTimeline {
+    pub entries: Vec<TimelineEntry>,
+    pub swings: HashMap<u32, SwingWatch>,
+    pub last_day: Option<u32>,
+}
+
+impl WorldTimeline {
+    pub fn push(&mut self, day: f32, species_id: u32, kind: TimelineKind) {
+        self.entries.push(TimelineEntry { day, species_id, kind });
+        // Peaks are dated back to when they happened, so keep the list in day order
+        self.entries.sort_by(|a, b| a.day.total_cmp(&b.day));
+    }
+
+    // Entries within `radius` days of `day`, nearest first
+    pub fn around(&self, day: f32, radius: f32) -> Vec<&TimelineEntry> {
+        let mut near: Vec<&TimelineEntry> = self.entries.iter().filter(|e| (e.day - day).abs() <= radius).collect();
+        near.sort_by(|a, b| (a.day - day).abs().total_cmp(&(b.day - day).abs()));
+        near
+    }
+
+    // One daily count: marks the last extreme as a peak (trough) once numbers
+    // have fallen (risen) far enough from it. Dying out is Extinct, not a trough.
+    pub fn observe(&mut self, cfg: &SimulationConfig, day: f32, species_id: u32, count: u32) {
+        let watch = self.swings.entry(species_id).or_insert(SwingWatch { rising: true, extreme: count, extreme_day: day });
+        let swing = ((watch.extreme as f32 * cfg.timeline_swing) as u32).max(cfg.timeline_min_swing);
+        let turned = if watch.rising {
+            if count >= watch.extreme {
+                watch.extreme = count;
+                watch.extreme_day = day;
+                None
+            } else {
+                (watch.extreme - count >= swing).then_some(TimelineKind::Peak(watch.extreme))
+            }
+        } else if count <= watch.extreme {
+            watch.extreme = count;
+            watch.extreme_day = day;
+            None
+        } else {
+            (count - watch.extreme >= swing).then_some(TimelineKind::Trough(watch.extreme))
+        };
+
+        let Some(kind) = turned else { return; };
+        let at = watch.extreme_day;
+        *watch = SwingWatch { rising: !watch.rising, extreme: count, extreme_day: day };
+        if kind != TimelineKind::Trough(0) {
+            self.push(at, species_id, kind);
+        }
+    }
+}
+
+// Counts every species once a sim-day for the timeline's peaks and troughs
+pub fn timeline_system(
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    mut timeline: ResMut<WorldTimeline>,
+    q_creatures: Query<&CreatureStats, (With<Creature>, Without<Dead>)>,
+) {
+    let day = stats.days as u32;
+    if timeline.last_day == Some(day) { return; }
+    timeline.last_day = Some(day);
+
+    let mut counts: HashMap<u32, u32> = cfg.species.keys().map(|&id| (id, 0)).collect();
+    for creature_stats in q_creatures.iter() {
+        *counts.entry(creature_stats.species_id).or_default() += 1;
+    }
+    let mut species: Vec<u32> = counts.keys().copied().collect();
+    species.sort_unstable();
+    for species_id in species {
+        timeline.observe(&cfg, stats.days, species_id, counts[&species_id]);
+    }
+}
+
+// --- END OF DAY ---
+/// What happened over one sim-day, sent when the next one starts
+#[derive(Message, Clone, Debug, Default)]
+pub struct DayReport {
+    pub day: u32,
+    pub births: u32,
+    pub deaths: Vec<(DeathCause, u32)>, // in the order causes first came up
+    pub kills: u32,
+    pub average_hunger: f32, // over the living, as the day ends
+    pub population: Vec<(u32, u32)>, // (species_id, living), by species_id
+    pub weather: Vec<WeatherKind>,   // as the day began, then each change
+}
+
+impl DayReport {
+    pub fn deaths_total(&self) -> u32 {
+        self.deaths.iter().map(|&(_, n)| n).sum()
+    }
+
+    // One line, no commas (it goes into the CSV log as is)
+    pub fn describe(&self) -> String {
+        let deaths: Vec<String> = self.deaths.iter().map(|(cause, n)| format!("{} {}", cause.label(), n)).collect();
+        let weather: Vec<&str> = self.weather.iter().map(|w| w.label()).collect();
+        format!(
+            "{} births; {} deaths{}; {} kills; average hunger {:.0}; weather {}",
+            self.births,
+            self.deaths_total(),
+            if deaths.is_empty() { String::new() } else { format!(" ({})", deaths.join(" / ")) },
+            self.kills,
+            self.average_hunger,
+            weather.join(" > ")
+        )
+    }
+}
+
+// The day being tallied. Not saved: after a load the first report starts
+// with the next full day.
+#[derive(Resource, Default)]
+pub struct DayLedger {
+    pub current: Option<DayReport>,
+}
+
+pub fn day_report_system(
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    weather: Res<Weather>,
+    mut ledger: ResMut<DayLedger>,
+    mut logger: ResMut<SimulationLogger>,
+    mut births: MessageReader<BirthEvent>,
+    mut deaths: MessageReader<DeathEvent>,
+    mut kills: MessageReader<KillEvent>,
+    mut weather_changes: MessageReader<WeatherChanged>,
+    mut reports: MessageWriter<DayReport>,
+    q_creatures: Query<(&CreatureStats, &Hunger), (With<Creature>, Without<Dead>)>,
+) {
+    let day = stats.days as u32;
+
+    // A new day: close the old one. Hunger and numbers are as it ends.
+    if ledger.current.as_ref().is_some_and(|r| r.day != day) {
+        let mut report = ledger.current.take().unwrap();
+        let mut population: HashMap<u32, u32> = cfg.species.keys().map(|&id| (id, 0)).collect();
+        let mut hunger = 0.0;
+        for (creature_stats, h) in q_creatures.iter() {
+            *population.entry(creature_stats.species_id).or_default() += 1;
+            hunger += h.0;
+        }
+        let living: u32 = population.values().sum();
+        report.average_hunger = if living > 0 { hunger / living as f32 } else { 0.0 };
+        report.population = population.into_iter().collect();
+        report.population.sort_unstable();
+
+        let counts: Vec<String> = report.population.iter().map(|&(id, n)| format!("{} {}", n, cfg.s(id).name)).collect();
+        println!("Day {}: {}; {} alive.", report.day, report.describe(), counts.join(", "));
+        logger.log_day(&report);
+        reports.write(report);
+    }
+    let report = ledger.current.get_or_insert_with(|| DayReport { day, weather: vec![weather.kind], ..default() });
+
+    report.births += births.read().count() as u32;
+    report.kills += kills.read().count() as u32;
+    for e in deaths.read() {
+        match report.deaths.iter_mut().find(|(cause, _)| *cause == e.cause) {
+            Some((_, n)) => *n += 1,
+            None => report.deaths.push((e.cause, 1)),
+        }
+    }
+    for e in weather_changes.read() {
+        if report.weather.last() != Some(&e.kind) {
+            report.weather.push(e.kind);
+        }
+    }
+}
+
+// --- SEASONAL STATISTICS ---
+// Day reports summed per season, so seasons can be compared with each other
+#[derive(Clone, Debug)]
+pub struct SeasonTally {
+    pub index: u32, // seasons since day 0
+    pub season: Season,
+    pub days: u32,
+    pub births: u32,
+    pub starved: u32,
+    pub eaten: u32,
+    pub other_deaths: u32,
+    pub hunger_sum: f32, // of each day's average
+}
+
+impl SeasonTally {
+    pub fn average_hunger(&self) -> f32 {
+        if self.days > 0 { self.hunger_sum / self.days as f32 } else { 0.0 }
+    }
+}
+
+// Not saved: a loaded world starts comparing afresh
+#[derive(Resource, Default)]
+pub struct SeasonalStats {
+    pub tallies: Vec<SeasonTally>, // oldest first
+}
+
+impl SeasonalStats {
+    // Seasons of this kind that have ended (or all, the current one included,
+    // when there are none yet)
+    pub fn of(&self, season: Season) -> Vec<&SeasonTally> {
+        let current = self.tallies.last().map(|t| t.index);
+        let done: Vec<&SeasonTally> =
+            self.tallies.iter().filter(|t| t.season == season && Some(t.index) != current).collect();
+        if done.is_empty() { self.tallies.iter().filter(|t| t.season == season).collect() } else { done }
+    }
+
+    // Mean of `value` per season of this kind, None before there's been one
+    pub fn mean(&self, season: Season, value: impl Fn(&SeasonTally) -> f32) -> Option<f32> {
+        let tallies = self.of(season);
+        (!tallies.is_empty()).then(|| tallies.iter().map(|t| value(t)).sum::<f32>() / tallies.len() as f32)
+    }
+}
+
+pub fn seasonal_stats_system(
+    cfg: Res<SimulationConfig>,
+    mut stats: ResMut<SeasonalStats>,
+    mut reports: MessageReader<DayReport>,
+) {
+    for report in reports.read() {
+        let day = GameStats { days: report.day as f32 };
+        let index = day.season_index(&cfg);
+        if stats.tallies.last().is_none_or(|t| t.index != index) {
+            stats.tallies.push(SeasonTally {
+                index,
+                season: day.season(&cfg),
+                days: 0,
+                births: 0,
+                starved: 0,
+                eaten: 0,
+                other_deaths: 0,
+                hunger_sum: 0.0,
+            });
+        }
+        let tally = stats.tallies.last_mut().unwrap();
+        tally.days += 1;
+        tally.births += report.births;
+        for &(cause, n) in &report.deaths {
+            match cause {
+                DeathCause::Starvation => tally.starved += n,
+                DeathCause::Predation => tally.eaten += n,
+                _ => tally.other_deaths += n,
+            }
+        }
+        tally.hunger_sum += report.average_hunger;
+    }
+}
+
+// --- GENEALOGY ---
+// Everyone seen this game, dead or alive, for family trees. Not saved:
+// creatures from a load are the roots of theirs.
+#[derive(Clone, Debug)]
+pub struct Pedigree {
+    pub name: String,
+    pub species_id: u32,
+    pub generation: u32,
+    pub parents: Option<(Entity, Entity)>, // None for founders and anyone loaded
+    pub born_day: f32,
+    pub died: Option<(f32, DeathCause)>,
+}
+
+#[derive(Resource, Default)]
+pub struct Genealogy(pub HashMap<Entity, Pedigree>);
+
+impl Genealogy {
+    // `root`, its ancestors and its descendants
+    pub fn family_of(&self, root: Entity) -> HashSet<Entity> {
+        let mut family = HashSet::from([root]);
+        let mut up = vec![root];
+        while let Some(e) = up.pop() {
+            let Some((a, b)) = self.0.get(&e).and_then(|p| p.parents) else { continue; };
+            for parent in [a, b] {
+                if self.0.contains_key(&parent) && family.insert(parent) {
+                    up.push(parent);
+                }
+            }
+        }
+        let mut children: HashMap<Entity, Vec<Entity>> = HashMap::new();
+        for (&child, pedigree) in &self.0 {
+            if let Some((a, b)) = pedigree.parents {
+                children.entry(a).or_default().push(child);
+                children.entry(b).or_default().push(child);
+            }
+        }
+        let mut down = vec![root];
+        while let Some(e) = down.pop() {
+            for &child in children.get(&e).into_iter().flatten() {
+                if family.insert(child) {
+                    down.push(child);
+                }
+            }
+        }
+        family
+    }
+
+    // GraphViz DOT of these creatures, parents pointing at children. Each node
+    // carries its name, generation, lifespan and (if dead) cause of death.
+    pub fn to_dot(&self, cfg: &SimulationConfig, members: &HashSet<Entity>, today: f32) -> String {
+        let mut ids: Vec<Entity> = members.iter().copied().filter(|e| self.0.contains_key(e)).collect();
+        ids.sort_by_key(|e| e.to_bits());
+
+        let mut dot = String::from("digraph family {\n    rankdir=TB;\n    node [shape=box, fontname=\"Helvetica\"];\n");
+        for &e in &ids {
+            let p = &self.0[&e];
+            let end = p.died.map(|(day, _)| day).unwrap_or(today);
+            let fate = match p.died {
+                Some((_, cause)) => cause.label().to_string(),
+                None => "alive".to_string(),
+            };
+            dot.push_str(&format!(
+                "    n{} [label=\"{}\\n{} gen {}\\nday {:.1} to {:.1} ({:.1} days)\\n{}\"{}];\n",
+                e.to_bits(),
+                p.name.replace('"', "'"),
+                cfg.s(p.species_id).name,
+                p.generation,
+                p.born_day,
+                end,
+                end - p.born_day,
+                fate,
+                if p.died.is_none() { ", style=bold" } else { "" },
+            ));
+        }
+        for &e in &ids {
+            let Some((a, b)) = self.0[&e].parents else { continue; };
+            for parent in [a, b] {
+                if members.contains(&parent) && self.0.contains_key(&parent) {
+                    dot.push_str(&format!("    n{} -> n{};\n", parent.to_bits(), e.to_bits()));
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+pub fn genealogy_system(
+    stats: Res<GameStats>,
+    mut genealogy: ResMut<Genealogy>,
+    mut births: MessageReader<BirthEvent>,
+    mut deaths: MessageReader<DeathEvent>,
+    q_creatures: Query<(Entity, &CreatureStats, &CreatureName, &Generation, &Age), (With<Creature>, Without<Dead>)>,
+) {
+    let today = stats.days;
+    for e in births.read() {
+        genealogy.0.insert(e.entity, Pedigree {
+            name: e.name.clone(),
+            species_id: e.species_id,
+            generation: e.generation,
+            parents: Some(e.parents),
+            born_day: today,
+            died: None,
+        });
+    }
+    // Founders, restocks and loaded creatures: no parents on record
+    for (entity, creature_stats, name, generation, age) in q_creatures.iter() {
+        genealogy.0.entry(entity).or_insert_with(|| Pedigree {
+            name: name.0.clone(),
+            species_id: creature_stats.species_id,
+            generation: generation.0,
+            parents: None,
+            born_day: today - age.seconds_alive / SECONDS_PER_DAY,
+            died: None,
+        });
+    }
+    for e in deaths.read() {
+        if let Some(pedigree) = genealogy.0.get_mut(&e.entity) {
+            pedigree.died.get_or_insert((today, e.cause));
+        }
+    }
+}
+
+pub fn count_births(mut pop: ResMut<PopulationStats>, mut births: MessageReader<BirthEvent>) {
+    for e in births.read() {
+        let entry = pop.species.entry(e.species_id).or_default();
+        entry.born += 1;
+        entry.total_ever += 1;
+    }
+}
+
+pub fn weather_notifications(
+    cfg: Res<SimulationConfig>,
+    mut notifications: ResMut<Notifications>,
+    mut weather: MessageReader<WeatherChanged>,
+) {
+    for e in weather.read() {
+        notifications.push(format!("Weather: {}", e.kind.label()), &cfg);
+    }
+}
+
+pub fn handle_exhaustion(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    tile_index: Res<TileIndex>,
+    mut q_fertility: Query<&mut Fertility>,
+    q_markers: Query<(Entity, &GridPosition), (With<ExhaustedSoil>, Without<Dead>)>,
+) {
+    let dt = time.delta().as_secs_f32();
+
+    // Recover fertility along the configured curve
+    for mut fertility in q_fertility.iter_mut() {
+        fertility.graze_pressure = (fertility.graze_pressure - cfg.overgraze_decay_per_second * dt).max(0.0);
+
+        if fertility.level >= 1.0 { continue; }
+
+        fertility.recovered_from += dt;
+        let t = (fertility.recovered_from / fertility.recovery_seconds.max(0.01)).clamp(0.0, 1.0);
+        fertility.level = 1.0 - (1.0 - t).powf(cfg.fertility_recovery_exponent);
+    }
+
+    // Remove the Brown X once the soil is fertile enough again
+    for (entity, pos) in q_markers.iter() {
+        let recovered = tile_index
+            .0
+            .get(&(pos.x, pos.y))
+            .and_then(|&tile| q_fertility.get(tile).ok())
+            .map(|f| f.level >= cfg.exhausted_marker_until)
+            .unwrap_or(true);
+
+        if recovered {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// With the FX hard cap on, soil markers beyond fx_budget go, the ones whose
+// tile has recovered the most first (they'd be removed soonest anyway)
+pub fn cap_soil_markers(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    tile_index: Res<TileIndex>,
+    q_fertility: Query<&Fertility>,
+    q_markers: Query<(Entity, &GridPosition), (With<ExhaustedSoil>, Without<Dead>)>,
+) {
+    if !cfg.fx_hard_cap { return; }
+    let excess = q_markers.iter().count().saturating_sub(cfg.fx_budget as usize);
+    if excess == 0 { return; }
+
+    let level = |pos: &GridPosition| {
+        tile_index.0.get(&(pos.x, pos.y)).and_then(|&tile| q_fertility.get(tile).ok()).map(|f| f.level).unwrap_or(1.0)
+    };
+    let mut markers: Vec<(f32, (i32, i32), Entity)> = q_markers
+        .iter()
+        .map(|(entity, pos)| (level(pos), (pos.x, pos.y), entity))
+        .collect();
+    // Sorted by position on ties so runs stay deterministic
+    markers.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (_, _, entity) in markers.into_iter().take(excess) {
+        commands.entity(entity).insert(Dead { cause: DeathCause::Removed });
+    }
+}
+
+// Any adult whose behavior rules list the species as Prey eats it on contact,
+// predators included (see SpeciesBehaviors::hunts)
+pub fn predator_hunting_system(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    behaviors: Res<SpeciesBehaviors>,
+    sim_rng: Res<SimRng>,
+    mut q_hunters: Query<(Entity, &GridPosition, &mut Hunger, &CreatureStats, &Age, Has<Digesting>, Has<Satiated>, Has<Desperate>, &StreamKey), (With<Creature>, Without<Dead>)>,
+    q_prey: Query<(Entity, &GridPosition, &CreatureStats, &CreatureName, &Generation, &Dna, &Age, Has<Sheltered>, &StreamKey), (With<Creature>, Without<Dead>)>,
+    mut kills: MessageWriter<KillEvent>,
+    mut deaths: MessageWriter<DeathEvent>,
+) {
+    let night = stats.is_night(&cfg);
+
+    // Huts are shut at night
+    let mut prey_by_tile: HashMap<(i32, i32), Vec<(u64, Entity)>> = HashMap::new();
+    for (entity, pos, _, _, _, _, _, in_hut, key) in q_prey.iter() {
+        if night && in_hut { continue; }
+        prey_by_tile.entry((pos.x, pos.y)).or_default().push((key.0, entity));
+    }
+    for candidates in prey_by_tile.values_mut() {
+        candidates.sort();
+    }
+
+    // In StreamKey order, so who eats whom on a shared tile doesn't depend on the query
+    let mut hunters: Vec<(u64, Entity)> = q_hunters.iter().map(|(entity, .., key)| (key.0, entity)).collect();
+    hunters.sort();
+
+    let mut eaten: HashSet<Entity> = HashSet::new();
+    for (hunter_key, hunter) in hunters {
+        // Eaten earlier this tick by someone further up the food chain
+        if eaten.contains(&hunter) { continue; }
+        let Ok((_, hunter_pos, mut hunter_hunger, hunter_stats, hunter_age, digesting, satiated, desperate, _)) = q_hunters.get_mut(hunter) else { continue };
+        if !hunter_age.is_adult { continue; }
+        if !cfg.wants_prey(hunter_hunger.0, digesting || satiated) { continue; }
+
+        let pos = (hunter_pos.x, hunter_pos.y);
+        let Some(candidates) = prey_by_tile.get(&pos) else { continue };
+        let meal = candidates.iter().map(|&(_, prey)| prey).find(|&prey| {
+            prey != hunter
+                && !eaten.contains(&prey)
+                && q_prey
+                    .get(prey)
+                    .map(|(_, _, s, _, _, dna, age, ..)| {
+                        behaviors.hunts(hunter_stats.species_id, s.species_id) && (desperate || !cfg.is_risky_prey(age, dna))
+                    })
+                    .unwrap_or(false)
+        });
+        let Some(prey) = meal else { continue };
+        let Ok((_, _, prey_stats, prey_name, prey_gen, prey_dna, prey_age, ..)) = q_prey.get(prey) else { continue };
+
+        // Kills gorge: hunger can drop below 0, so the predator is "super full"
+        // and won't starve for a long time. Bigger prey is a bigger meal.
+        hunter_hunger.0 = (hunter_hunger.0 - cfg.prey_nutrition * prey_dna.size_multiplier).max(-cfg.max_gorge);
+
+        commands.entity(hunter).insert(Digesting);
+        commands.entity(prey).insert(Dead { cause: DeathCause::Predation });
+        eaten.insert(prey);
+
+        // Big adults fight back
+        if cfg.is_risky_prey(prey_age, prey_dna)
+            && sim_rng.stream_for("injury", hunter_key).random::<f32>() < cfg.risky_prey_injury_chance
+        {
+            commands.entity(hunter).insert(Injured(Timer::from_seconds(cfg.injury_seconds, TimerMode::Once)));
+        }
+
+        kills.write(KillEvent { predator: hunter, prey, pos });
+        deaths.write(DeathEvent {
+            entity: prey,
+            species_id: prey_stats.species_id,
+            name: prey_name.0.clone(),
+            generation: prey_gen.0,
+            dna: *prey_dna,
+            pos,
+            cause: DeathCause::Predation,
+        });
+    }
+}
+
+// Adult hunters turn Desperate at desperation_hunger and calm down once fed;
+// injuries heal on their timer
+pub fn desperation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    cfg: Res<SimulationConfig>,
+    behaviors: Res<SpeciesBehaviors>,
+    mut q_creatures: Query<(Entity, &Hunger, &CreatureStats, &Age, Has<Desperate>, Option<&mut Injured>), (With<Creature>, Without<Dead>)>,
+) {
+    for (entity, hunger, stats, age, desperate, injured) in q_creatures.iter_mut() {
+        let starving = age.is_adult && hunger.0 >= cfg.desperation_hunger && behaviors.is_hunter(stats.species_id);
+        if starving && !desperate {
+            commands.entity(entity).insert(Desperate);
+        } else if !starving && desperate {
+            commands.entity(entity).remove::<Desperate>();
+        }
+
+        if let Some(mut injured) = injured {
+            injured.0.tick(time.delta());
+            if injured.0.is_finished() {
+                commands.entity(entity).remove::<Injured>();
+            }
+        }
+    }
+}
+
+pub fn generate_name(rng: &mut impl Rng) -> String {
+    let vowels = ["a", "e", "i", "o", "u", "y", "aa", "ee"];
+    let consonants = ["b", "c", "d", "f", "g", "h", "j", "k", "l", "m", "n", "p", "r", "s", "t", "v", "z", "th", "qr"];
+
+    // We cast to usize for array indexing.
+    let len = 2 + (rng.random::<u32>() % 2);
+    let mut name = String::new();
+
+    for _ in 0..len {
+        let c_idx = (rng.random::<u32>() as usize) % consonants.len();
+        let v_idx = (rng.random::<u32>() as usize) % vowels.len();
+
+        name.push_str(consonants[c_idx]);
+        name.push_str(vowels[v_idx]);
+    }
+
+    // Capitalize first letter
+    let mut chars = name.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(f) => f.to_uppercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+// Once per sim-day (and right after a new game resets the clock)
+pub fn genetics_sampling_system(
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    mut history: ResMut<GeneticsHistory>,
+    q_creatures: Query<(&CreatureStats, &Dna), (With<Creature>, Without<Dead>)>,
+) {
+    let day = stats.days as u32;
+    if history.last_day == Some(day) { return; }
+    history.last_day = Some(day);
+
+    let mut by_species: HashMap<u32, Vec<Dna>> = HashMap::new();
+    for (creature_stats, dna) in q_creatures.iter() {
+        by_species.entry(creature_stats.species_id).or_default().push(*dna);
+    }
+    let mut species: Vec<u32> = by_species.keys().copied().collect();
+    species.sort_unstable();
+    for species_id in species {
+        history.samples.extend(GeneticsSample::measure(stats.days, species_id, &by_species[&species_id]));
+    }
+
+    let oldest = stats.days - cfg.genetics_history_days as f32;
+    history.samples.retain(|s| s.day >= oldest);
+}
+
+// Every speciation_check_days: group each clade by who could meet whom (links
+// within speciation_link_distance). The biggest group keeps the clade; any other
+// big enough group that has drifted past speciation_divergence from it splits off.
+pub fn speciation_system(
+    mut commands: Commands,
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    mut registry: ResMut<SpeciesRegistry>,
+    q_creatures: Query<(Entity, &GridPosition, &CreatureStats, &Dna, Option<&Clade>), (With<Creature>, Without<Dead>)>,
+    mut speciations: MessageWriter<SpeciationEvent>,
+) {
+    if cfg.speciation_check_days == 0 { return; }
+    let day = stats.days as u32;
+    if !day.is_multiple_of(cfg.speciation_check_days) || registry.last_check_day == Some(day) { return; }
+    registry.last_check_day = Some(day);
+
+    let mut clades: HashMap<u32, Vec<(Entity, (i32, i32), u32, Dna)>> = HashMap::new();
+    for (entity, pos, creature_stats, dna, clade) in q_creatures.iter() {
+        clades
+            .entry(clade_of(creature_stats, clade))
+            .or_default()
+            .push((entity, (pos.x, pos.y), creature_stats.species_id, *dna));
+    }
+    let mut clade_ids: Vec<u32> = clades.keys().copied().collect();
+    clade_ids.sort_unstable();
+
+    fn find(root: &mut [usize], mut i: usize) -> usize {
+        while root[i] != i {
+            root[i] = root[root[i]];
+            i = root[i];
+        }
+        i
+    }
+
+    let min_group = cfg.speciation_min_group.max(1) as usize;
+    for clade_id in clade_ids {
+        let mut members = clades.remove(&clade_id).unwrap_or_default();
+        if members.len() < min_group * 2 { continue; }
+        members.sort_by_key(|(entity, ..)| entity.to_bits());
+
+        // Union-find over everyone within link distance of each other
+        let mut root: Vec<usize> = (0..members.len()).collect();
+        for i in 0..members.len() {
+            for j in (i + 1)..members.len() {
+                let (a, b) = (members[i].1, members[j].1);
+                if (a.0 - b.0).abs() + (a.1 - b.1).abs() <= cfg.speciation_link_distance {
+                    let (ri, rj) = (find(&mut root, i), find(&mut root, j));
+                    if ri != rj { root[rj.max(ri)] = rj.min(ri); }
+                }
+            }
+        }
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for i in 0..members.len() {
+            let r = find(&mut root, i);
+            groups.entry(r).or_default().push(i);
+        }
+        // Biggest first, ties to the oldest root: deterministic
+        let mut groups: Vec<(usize, Vec<usize>)> = groups.into_iter().collect();
+        groups.sort_by(|(ra, a), (rb, b)| b.len().cmp(&a.len()).then(ra.cmp(rb)));
+        if groups.len() < 2 { continue; }
+
+        let dna_of = |group: &[usize]| group.iter().map(|&i| members[i].3).collect::<Vec<Dna>>();
+        let main = dna_of(&groups[0].1);
+        for (_, group) in groups.iter().skip(1) {
+            if group.len() < min_group { continue; }
+            if trait_divergence(&main, &dna_of(group)) < cfg.speciation_divergence { continue; }
+
+            let species_id = members[group[0]].2;
+            let info = registry.found(&cfg, species_id, clade_id, stats.days);
+            for &i in group {
+                commands.entity(members[i].0).insert(Clade(info.id));
+            }
+            speciations.write(SpeciationEvent {
+                clade: info.id,
+                parent: clade_id,
+                species_id,
+                name: info.name.clone(),
+                members: group.len() as u32,
+                day: stats.days,
+            });
+        }
+    }
+}
+
+pub fn record_speciations(
+    mut events: MessageReader<SpeciationEvent>,
+    cfg: Res<SimulationConfig>,
+    registry: Res<SpeciesRegistry>,
+    mut logger: ResMut<SimulationLogger>,
+    mut notifications: ResMut<Notifications>,
+) {
+    for event in events.read() {
+        let parent = registry.name(&cfg, event.parent);
+        logger.log_species("Speciation", event.day, &event.name);
+        notifications.push(format!("New species: {} split off from {}", event.name, parent), &cfg);
+    }
+}
+
+// Experiment mode: keep each creature's score up to date. Creatures already
+// alive when the mode was entered are picked up with their current age.
+pub fn fitness_tracking_system(
+    stats: Res<GameStats>,
+    mut ledger: ResMut<FitnessLedger>,
+    mut births: MessageReader<BirthEvent>,
+    mut deaths: MessageReader<DeathEvent>,
+    mut kills: MessageReader<KillEvent>,
+    q_creatures: Query<(Entity, &CreatureStats, &CreatureName, &Generation, &Dna, &Age), (With<Creature>, Without<Dead>)>,
+) {
+    let today = stats.days;
+    for (entity, creature_stats, name, generation, dna, age) in q_creatures.iter() {
+        ledger.records.entry(entity).or_insert_with(|| FitnessRecord {
+            name: name.0.clone(),
+            species_id: creature_stats.species_id,
+            generation: generation.0,
+            dna: *dna,
+            born_day: today - age.seconds_alive / SECONDS_PER_DAY,
+            died_day: None,
+            offspring: 0,
+            kills: 0,
+        });
+    }
+
+    for e in births.read() {
+        ledger.records.entry(e.entity).or_insert_with(|| FitnessRecord {
+            name: e.name.clone(),
+            species_id: e.species_id,
+            generation: e.generation,
+            dna: e.dna,
+            born_day: today,
+            died_day: None,
+            offspring: 0,
+            kills: 0,
+        });
+        for parent in [e.parents.0, e.parents.1] {
+            if let Some(record) = ledger.records.get_mut(&parent) {
+                record.offspring += 1;
+            }
+        }
+    }
+    for e in kills.read() {
+        if let Some(record) = ledger.records.get_mut(&e.predator) {
+            record.kills += 1;
+        }
+    }
+    for e in deaths.read() {
+        if let Some(record) = ledger.records.get_mut(&e.entity) {
+            record.died_day.get_or_insert(today);
+        }
+    }
+}
+
+// Every fitness_report_days: log and notify the fittest of each species,
+// export their genomes to TOP_GENOMES_FILE, and forget the dead that didn't make it
+pub fn fitness_report_system(
+    cfg: Res<SimulationConfig>,
+    stats: Res<GameStats>,
+    mut ledger: ResMut<FitnessLedger>,
+    mut logger: ResMut<SimulationLogger>,
+    mut notifications: ResMut<Notifications>,
+    headless: Option<Res<Headless>>,
+) {
+    if cfg.fitness_report_days == 0 { return; }
+    let day = stats.days as u32;
+    if day == 0 || !day.is_multiple_of(cfg.fitness_report_days) || ledger.last_report_day == Some(day) { return; }
+    ledger.last_report_day = Some(day);
+
+    let mut species: Vec<u32> = cfg.species.keys().copied().collect();
+    species.sort_unstable();
+
+    let mut keep: HashSet<Entity> = HashSet::new();
+    let mut seed_genomes = Vec::new();
+    for species_id in species {
+        let name = cfg.s(species_id).name.clone();
+        let ranking = ledger.ranking(&cfg, species_id, stats.days);
+        for (entity, record) in ranking.iter().take(cfg.fitness_top_count) {
+            logger.log("TopGenome", stats.days, &name, &record.name, record.generation, &record.dna);
+            keep.insert(*entity);
+            seed_genomes.push(SeedGenome { species_id, dna: record.dna });
+        }
+        if let Some((_, best)) = ranking.first() {
+            notifications.push(
+                format!("Fittest {}: {} (Gen {}, score {:.1})", name, best.name, best.generation, best.score(&cfg, stats.days)),
+                &cfg,
+            );
+        }
+    }
+
+    ledger.records.retain(|entity, record| record.died_day.is_none() || keep.contains(entity));
+
+    if headless.is_some() { return; }
+    #[derive(Serialize)]
+    struct TopGenomes {
+        seed_genomes: Vec<SeedGenome>,
+    }
+    let written = ron::ser::to_string_pretty(&TopGenomes { seed_genomes }, ron::ser::PrettyConfig::default())
+        .map_err(|e| e.to_string())
+        .and_then(|text| write_data_file(TOP_GENOMES_FILE, text).map_err(|e| e.to_string()));
+    if let Err(err) = written {
+        println!("Could not write {}: {}", TOP_GENOMES_FILE, err);
+    }
+}
+
+pub fn mutate_dna(rng: &mut impl Rng, dna1: &Dna, dna2: &Dna) -> Dna {
+    // 1. Average
+    let avg_speed = (dna1.move_speed_seconds + dna2.move_speed_seconds) / 2.0;
+    let avg_meta = (dna1.metabolism_rate + dna2.metabolism_rate) / 2.0;
+    let avg_sight = (dna1.sight_range + dna2.sight_range) as f32 / 2.0;
+    let avg_size = (dna1.size_multiplier + dna2.size_multiplier) / 2.0;
+
+    // 2. Mutate (variance)
+    // +/- 10% mutation rate usually works well
+    let mutation_strength = 0.10;
+
+    let mut rand_factor = |val: f32| -> f32 {
+        let variance = val * mutation_strength;
+        val + (rng.random::<f32>() * variance * 2.0) - variance
+    };
+
+    Dna {
+        move_speed_seconds: rand_factor(avg_speed).clamp(0.05, 5.0),
+        metabolism_rate: rand_factor(avg_meta).clamp(0.5, 20.0),
+        sight_range: rand_factor(avg_sight).round() as i32,
+        size_multiplier: rand_factor(avg_size).clamp(0.5, 2.0),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use living_world_core::{Creature, Dead, Digesting, GridPosition, History, Hunger, Simulation, SimulationConfig};
+use proptest::prelude::*;
+
+// Whole worlds per case: keep the count low and the maps small
+fn small_world(map_size: i32, seed: u64) -> Simulation {
+    let config = SimulationConfig::from_overrides(&format!("(map_size: {}, tree_count: 4, lake_count: 1)", map_size)).unwrap();
+    Simulation::new(config, seed)
+}
+
+fn check_creatures(sim: &mut Simulation) -> Result<(), TestCaseError> {
+    let map_size = sim.config().map_size;
+    let world = sim.world_mut();
+    let mut q = world.query_filtered::<(&GridPosition, &History, &Hunger, Has<Digesting>), (With<Creature>, Without<Dead>)>();
+    for (pos, history, hunger, digesting) in q.iter(world) {
+        prop_assert!(
+            (-map_size..map_size).contains(&pos.x) && (-map_size..map_size).contains(&pos.y),
+            "creature at ({}, {}) outside a map of size {}", pos.x, pos.y, map_size
+        );
+        // One step at a time: the last tile is this one or a neighbour
+        let step = (pos.x - history.last_x).abs() + (pos.y - history.last_y).abs();
+        prop_assert!(step <= 1, "moved {} tiles in one step to ({}, {})", step, pos.x, pos.y);
+        prop_assert!(hunger.0.is_finite(), "hunger is {}", hunger.0);
+        // Only a fresh kill takes hunger below zero
+        prop_assert!(hunger.0 >= 0.0 || digesting, "hunger {} without digesting", hunger.0);
+    }
+    Ok(())
+}
+
+// Anything marked dead at the end of a frame is gone by the end of the next
+fn check_reaped(sim: &mut Simulation) -> Result<(), TestCaseError> {
+    let world = sim.world_mut();
+    let dead: Vec<Entity> = world.query_filtered::<Entity, With<Dead>>().iter(world).collect();
+    sim.step();
+    for entity in dead {
+        prop_assert!(sim.world().get_entity(entity).is_err(), "{:?} still around a frame after dying", entity);
+    }
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(12))]
+
+    #[test]
+    fn invariants_hold_between_runs(
+        seed in any::<u64>(),
+        map_size in 6i32..14,
+        runs in prop::collection::vec(1u64..40, 1..8),
+    ) {
+        let mut sim = small_world(map_size, seed);
+        check_creatures(&mut sim)?;
+        for ticks in runs {
+            sim.run(ticks);
+            check_creatures(&mut sim)?;
+            check_reaped(&mut sim)?;
+            check_creatures(&mut sim)?;
+        }
+    }
+}
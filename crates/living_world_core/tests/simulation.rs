@@ -0,0 +1,550 @@
+use std::collections::BTreeSet;
+
+use bevy::prelude::Entity;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use living_world_core::{Age, Corpse, CreatureBehavior, CreatureRecord, DeathCause, DecisionTrace, Desperate, GridPosition, History, Hunger, Plant, Pollution, DNA_RANGES, Dna, Fostered, GameMode, GeneticsSample, GhostRun, Goal, Infected, LifeStage, MapBlueprint, MapImage, Mother, SpeciesBehaviors, trait_divergence, PlayerResources, ResourceKind, SaveFormat, scatter_spots, Simulation, SimulationConfig, SystemTimings, TimelineKind, Tile, Water, Weather, WeatherKind, WorldCode, WorldSnapshot, WorldTimeline};
+
+fn small_world() -> Simulation {
+    let config = SimulationConfig::from_overrides("(map_size: 10, tree_count: 4, lake_count: 1)").unwrap();
+    Simulation::new(config, 3)
+}
+
+#[test]
+fn new_world_has_founders() {
+    let mut sim = small_world();
+    let population = sim.population();
+    assert!(population.get(&0).copied().unwrap_or(0) > 0);
+    assert!(population.get(&1).copied().unwrap_or(0) > 0);
+    assert_eq!(sim.creatures().len() as u32, population.values().sum::<u32>());
+}
+
+#[test]
+fn stepping_advances_the_clock() {
+    let mut sim = small_world();
+    let (tick, day) = (sim.tick(), sim.day());
+    sim.run(120);
+    assert_eq!(sim.tick(), tick + 120);
+    assert!(sim.day() > day);
+}
+
+#[test]
+fn forks_stay_in_lockstep_with_their_source() {
+    let mut sim = small_world();
+    sim.run(600);
+    let mut fork = sim.fork();
+    for _ in 0..3000 {
+        sim.step();
+        fork.step();
+    }
+    assert_eq!(fork.state_hash(), sim.state_hash());
+}
+
+#[test]
+fn every_step_times_each_system_by_name() {
+    let mut sim = small_world();
+    sim.step();
+    let timings = sim.world().resource::<SystemTimings>().take();
+    assert!(timings.contains_key("creature_eating"));
+    assert!(timings.contains_key("plan_creature_moves"));
+    assert!(timings.values().all(|ms| *ms >= 0.0));
+}
+
+#[test]
+fn lightning_strikes_creatures_without_shelter() {
+    let config = SimulationConfig::from_overrides("(map_size: 10, tree_count: 0, lake_count: 0, lightning_kill_chance: 1000.0)").unwrap();
+    let mut sim = Simulation::new(config, 3);
+    assert!(!sim.creatures().is_empty());
+
+    sim.world_mut().resource_mut::<Weather>().kind = WeatherKind::Storm;
+    sim.step();
+    assert!(sim.creatures().is_empty());
+}
+
+#[test]
+fn elders_die_of_old_age_and_leave_a_corpse() {
+    let overrides = "(map_size: 10, lake_count: 0, species: { 0: (adult_seconds: 0.5, elder_seconds: 1.0, lifespan_seconds: 2.0, reproduction_chance: 0.0), 1: (starting_count: 0) })";
+    let mut sim = Simulation::new(SimulationConfig::from_overrides(overrides).unwrap(), 3);
+    sim.run(180);
+    assert!(sim.creatures().is_empty());
+    let world = sim.world_mut();
+    let corpses: Vec<DeathCause> = world.query::<&Corpse>().iter(world).map(|c| c.cause).collect();
+    assert!(!corpses.is_empty());
+    assert!(corpses.iter().all(|&cause| cause == DeathCause::OldAge));
+}
+
+#[test]
+fn new_games_start_with_seeds_to_sow() {
+    let sim = small_world();
+    let seeds = sim.world().resource::<PlayerResources>().get(ResourceKind::Seeds);
+    assert_eq!(seeds, sim.world().resource::<SimulationConfig>().starting_seeds);
+    assert_eq!(sim.world().resource::<PlayerResources>().get(ResourceKind::Wool), 0);
+}
+
+#[test]
+fn spending_needs_enough_in_the_pool() {
+    let mut resources = PlayerResources::default();
+    resources.add(ResourceKind::Wool, 3);
+    assert!(!resources.spend(ResourceKind::Wool, 4));
+    assert!(resources.spend(ResourceKind::Wool, 3));
+    assert_eq!(resources.get(ResourceKind::Wool), 0);
+}
+
+#[test]
+fn challenges_charge_like_management() {
+    let charging: Vec<GameMode> = [GameMode::Sandbox, GameMode::Challenge, GameMode::Observer, GameMode::Management, GameMode::Experiment]
+        .into_iter()
+        .filter(GameMode::charges)
+        .collect();
+    assert_eq!(charging, [GameMode::Challenge, GameMode::Management]);
+}
+
+#[test]
+fn elders_are_a_separate_life_stage() {
+    let overrides = "(map_size: 10, lake_count: 0, species: { 0: (starting_count: 1, adult_seconds: 0.5, elder_seconds: 1.0, reproduction_chance: 0.0), 1: (starting_count: 0) })";
+    let mut sim = Simulation::new(SimulationConfig::from_overrides(overrides).unwrap(), 3);
+    let sheep = sim.creatures()[0].entity;
+    let mut stages = Vec::new();
+    for _ in 0..120 {
+        let stage = sim.world().get::<Age>(sheep).unwrap().stage();
+        if stages.last() != Some(&stage) {
+            stages.push(stage);
+        }
+        sim.step();
+    }
+    assert_eq!(stages, [LifeStage::Juvenile, LifeStage::Adult, LifeStage::Elder]);
+}
+
+#[test]
+fn clones_have_no_genetic_variation() {
+    let dna = Dna { move_speed_seconds: 0.5, metabolism_rate: 2.0, sight_range: 8, size_multiplier: 1.0 };
+    let sample = GeneticsSample::measure(0.0, 0, &[dna; 4]).unwrap();
+    assert!(sample.traits.iter().all(|t| t.variance == 0.0));
+    assert_eq!(sample.heterozygosity, 0.0);
+
+    let mixed = [dna, Dna { sight_range: 12, size_multiplier: 1.5, ..dna }];
+    assert!(GeneticsSample::measure(0.0, 0, &mixed).unwrap().heterozygosity > 0.0);
+    assert!(GeneticsSample::measure(0.0, 0, &[]).is_none());
+}
+
+#[test]
+fn divergence_grows_with_trait_drift() {
+    let dna = Dna { move_speed_seconds: 0.5, metabolism_rate: 2.0, sight_range: 8, size_multiplier: 1.0 };
+    let drifted = Dna { move_speed_seconds: 0.7, metabolism_rate: 2.6, ..dna };
+    assert_eq!(trait_divergence(&[dna; 3], &[dna; 2]), 0.0);
+    assert!(trait_divergence(&[dna; 3], &[drifted; 2]) > 0.1);
+}
+
+#[test]
+fn the_food_web_follows_prey_rules() {
+    let mut behaviors = SpeciesBehaviors::load(&SimulationConfig::default());
+    assert!(behaviors.hunts(1, 0));
+    assert!(!behaviors.hunts(0, 1));
+    assert!(!behaviors.hunts(1, 1));
+
+    let wolves = behaviors.0.get_mut(&1).unwrap();
+    let mut cannibal = wolves.rules.iter().find(|r| r.goal == Goal::Prey).unwrap().clone();
+    cannibal.species = vec![1];
+    wolves.rules.push(cannibal);
+    assert!(behaviors.hunts(1, 1));
+}
+
+#[test]
+fn the_timeline_marks_turns_in_numbers() {
+    let cfg = SimulationConfig::from_overrides("(timeline_swing: 0.3, timeline_min_swing: 5)").unwrap();
+    let mut timeline = WorldTimeline::default();
+    for (day, count) in [10, 20, 40, 38, 25, 12, 10, 16, 30].into_iter().enumerate() {
+        timeline.observe(&cfg, day as f32, 0, count);
+    }
+    let marks: Vec<(f32, TimelineKind)> = timeline.entries.iter().map(|e| (e.day, e.kind)).collect();
+    // The small dip from 40 to 38 isn't a turn; both marks are dated to the extreme
+    assert_eq!(marks, vec![(2.0, TimelineKind::Peak(40)), (6.0, TimelineKind::Trough(10))]);
+}
+
+#[test]
+fn map_images_cover_every_tile() {
+    let mut sim = small_world();
+    let image = MapImage::render(&sim.snapshot(), 3);
+    assert_eq!((image.width, image.height), (60, 60));
+    assert_eq!(image.rgb.len(), 60 * 60 * 3);
+    // Every tile is painted: nothing is left black
+    assert!(image.rgb.chunks(3).all(|px| px != [0, 0, 0]));
+    assert!(image.to_png().unwrap().starts_with(b"\x89PNG"));
+}
+
+#[test]
+fn exported_maps_import_as_themselves() {
+    let mut sim = small_world();
+    let snapshot = sim.snapshot();
+    let png = MapImage::render(&snapshot, 4).to_png().unwrap();
+    let blueprint = MapBlueprint::from_image(&MapImage::decode(&png).unwrap(), &snapshot.config);
+
+    let trees: BTreeSet<(i32, i32)> = snapshot.trees.iter().copied().collect();
+    assert_eq!(blueprint.trees.iter().copied().collect::<BTreeSet<_>>(), trees);
+    // A tree is drawn over whatever ground it stands on
+    let water: BTreeSet<(i32, i32)> =
+        snapshot.tiles.iter().filter(|t| t.water.is_some() && !trees.contains(&t.pos)).map(|t| t.pos).collect();
+    assert_eq!(blueprint.water.iter().map(|&(pos, _)| pos).collect::<BTreeSet<_>>(), water);
+}
+
+#[test]
+fn painted_maps_replace_the_generated_terrain() {
+    // West half blue, east half grass, one pixel per tile
+    let mut image = MapImage { width: 20, height: 20, rgb: Vec::new() };
+    for _row in 0..20 {
+        for col in 0..20 {
+            image.rgb.extend_from_slice(if col < 10 { &[40, 60, 200] } else { &[90, 120, 70] });
+        }
+    }
+    let path = std::env::temp_dir().join(format!("living_world_painted_{}.png", std::process::id()));
+    std::fs::write(&path, image.to_png().unwrap()).unwrap();
+
+    let overrides = format!("(map_size: 10, tree_count: 4, lake_count: 1, map_image: Some({:?}))", path.display().to_string());
+    let mut sim = Simulation::new(SimulationConfig::from_overrides(&overrides).unwrap(), 3);
+    let snapshot = sim.snapshot();
+    std::fs::remove_file(&path).ok();
+
+    assert!(snapshot.trees.is_empty());
+    assert!(snapshot.tiles.iter().all(|t| t.water.is_some() == (t.pos.0 < 0)));
+    // Founders whose spots were painted over move ashore
+    assert!(sim.creatures().iter().all(|c| c.pos.0 >= 0));
+}
+
+#[test]
+fn map_files_keep_the_ground_and_where_each_species_lives() {
+    let mut sim = small_world();
+    let snapshot = sim.snapshot();
+    let blueprint = MapBlueprint::from_snapshot(&snapshot);
+    let text = ron::to_string(&blueprint).unwrap();
+    let loaded: MapBlueprint = ron::from_str(&text).unwrap();
+
+    assert_eq!(loaded.map_size, 10);
+    assert_eq!(loaded.water.len(), snapshot.tiles.iter().filter(|t| t.water.is_some()).count());
+    assert_eq!(loaded.spawns.iter().map(|r| r.species_id).collect::<Vec<_>>(), vec![0, 1]);
+    for creature in snapshot.creature_records.iter().map(|r| &r.creature) {
+        let region = loaded.spawns.iter().find(|r| r.species_id == creature.species_id).unwrap();
+        let (x, y) = creature.pos;
+        assert!(region.min.0 <= x && x <= region.max.0 && region.min.1 <= y && y <= region.max.1);
+    }
+}
+
+#[test]
+fn world_codes_survive_retyping_and_catch_typos() {
+    let config = SimulationConfig::from_overrides("(map_size: 14, tree_count: 9, lake_count: 2)").unwrap();
+    let code = WorldCode::of(&config, 0x1234_5678_9abc_def0, Some("lush-valley"));
+    let text = code.encode();
+    assert_eq!(WorldCode::decode(&text.to_lowercase().replace('-', " ")), Ok(code.clone()));
+
+    let mut applied = SimulationConfig::default();
+    code.apply(&mut applied);
+    assert_eq!((applied.map_size, applied.tree_count, applied.lake_count), (14, 9, 2));
+
+    // Changing any one character breaks the checksum
+    let chars: Vec<char> = text.chars().collect();
+    for (i, &c) in chars.iter().enumerate().filter(|&(_, &c)| c != '-') {
+        for typo in "0123456789ABCDEFGHJKMNPQRSTVWXYZ".chars().filter(|&t| t != c) {
+            let mut mistyped = chars.clone();
+            mistyped[i] = typo;
+            let mistyped: String = mistyped.into_iter().collect();
+            assert!(WorldCode::decode(&mistyped).is_err(), "{} decoded", mistyped);
+        }
+    }
+}
+
+#[test]
+fn gene_edits_stay_inside_each_traits_range() {
+    let dna = Dna { move_speed_seconds: 1.0, metabolism_rate: 2.0, sight_range: 8, size_multiplier: 1.0 };
+    let edited = dna.with_trait(0, 100.0).with_trait(1, -3.0).with_trait(2, 7.6).with_trait(3, 1.5);
+    assert_eq!(edited.traits(), [DNA_RANGES[0].1, DNA_RANGES[1].0, 8.0, 1.5]);
+    // Out-of-range indices leave the genome alone
+    assert_eq!(dna.with_trait(9, 0.0).traits(), dna.traits());
+}
+
+#[test]
+fn creature_files_carry_an_individual_to_another_world() {
+    let mut home = small_world();
+    let record = home.snapshot().creature_records[0].clone();
+    let carried: CreatureRecord = ron::from_str(&ron::to_string(&record).unwrap()).unwrap();
+
+    let mut away = Simulation::new(SimulationConfig::from_overrides("(map_size: 12)").unwrap(), 9);
+    let before = away.creatures().len();
+    let cfg = away.config().clone();
+    let world = away.world_mut();
+    carried.at((3, -2)).spawn(&mut world.commands(), &cfg);
+    world.flush();
+
+    let creatures = away.creatures();
+    assert_eq!(creatures.len(), before + 1);
+    let arrived = creatures.iter().find(|c| c.pos == (3, -2) && c.name == record.creature.name).unwrap();
+    assert_eq!(arrived.dna.traits(), record.creature.dna.traits());
+    assert_eq!((arrived.species_id, arrived.generation), (record.creature.species_id, record.creature.generation));
+}
+
+#[test]
+fn scattering_spreads_creatures_over_the_land_before_doubling_up() {
+    let land: Vec<(i32, i32)> = (0..5).map(|x| (x, 2)).collect();
+    let mut rng = SmallRng::seed_from_u64(4);
+    let few = scatter_spots(&mut rng, &land, 5);
+    assert_eq!(few.iter().collect::<BTreeSet<_>>().len(), 5);
+
+    let many = scatter_spots(&mut rng, &land, 12);
+    assert_eq!(many.len(), 12);
+    assert!(many.iter().all(|spot| land.contains(spot)));
+    assert!(scatter_spots(&mut rng, &[], 3).is_empty());
+}
+
+#[test]
+fn a_species_time_scale_speeds_up_its_hunger() {
+    let wolf_hunger = |scale: f32| {
+        let overrides = format!("(map_size: 10, lake_count: 0, species: {{ 0: (starting_count: 0), 1: (time_scale: {:?}) }})", scale);
+        let mut sim = Simulation::new(SimulationConfig::from_overrides(&overrides).unwrap(), 5);
+        sim.run(120);
+        let wolves: Vec<f32> = sim.creatures().iter().filter(|c| c.species_id == 1).map(|c| c.hunger).collect();
+        wolves.iter().sum::<f32>() / wolves.len() as f32
+    };
+    let (normal, double) = (wolf_hunger(1.0), wolf_hunger(2.0));
+    assert!(normal > 0.0);
+    // About twice: ticks picked up on the way can add a little either way
+    assert!((double / normal - 2.0).abs() < 0.5, "{} vs {}", double, normal);
+}
+
+#[test]
+fn a_ghost_run_keeps_one_frame_per_step_and_forgets_a_rewound_future() {
+    let mut run = GhostRun::new(7, 0.5);
+    for tick in 0..8 {
+        run.record(tick as f32 * 0.25, || vec![(0, (tick, 0))]);
+    }
+    assert_eq!(run.frames.len(), 4);
+    assert_eq!(run.at(1.2), Some(&[(0, (4, 0))][..]));
+    assert!(run.at(2.0).is_none());
+
+    // A jump ahead fills the skipped frames; going back drops what came after
+    run.record(3.0, || vec![(1, (9, 9))]);
+    assert_eq!(run.frames.len(), 7);
+    assert_eq!(run.at(2.0), Some(&[(1, (9, 9))][..]));
+    run.record(0.6, || vec![(0, (5, 5))]);
+    assert_eq!(run.frames.len(), 2);
+    assert_eq!(run.at(0.5), Some(&[(0, (5, 5))][..]));
+}
+
+#[test]
+fn a_simulation_picks_up_a_snapshot_from_another() {
+    let mut sim = small_world();
+    sim.run(300);
+    let snapshot = sim.snapshot();
+    let mut copy = Simulation::from_snapshot(snapshot.clone(), 11);
+    assert!((copy.day() - snapshot.days).abs() < 0.01);
+    assert_eq!(copy.creatures().len(), snapshot.creature_records.len());
+    assert_eq!(copy.config().map_size, 10);
+
+    // And keeps going on its own
+    copy.run(60);
+    assert!(copy.day() > snapshot.days);
+}
+
+#[test]
+fn saves_load_back_as_the_same_world_in_either_format() {
+    let mut sim = small_world();
+    sim.run(600);
+    let snapshot = sim.snapshot();
+    let hash = |snapshot: WorldSnapshot| Simulation::from_snapshot(snapshot, 5).state_hash();
+    let expected = hash(snapshot.clone());
+
+    for format in SaveFormat::ALL {
+        let bytes = snapshot.clone().encode(format, 3).unwrap();
+        let loaded = WorldSnapshot::decode(&bytes, format).unwrap();
+        assert_eq!(loaded.creature_records.len(), snapshot.creature_records.len());
+        assert_eq!(loaded.tiles.len(), snapshot.tiles.len());
+        assert_eq!(loaded.birds.len(), snapshot.birds.len());
+        assert_eq!(loaded.corpses.len(), snapshot.corpses.len());
+        assert_eq!(hash(loaded), expected);
+    }
+}
+
+// Written by a version 2 build: every list added since has to come back empty
+#[test]
+fn version_2_compressed_saves_still_decode() {
+    let bytes = include_bytes!("fixtures/v2_world.lws");
+    let snapshot = WorldSnapshot::decode(bytes, SaveFormat::Compressed).unwrap();
+
+    assert_eq!(snapshot.config.map_size, 2);
+    assert!((snapshot.days - 2.5).abs() < 1e-6);
+    assert_eq!(snapshot.weather, WeatherKind::Rain);
+    assert_eq!(snapshot.tiles.len(), 16);
+    assert_eq!(snapshot.shepherd, Some((-1, 0)));
+    let names: Vec<&str> = snapshot.creature_records.iter().map(|r| r.creature.name.as_str()).collect();
+    assert_eq!(names, ["Dolly", "Fang"]);
+    assert!(snapshot.creature_records.iter().all(|r| r.parasites == 0 && r.founder == r.creature.name && !r.edited));
+    assert!(snapshot.shelters.is_empty() && snapshot.tile_grass.is_empty() && snapshot.resources.is_empty());
+    assert!(snapshot.records.is_empty() && snapshot.timeline.is_empty() && snapshot.birds.is_empty());
+    assert!(snapshot.corpses.is_empty());
+}
+
+#[test]
+fn loading_a_world_keeps_babies_with_their_mothers() {
+    let mut sim = small_world();
+    sim.run(1200);
+    let mothers = |sim: &mut Simulation| {
+        let world = sim.world_mut();
+        let mut q = world.query::<(&Age, &Mother)>();
+        q.iter(world).filter(|(age, _)| !age.is_adult).count()
+    };
+    let before = mothers(&mut sim);
+    assert!(before > 0, "no babies to check");
+
+    let mut copy = Simulation::from_snapshot(sim.snapshot(), 3);
+    assert_eq!(mothers(&mut copy), before);
+}
+
+#[test]
+fn only_babies_whose_mother_died_get_adopted() {
+    let overrides = "(map_size: 10, lake_count: 0, tree_count: 0, species: { 0: (starting_count: 3, reproduction_chance: 0.0), 1: (starting_count: 0) })";
+    let mut sim = Simulation::new(SimulationConfig::from_overrides(overrides).unwrap(), 3);
+    let sheep: Vec<Entity> = sim.creatures().iter().map(|c| c.entity).collect();
+    let (foster, founder, orphan) = (sheep[0], sheep[1], sheep[2]);
+    let world = sim.world_mut();
+    world.get_mut::<Age>(foster).unwrap().is_adult = true;
+    world.get_mut::<CreatureBehavior>(foster).unwrap().altruistic = true;
+    for (entity, x) in [(foster, 0), (founder, 1), (orphan, -1)] {
+        *world.get_mut::<GridPosition>(entity).unwrap() = GridPosition { x, y: 0 };
+    }
+    // A mother that's already gone
+    let mother = world.spawn_empty().id();
+    world.entity_mut(orphan).insert(Mother(mother));
+    world.despawn(mother);
+
+    sim.step();
+    let world = sim.world();
+    assert!(world.get::<Fostered>(founder).is_none());
+    assert!(world.get::<Fostered>(orphan).is_some());
+    assert_eq!(world.get::<Mother>(orphan).unwrap().0, foster);
+}
+
+#[test]
+fn births_never_crowd_a_tile_past_the_cap() {
+    let overrides = "(map_size: 3, lake_count: 0, tree_count: 0, max_creatures_per_tile: Some(2), \
+        species: { 0: (starting_count: 10, adult_seconds: 0.2, reproduction_chance: 1.0, reproduction_cooldown_seconds: 0.5), 1: (starting_count: 0) })";
+    let mut sim = Simulation::new(SimulationConfig::from_overrides(overrides).unwrap(), 3);
+    let mut births = 0;
+    for _ in 0..600 {
+        let before = sim.creatures();
+        sim.step();
+        let after = sim.creatures();
+        for baby in after.iter().filter(|c| before.iter().all(|b| b.entity != c.entity)) {
+            births += 1;
+            // Whoever walked in this step planned without seeing the baby; count the rest
+            let stayed = before.iter().filter(|b| b.pos == baby.pos && after.iter().any(|a| a.entity == b.entity && a.pos == b.pos));
+            let born = after.iter().filter(|c| c.pos == baby.pos && before.iter().all(|b| b.entity != c.entity));
+            let crowd = stayed.count() + born.count();
+            assert!(crowd <= 2, "{} born onto a tile with {} creatures", baby.name, crowd);
+        }
+    }
+    assert!(births > 0, "nobody was born");
+}
+
+#[test]
+fn sheep_between_two_wolves_flee_sideways() {
+    let overrides = "(map_size: 10, lake_count: 0, tree_count: 0, stressed_move_noise: 1.0, species: { 0: (starting_count: 1), 1: (starting_count: 2) })";
+    let mut sim = Simulation::new(SimulationConfig::from_overrides(overrides).unwrap(), 3);
+    let creatures = sim.creatures();
+    let sheep = creatures.iter().find(|c| c.species_id == 0).unwrap().entity;
+    let wolves: Vec<Entity> = creatures.iter().filter(|c| c.species_id == 1).map(|c| c.entity).collect();
+    sim.world_mut().entity_mut(sheep).insert(DecisionTrace::default());
+
+    // Fleeing the nearer wolf alone runs straight at the other one
+    let spots = [(sheep, (0, 0)), (wolves[0], (2, 0)), (wolves[1], (-2, 0))];
+    for _ in 0..600 {
+        for &(entity, (x, y)) in &spots {
+            let mut creature = sim.world_mut().entity_mut(entity);
+            *creature.get_mut::<GridPosition>().unwrap() = GridPosition { x, y };
+            creature.get_mut::<History>().unwrap().last_x = x;
+            creature.get_mut::<History>().unwrap().last_y = y;
+            creature.get_mut::<Age>().unwrap().is_adult = true;
+        }
+        sim.step();
+        let trace = sim.world().get::<DecisionTrace>(sheep).unwrap();
+        if let Some((dx, dy)) = trace.chosen {
+            assert_eq!(trace.goal, Some(Goal::Flee));
+            assert_eq!((dx, dy.abs()), (0, 1));
+            return;
+        }
+    }
+    panic!("the sheep never moved");
+}
+
+#[test]
+fn a_meal_takes_plant_nutrition_off_hunger_but_not_below_zero() {
+    for (hunger, left) in [(60.0, 35.0), (10.0, 0.0)] {
+        let overrides = "(map_size: 10, lake_count: 0, tree_count: 0, plant_nutrition: 25.0, species: { 0: (starting_count: 1, grazer: false), 1: (starting_count: 0) })";
+        let mut sim = Simulation::new(SimulationConfig::from_overrides(overrides).unwrap(), 3);
+        let sheep = sim.creatures()[0].clone();
+        let world = sim.world_mut();
+        // A plant wherever it ends up this step
+        for (dx, dy) in [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)] {
+            world.spawn((Plant, GridPosition { x: sheep.pos.0 + dx, y: sheep.pos.1 + dy }));
+        }
+        world.get_mut::<Hunger>(sheep.entity).unwrap().0 = hunger;
+
+        sim.step();
+        let now = sim.world().get::<Hunger>(sheep.entity).unwrap().0;
+        assert!((now - left).abs() < 0.5, "hunger {} after eating at {}", now, hunger);
+    }
+}
+
+#[test]
+fn starving_hunters_turn_desperate_until_fed() {
+    let overrides = "(map_size: 10, lake_count: 0, tree_count: 0, species: { 0: (starting_count: 0), 1: (starting_count: 1) })";
+    let mut sim = Simulation::new(SimulationConfig::from_overrides(overrides).unwrap(), 3);
+    let wolf = sim.creatures()[0].entity;
+    let threshold = sim.config().desperation_hunger;
+    let feed = |sim: &mut Simulation, hunger: f32| {
+        let mut creature = sim.world_mut().entity_mut(wolf);
+        creature.get_mut::<Age>().unwrap().is_adult = true;
+        creature.get_mut::<Hunger>().unwrap().0 = hunger;
+        sim.step();
+        sim.world().get::<Desperate>(wolf).is_some()
+    };
+
+    assert!(!feed(&mut sim, threshold - 5.0));
+    assert!(feed(&mut sim, threshold));
+    assert!(!feed(&mut sim, 10.0));
+}
+
+#[test]
+fn drinking_foul_water_infects_and_infections_burn_hunger() {
+    let overrides = "(map_size: 10, lake_count: 1, tree_count: 0, drink_interval_seconds: 0.0, infection_chance: 1.0, \
+        infection_hunger_multiplier: 3.0, species: { 0: (starting_count: 0), 1: (starting_count: 1) })";
+    let mut sim = Simulation::new(SimulationConfig::from_overrides(overrides).unwrap(), 3);
+    let wolf = sim.creatures()[0].entity;
+
+    // Healthy and infected, one step each from empty
+    let burn = |sim: &mut Simulation| {
+        sim.world_mut().get_mut::<Hunger>(wolf).unwrap().0 = 0.0;
+        sim.step();
+        sim.world().get::<Hunger>(wolf).unwrap().0
+    };
+    let healthy = burn(&mut sim);
+    sim.world_mut().entity_mut(wolf).insert(Infected(10.0));
+    let infected = burn(&mut sim);
+    assert!((infected / healthy - 3.0).abs() < 0.1, "{} vs {}", infected, healthy);
+    sim.world_mut().entity_mut(wolf).remove::<Infected>();
+
+    // Clean water is safe to drink, fully fouled water always infects
+    let lake: Vec<(Entity, (i32, i32))> = {
+        let world = sim.world_mut();
+        let mut q = world.query::<(Entity, &Tile, &Water)>();
+        q.iter(world).map(|(e, p, _)| (e, (p.x, p.y))).collect()
+    };
+    let drink = |sim: &mut Simulation| {
+        let mut pos = sim.world_mut().get_mut::<GridPosition>(wolf).unwrap();
+        (pos.x, pos.y) = lake[0].1;
+        sim.step();
+        sim.world().get::<Infected>(wolf).is_some()
+    };
+    assert!(!drink(&mut sim));
+    for (tile, _) in &lake {
+        sim.world_mut().entity_mut(*tile).insert(Pollution(1.0));
+    }
+    assert!(drink(&mut sim));
+}